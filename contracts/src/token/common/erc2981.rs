@@ -0,0 +1,488 @@
+//! Implementation of the NFT Royalty Standard, a standardized way to retrieve
+//! royalty payment information, as defined in the [ERC].
+//!
+//! Royalty information can be specified globally for all token ids via
+//! [`Erc2981::_set_default_royalty`], and/or individually for specific token
+//! ids via [`Erc2981::_set_token_royalty`]. The latter takes precedence over
+//! the first.
+//!
+//! Royalty is specified as a fraction of sale price.
+//! [`Erc2981::_fee_denominator`] defaults to [`DEFAULT_FEE_DENOMINATOR`],
+//! meaning the fee is specified in basis points by default, and can be changed
+//! through [`Erc2981::_set_fee_denominator`].
+//!
+//! IMPORTANT: ERC-2981 only specifies a way to signal royalty information and
+//! does not enforce its payment. See [Rationale] in the ERC. Marketplaces are
+//! expected to voluntarily pay royalties together with sales, but note that
+//! this standard is not yet widely supported.
+//!
+//! [ERC]: https://eips.ethereum.org/EIPS/eip-2981
+//! [Rationale]: https://eips.ethereum.org/EIPS/eip-2981#optional-royalty-payments
+
+use alloc::vec::Vec;
+
+use alloy_primitives::{uint, Address, FixedBytes, U256};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    prelude::storage,
+    storage::{StorageAddress, StorageMap, StorageUint},
+    stylus_proc::{public, SolidityError},
+};
+
+use crate::utils::{
+    introspection::erc165::{Erc165, IErc165},
+    math::alloy::{Math, Rounding},
+};
+
+type U96 = alloy_primitives::Uint<96, 2>;
+type StorageU96 = StorageUint<96, 2>;
+
+/// Default denominator of royalty fractions, so that fees are expressed in
+/// basis points.
+pub const DEFAULT_FEE_DENOMINATOR: U96 = uint!(10_000_U96);
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// The default royalty set is invalid (eg. (numerator / denominator) >= 1).
+        ///
+        /// * `numerator` - Royalty fraction.
+        /// * `denominator` - Fee denominator.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC2981InvalidDefaultRoyalty(uint256 numerator, uint256 denominator);
+
+        /// The default royalty receiver is invalid.
+        ///
+        /// * `receiver` - Address of the royalty receiver.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC2981InvalidDefaultRoyaltyReceiver(address receiver);
+
+        /// The royalty set for a specific `token_id` is invalid (eg.
+        /// (numerator / denominator) >= 1).
+        ///
+        /// * `token_id` - Token id as a number.
+        /// * `numerator` - Royalty fraction.
+        /// * `denominator` - Fee denominator.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC2981InvalidTokenRoyalty(uint256 token_id, uint256 numerator, uint256 denominator);
+
+        /// The royalty receiver for `token_id` is invalid.
+        ///
+        /// * `token_id` - Token id as a number.
+        /// * `receiver` - Address of the royalty receiver.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC2981InvalidTokenRoyaltyReceiver(uint256 token_id, address receiver);
+    }
+}
+
+/// An [`Erc2981`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// The default royalty set is invalid (eg. (numerator / denominator) >=
+    /// 1).
+    InvalidDefaultRoyalty(ERC2981InvalidDefaultRoyalty),
+    /// The default royalty receiver is invalid.
+    InvalidDefaultRoyaltyReceiver(ERC2981InvalidDefaultRoyaltyReceiver),
+    /// The royalty set for a specific `token_id` is invalid (eg. (numerator /
+    /// denominator) >= 1).
+    InvalidTokenRoyalty(ERC2981InvalidTokenRoyalty),
+    /// The royalty receiver for `token_id` is invalid.
+    InvalidTokenRoyaltyReceiver(ERC2981InvalidTokenRoyaltyReceiver),
+}
+
+/// Royalty receiver and fraction of a token, or of all tokens by default.
+#[storage]
+pub struct RoyaltyInfo {
+    /// Account receiving the royalty.
+    pub(crate) receiver: StorageAddress,
+    /// Royalty as a fraction of [`Erc2981::_fee_denominator`].
+    pub(crate) royalty_fraction: StorageU96,
+}
+
+/// State of an [`Erc2981`] contract.
+#[storage]
+pub struct Erc2981 {
+    /// Royalty that applies to every token without its own royalty.
+    pub(crate) default_royalty_info: RoyaltyInfo,
+    /// Mapping from token id to its royalty.
+    pub(crate) token_royalty_info: StorageMap<U256, RoyaltyInfo>,
+    /// Denominator of royalty fractions. [`DEFAULT_FEE_DENOMINATOR`] is used
+    /// while it is zero.
+    pub(crate) fee_denominator: StorageU96,
+}
+
+/// Interface for the NFT Royalty Standard.
+#[interface_id]
+pub trait IErc2981 {
+    /// Returns how much royalty is owed and to whom, based on a sale price
+    /// that may be denominated in any unit of exchange. The royalty amount
+    /// is denominated and should be paid in that same unit of exchange.
+    ///
+    /// NOTE: ERC-2981 allows setting the royalty to 100% of the price. In
+    /// that case all the price would be sent to the royalty receiver and 0
+    /// tokens to the seller. Contracts dealing with royalty should consider
+    /// empty transfers.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    /// * `sale_price` - Sale price of the token.
+    fn royalty_info(&self, token_id: U256, sale_price: U256)
+        -> (Address, U256);
+}
+
+#[public]
+impl IErc2981 for Erc2981 {
+    fn royalty_info(
+        &self,
+        token_id: U256,
+        sale_price: U256,
+    ) -> (Address, U256) {
+        let token_royalty = self.token_royalty_info.get(token_id);
+        let (receiver, royalty_fraction) = if token_royalty
+            .receiver
+            .get()
+            .is_zero()
+        {
+            (
+                self.default_royalty_info.receiver.get(),
+                self.default_royalty_info.royalty_fraction.get(),
+            )
+        } else {
+            (token_royalty.receiver.get(), token_royalty.royalty_fraction.get())
+        };
+
+        let royalty_amount = sale_price.mul_div(
+            U256::from(royalty_fraction),
+            U256::from(self._fee_denominator()),
+            Rounding::Floor,
+        );
+
+        (receiver, royalty_amount)
+    }
+}
+
+impl IErc165 for Erc2981 {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IErc2981>::INTERFACE_ID == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+impl Erc2981 {
+    /// The denominator with which to interpret the fee set in
+    /// [`Self::_set_token_royalty`] and [`Self::_set_default_royalty`] as a
+    /// fraction of the sale price.
+    ///
+    /// Defaults to [`DEFAULT_FEE_DENOMINATOR`], and can be changed with
+    /// [`Self::_set_fee_denominator`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn _fee_denominator(&self) -> U96 {
+        let fee_denominator = self.fee_denominator.get();
+        if fee_denominator.is_zero() {
+            DEFAULT_FEE_DENOMINATOR
+        } else {
+            fee_denominator
+        }
+    }
+
+    /// Sets the denominator with which royalty fractions are interpreted.
+    /// Setting it to zero restores [`DEFAULT_FEE_DENOMINATOR`].
+    ///
+    /// NOTE: Royalty fractions are stored as is, so this function should be
+    /// called during construction, before any royalty is set. Changing the
+    /// denominator afterwards changes the royalty of every token.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `fee_denominator` - Denominator of royalty fractions.
+    pub fn _set_fee_denominator(&mut self, fee_denominator: U96) {
+        self.fee_denominator.set(fee_denominator);
+    }
+
+    /// Sets the royalty information that all ids in this contract will
+    /// default to.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `receiver` - Address of the royalty receiver.
+    /// * `fee_numerator` - Royalty as a fraction of [`Self::_fee_denominator`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidDefaultRoyalty`] - If `fee_numerator` exceeds
+    ///   [`Self::_fee_denominator`].
+    /// * [`Error::InvalidDefaultRoyaltyReceiver`] - If `receiver` is
+    ///   `Address::ZERO`.
+    pub fn _set_default_royalty(
+        &mut self,
+        receiver: Address,
+        fee_numerator: U96,
+    ) -> Result<(), Error> {
+        let denominator = self._fee_denominator();
+        if fee_numerator > denominator {
+            return Err(Error::InvalidDefaultRoyalty(
+                ERC2981InvalidDefaultRoyalty {
+                    numerator: U256::from(fee_numerator),
+                    denominator: U256::from(denominator),
+                },
+            ));
+        }
+
+        if receiver.is_zero() {
+            return Err(Error::InvalidDefaultRoyaltyReceiver(
+                ERC2981InvalidDefaultRoyaltyReceiver {
+                    receiver: Address::ZERO,
+                },
+            ));
+        }
+
+        self.default_royalty_info.receiver.set(receiver);
+        self.default_royalty_info.royalty_fraction.set(fee_numerator);
+        Ok(())
+    }
+
+    /// Removes default royalty information.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    pub fn _delete_default_royalty(&mut self) {
+        self.default_royalty_info.receiver.set(Address::ZERO);
+        self.default_royalty_info.royalty_fraction.set(U96::ZERO);
+    }
+
+    /// Sets the royalty information for a specific token id, overriding the
+    /// global default.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    /// * `receiver` - Address of the royalty receiver.
+    /// * `fee_numerator` - Royalty as a fraction of [`Self::_fee_denominator`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidTokenRoyalty`] - If `fee_numerator` exceeds
+    ///   [`Self::_fee_denominator`].
+    /// * [`Error::InvalidTokenRoyaltyReceiver`] - If `receiver` is
+    ///   `Address::ZERO`.
+    pub fn _set_token_royalty(
+        &mut self,
+        token_id: U256,
+        receiver: Address,
+        fee_numerator: U96,
+    ) -> Result<(), Error> {
+        let denominator = self._fee_denominator();
+        if fee_numerator > denominator {
+            return Err(Error::InvalidTokenRoyalty(
+                ERC2981InvalidTokenRoyalty {
+                    token_id,
+                    numerator: U256::from(fee_numerator),
+                    denominator: U256::from(denominator),
+                },
+            ));
+        }
+
+        if receiver.is_zero() {
+            return Err(Error::InvalidTokenRoyaltyReceiver(
+                ERC2981InvalidTokenRoyaltyReceiver {
+                    token_id,
+                    receiver: Address::ZERO,
+                },
+            ));
+        }
+
+        let mut token_royalty = self.token_royalty_info.setter(token_id);
+        token_royalty.receiver.set(receiver);
+        token_royalty.royalty_fraction.set(fee_numerator);
+        Ok(())
+    }
+
+    /// Resets royalty information for the token id back to the global
+    /// default.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    pub fn _reset_token_royalty(&mut self, token_id: U256) {
+        let mut token_royalty = self.token_royalty_info.setter(token_id);
+        token_royalty.receiver.set(Address::ZERO);
+        token_royalty.royalty_fraction.set(U96::ZERO);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::TopLevelStorage;
+
+    use super::{Erc2981, Error, IErc2981, U96};
+    use crate::utils::introspection::erc165::IErc165;
+
+    unsafe impl TopLevelStorage for Erc2981 {}
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+    const OTHER_TOKEN_ID: U256 = uint!(2_U256);
+    const SALE_PRICE: U256 = uint!(1_000_U256);
+
+    #[motsu::test]
+    fn default_royalty_applies_to_all_tokens(
+        contract: Contract<Erc2981>,
+        alice: Address,
+        bob: Address,
+    ) {
+        assert_eq!(
+            (Address::ZERO, U256::ZERO),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+
+        contract
+            .sender(alice)
+            ._set_default_royalty(bob, uint!(500_U96))
+            .expect("should set the default royalty");
+
+        assert_eq!(
+            (bob, uint!(50_U256)),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+        assert_eq!(
+            (bob, uint!(50_U256)),
+            contract.sender(alice).royalty_info(OTHER_TOKEN_ID, SALE_PRICE)
+        );
+
+        contract.sender(alice)._delete_default_royalty();
+        assert_eq!(
+            (Address::ZERO, U256::ZERO),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+    }
+
+    #[motsu::test]
+    fn token_royalty_overrides_default(
+        contract: Contract<Erc2981>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._set_default_royalty(bob, uint!(500_U96))
+            .expect("should set the default royalty");
+        contract
+            .sender(alice)
+            ._set_token_royalty(TOKEN_ID, alice, uint!(1_000_U96))
+            .expect("should set the token royalty");
+
+        assert_eq!(
+            (alice, uint!(100_U256)),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+        assert_eq!(
+            (bob, uint!(50_U256)),
+            contract.sender(alice).royalty_info(OTHER_TOKEN_ID, SALE_PRICE)
+        );
+
+        contract.sender(alice)._reset_token_royalty(TOKEN_ID);
+        assert_eq!(
+            (bob, uint!(50_U256)),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+    }
+
+    #[motsu::test]
+    fn fee_denominator_can_be_changed(
+        contract: Contract<Erc2981>,
+        alice: Address,
+    ) {
+        assert_eq!(
+            uint!(10_000_U96),
+            contract.sender(alice)._fee_denominator()
+        );
+
+        contract.sender(alice)._set_fee_denominator(uint!(100_U96));
+        contract
+            .sender(alice)
+            ._set_default_royalty(alice, uint!(5_U96))
+            .expect("should set the default royalty");
+
+        assert_eq!(
+            (alice, uint!(50_U256)),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+
+        contract.sender(alice)._set_fee_denominator(U96::ZERO);
+        assert_eq!(
+            uint!(10_000_U96),
+            contract.sender(alice)._fee_denominator()
+        );
+    }
+
+    #[motsu::test]
+    fn error_when_royalty_is_invalid(
+        contract: Contract<Erc2981>,
+        alice: Address,
+    ) {
+        let too_high = uint!(10_001_U96);
+
+        let err = contract
+            .sender(alice)
+            ._set_default_royalty(alice, too_high)
+            .expect_err("should not exceed the fee denominator");
+        assert!(matches!(err, Error::InvalidDefaultRoyalty(_)));
+
+        let err = contract
+            .sender(alice)
+            ._set_default_royalty(Address::ZERO, U96::ZERO)
+            .expect_err("should not accept the zero address");
+        assert!(matches!(err, Error::InvalidDefaultRoyaltyReceiver(_)));
+
+        let err = contract
+            .sender(alice)
+            ._set_token_royalty(TOKEN_ID, alice, too_high)
+            .expect_err("should not exceed the fee denominator");
+        assert!(matches!(err, Error::InvalidTokenRoyalty(_)));
+
+        let err = contract
+            .sender(alice)
+            ._set_token_royalty(TOKEN_ID, Address::ZERO, U96::ZERO)
+            .expect_err("should not accept the zero address");
+        assert!(matches!(err, Error::InvalidTokenRoyaltyReceiver(_)));
+    }
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <Erc2981 as IErc2981>::INTERFACE_ID;
+        let expected = 0x2a55_205a;
+        assert_eq!(actual, expected);
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc2981::supports_interface(
+            (<Erc2981 as IErc2981>::INTERFACE_ID).into()
+        ));
+        assert!(Erc2981::supports_interface(
+            (<Erc2981 as IErc165>::INTERFACE_ID).into()
+        ));
+
+        let fake_interface_id = 0x1234_5678_u32;
+        assert!(!Erc2981::supports_interface(fake_interface_id.into()));
+    }
+}
@@ -0,0 +1,5 @@
+//! Components shared by several token standards.
+pub mod erc2981;
+pub mod token_uri;
+
+pub use erc2981::{Erc2981, IErc2981};
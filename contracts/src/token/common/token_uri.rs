@@ -0,0 +1,115 @@
+//! Helpers to build token metadata URIs fully on-chain.
+//!
+//! [`token_uri`] returns the metadata of a token as a
+//! `data:application/json;base64,...` URI, with its image embedded as a
+//! `data:image/svg+xml;base64,...` URI. The result can be returned as is from
+//! `Erc721Metadata::token_uri` or `Erc1155MetadataUri::uri` overrides, so that
+//! no off-chain storage is needed.
+use alloc::{format, string::String, vec::Vec};
+
+use crate::utils::{base64, json};
+
+/// An attribute of a token, as displayed by marketplaces.
+#[derive(Clone, Copy, Debug)]
+pub struct Attribute<'a> {
+    /// Name of the trait.
+    pub trait_type: &'a str,
+    /// Value of the trait.
+    pub value: &'a str,
+}
+
+/// Returns a `data:` URI of `media_type` with `data` encoded in Base64.
+///
+/// # Arguments
+///
+/// * `media_type` - Media type of `data`, e.g. `image/svg+xml`.
+/// * `data` - Content of the URI.
+#[must_use]
+pub fn data_uri(media_type: &str, data: impl AsRef<[u8]>) -> String {
+    format!("data:{media_type};base64,{}", base64::encode(data))
+}
+
+/// Returns the JSON metadata of a token as a Base64 `data:` URI, with `svg`
+/// embedded as its image.
+///
+/// Every string is escaped, so values can safely contain quotation marks or
+/// other special characters.
+///
+/// # Arguments
+///
+/// * `name` - Name of the token.
+/// * `description` - Description of the token.
+/// * `svg` - SVG document of the token's image.
+/// * `attributes` - Attributes of the token.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[selector(name = "tokenURI")]
+/// pub fn token_uri(&self, token_id: U256) -> Result<String, Vec<u8>> {
+///     self.erc721.owner_of(token_id)?;
+///     let svg = format!("<svg xmlns='http://www.w3.org/2000/svg'><text>{token_id}</text></svg>");
+///     Ok(token_uri(&format!("Token #{token_id}"), "On-chain art.", &svg, &[]))
+/// }
+/// ```
+#[must_use]
+pub fn token_uri(
+    name: &str,
+    description: &str,
+    svg: &str,
+    attributes: &[Attribute],
+) -> String {
+    let attributes: Vec<String> = attributes
+        .iter()
+        .map(|attribute| {
+            format!(
+                r#"{{"trait_type":"{}","value":"{}"}}"#,
+                json::escape(attribute.trait_type),
+                json::escape(attribute.value)
+            )
+        })
+        .collect();
+
+    let metadata = format!(
+        r#"{{"name":"{}","description":"{}","image":"{}","attributes":[{}]}}"#,
+        json::escape(name),
+        json::escape(description),
+        data_uri("image/svg+xml", svg),
+        attributes.join(",")
+    );
+
+    data_uri("application/json", metadata)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{data_uri, token_uri, Attribute};
+
+    #[test]
+    fn builds_data_uri() {
+        assert_eq!(
+            data_uri("text/plain", "foobar"),
+            "data:text/plain;base64,Zm9vYmFy"
+        );
+    }
+
+    #[test]
+    fn builds_token_uri() {
+        let svg = "<svg xmlns='http://www.w3.org/2000/svg'></svg>";
+        let uri = token_uri(
+            "Token \"1\"",
+            "On-chain",
+            svg,
+            &[
+                Attribute { trait_type: "Color", value: "Red" },
+                Attribute { trait_type: "Size", value: "1" },
+            ],
+        );
+
+        let expected = format!(
+            r#"{{"name":"Token \"1\"","description":"On-chain","image":"{}","attributes":[{{"trait_type":"Color","value":"Red"}},{{"trait_type":"Size","value":"1"}}]}}"#,
+            data_uri("image/svg+xml", svg)
+        );
+        assert_eq!(uri, data_uri("application/json", expected));
+    }
+}
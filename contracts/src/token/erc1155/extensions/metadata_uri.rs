@@ -3,13 +3,15 @@
 //!
 //! [ERC]: https://eips.ethereum.org/EIPS/eip-1155#metadata-extensions
 
-use alloc::{string::String, vec::Vec};
+use alloc::{format, string::String, vec::Vec};
 
 use alloy_primitives::{FixedBytes, U256};
 use openzeppelin_stylus_proc::interface_id;
 pub use sol::*;
 use stylus_sdk::{
-    prelude::storage, storage::StorageString, stylus_proc::public,
+    prelude::storage,
+    storage::{StorageBool, StorageString},
+    stylus_proc::public,
 };
 
 use crate::utils::introspection::erc165::{Erc165, IErc165};
@@ -37,6 +39,29 @@ pub struct Erc1155MetadataUri {
     /// Used as the URI for all token types by relying on ID substitution,
     /// e.g. https://token-cdn-domain/{id}.json.
     pub(crate) uri: StorageString,
+    /// Whether [`IErc1155MetadataUri::uri`] substitutes the token type ID for
+    /// the `{id}` placeholder instead of leaving it to clients.
+    pub(crate) substitute_id: StorageBool,
+}
+
+/// Placeholder that clients replace with the token type ID.
+const ID_PLACEHOLDER: &str = "{id}";
+
+/// Replaces every `{id}` placeholder in `uri` with `id`, as the lowercase
+/// hexadecimal representation zero-padded to 64 characters, which is the
+/// substitution that clients are required to do.
+///
+/// # Arguments
+///
+/// * `uri` - URI that may contain the `{id}` placeholder.
+/// * `id` - Token type ID.
+#[must_use]
+pub fn substitute_id(uri: &str, id: U256) -> String {
+    if uri.contains(ID_PLACEHOLDER) {
+        uri.replace(ID_PLACEHOLDER, &format!("{id:064x}"))
+    } else {
+        String::from(uri)
+    }
 }
 
 /// Interface for the optional metadata functions from the ERC-1155 standard.
@@ -59,8 +84,29 @@ impl IErc1155MetadataUri for Erc1155MetadataUri {
     /// This implementation returns the same URI for all token types.
     /// Clients calling this function must replace the `id` substring with
     /// the actual token type ID.
-    fn uri(&self, _id: U256) -> String {
-        self.uri.get_string()
+    ///
+    /// Once [`Erc1155MetadataUri::_set_id_substitution`] is enabled, the `{id}`
+    /// substring is replaced on-chain instead.
+    fn uri(&self, id: U256) -> String {
+        let uri = self.uri.get_string();
+        if self.substitute_id.get() {
+            substitute_id(&uri, id)
+        } else {
+            uri
+        }
+    }
+}
+
+impl Erc1155MetadataUri {
+    /// Sets whether [`IErc1155MetadataUri::uri`] substitutes the token type
+    /// ID for the `{id}` placeholder, so that it returns a fully resolved URI.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `enabled` - Whether to substitute the token type ID.
+    pub fn _set_id_substitution(&mut self, enabled: bool) {
+        self.substitute_id.set(enabled);
     }
 }
 
@@ -78,7 +124,9 @@ mod tests {
     use motsu::prelude::Contract;
     use stylus_sdk::{alloy_primitives::uint, prelude::TopLevelStorage};
 
-    use super::{Erc1155MetadataUri, IErc1155MetadataUri, IErc165};
+    use super::{
+        substitute_id, Erc1155MetadataUri, IErc1155MetadataUri, IErc165,
+    };
 
     unsafe impl TopLevelStorage for Erc1155MetadataUri {}
 
@@ -109,4 +157,29 @@ mod tests {
         let expected = 0x01ffc9a7;
         assert_eq!(actual, expected);
     }
+
+    #[motsu::test]
+    fn uri_substitutes_token_id_when_enabled(
+        contract: Contract<Erc1155MetadataUri>,
+        alice: Address,
+    ) {
+        contract.init(alice, |contract| {
+            contract.uri.set_str("https://token-cdn-domain/{id}.json");
+            contract._set_id_substitution(true);
+        });
+
+        assert_eq!(
+            format!("https://token-cdn-domain/{}4cce0.json", "0".repeat(59)),
+            contract.sender(alice).uri(uint!(314_592_U256))
+        );
+    }
+
+    #[test]
+    fn substitutes_every_placeholder() {
+        assert_eq!(
+            format!("{0}/{0}", "0".repeat(63) + "a"),
+            substitute_id("{id}/{id}", uint!(10_U256))
+        );
+        assert_eq!("https://uri", substitute_id("https://uri", uint!(10_U256)));
+    }
 }
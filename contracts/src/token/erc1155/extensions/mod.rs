@@ -1,10 +1,14 @@
 //! Common extensions to the ERC-1155 standard.
 pub mod burnable;
 pub mod metadata_uri;
+pub mod pausable;
+pub mod royalty;
 pub mod supply;
 pub mod uri_storage;
 
 pub use burnable::IErc1155Burnable;
 pub use metadata_uri::{Erc1155MetadataUri, IErc1155MetadataUri};
+pub use pausable::Erc1155Pausable;
+pub use royalty::Erc1155Royalty;
 pub use supply::{Erc1155Supply, IErc1155Supply};
 pub use uri_storage::Erc1155UriStorage;
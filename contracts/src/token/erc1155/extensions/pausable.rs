@@ -0,0 +1,433 @@
+//! ERC-1155 token with pausable token transfers, minting, and burning.
+//!
+//! Useful for scenarios such as preventing trades until the end of an
+//! evaluation period, or having an emergency switch for freezing all token
+//! transfers in the event of a large bug.
+//!
+//! Every path through [`Erc1155Pausable::_update`], including batch
+//! operations, reverts while the contract is paused.
+//!
+//! NOTE: [`Pausable::pause`] and [`Pausable::unpause`] are not exposed by
+//! default. You should expose them manually in your contract's abi, with the
+//! access control of your choice.
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use stylus_sdk::{
+    abi::Bytes,
+    msg,
+    prelude::{storage, TopLevelStorage},
+    stylus_proc::{public, SolidityError},
+};
+
+use crate::{
+    token::erc1155::{self, extensions::IErc1155Burnable, Erc1155, IErc1155},
+    utils::{
+        introspection::erc165::IErc165,
+        pausable::{self, Pausable},
+    },
+};
+
+/// An [`Erc1155Pausable`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Error type from [`Erc1155`] contract [`erc1155::Error`].
+    Erc1155(erc1155::Error),
+    /// Error type from [`Pausable`] contract [`pausable::Error`].
+    Pausable(pausable::Error),
+}
+
+/// State of an [`Erc1155Pausable`] token.
+#[storage]
+pub struct Erc1155Pausable {
+    /// [`Erc1155`] contract.
+    pub erc1155: Erc1155,
+    /// [`Pausable`] contract.
+    pub pausable: Pausable,
+}
+
+impl Deref for Erc1155Pausable {
+    type Target = Erc1155;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc1155
+    }
+}
+
+impl DerefMut for Erc1155Pausable {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc1155
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc1155Pausable {}
+
+#[public]
+impl IErc1155 for Erc1155Pausable {
+    type Error = Error;
+
+    fn balance_of(&self, account: Address, id: U256) -> U256 {
+        self.erc1155.balance_of(account, id)
+    }
+
+    fn balance_of_batch(
+        &self,
+        accounts: Vec<Address>,
+        ids: Vec<U256>,
+    ) -> Result<Vec<U256>, Error> {
+        Ok(self.erc1155.balance_of_batch(accounts, ids)?)
+    }
+
+    fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Error> {
+        Ok(self.erc1155.set_approval_for_all(operator, approved)?)
+    }
+
+    fn is_approved_for_all(&self, account: Address, operator: Address) -> bool {
+        self.erc1155.is_approved_for_all(account, operator)
+    }
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        self.erc1155.authorize_transfer(from)?;
+        self.do_safe_transfer_from(from, to, vec![id], vec![value], &data)
+    }
+
+    fn safe_batch_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        self.erc1155.authorize_transfer(from)?;
+        self.do_safe_transfer_from(from, to, ids, values, &data)
+    }
+}
+
+impl IErc1155Burnable for Erc1155Pausable {
+    type Error = Error;
+
+    fn burn(
+        &mut self,
+        account: Address,
+        token_id: U256,
+        value: U256,
+    ) -> Result<(), Error> {
+        self.pausable.when_not_paused()?;
+        Ok(self.erc1155.burn(account, token_id, value)?)
+    }
+
+    fn burn_batch(
+        &mut self,
+        account: Address,
+        token_ids: Vec<U256>,
+        values: Vec<U256>,
+    ) -> Result<(), Error> {
+        self.pausable.when_not_paused()?;
+        Ok(self.erc1155.burn_batch(account, token_ids, values)?)
+    }
+}
+
+impl IErc165 for Erc1155Pausable {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        Erc1155::supports_interface(interface_id)
+    }
+}
+
+impl Erc1155Pausable {
+    /// Creates a `value` amount of tokens of type `id`, and assigns
+    /// them to `to`.
+    ///
+    /// Re-export of [`Erc1155::_mint`] that reverts while paused.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint(
+        &mut self,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        self._do_mint(to, vec![id], vec![value], data)
+    }
+
+    /// Batched version of [`Self::_mint`].
+    ///
+    /// Re-export of [`Erc1155::_mint_batch`] that reverts while paused.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint_batch(
+        &mut self,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        self._do_mint(to, ids, values, data)
+    }
+
+    /// Destroys a `value` amount of tokens of type `id` from `from`.
+    ///
+    /// Re-export of [`Erc1155::_burn`] that reverts while paused.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(
+        &mut self,
+        from: Address,
+        id: U256,
+        value: U256,
+    ) -> Result<(), Error> {
+        self._do_burn(from, vec![id], vec![value])
+    }
+
+    /// Batched version of [`Self::_burn`].
+    ///
+    /// Re-export of [`Erc1155::_burn_batch`] that reverts while paused.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn_batch(
+        &mut self,
+        from: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+    ) -> Result<(), Error> {
+        self._do_burn(from, ids, values)
+    }
+}
+
+impl Erc1155Pausable {
+    /// Extended version of [`Erc1155::_update`] that reverts while the
+    /// contract is paused.
+    ///
+    /// NOTE: The ERC-1155 acceptance check is not performed in this function.
+    /// See [`Self::_update_with_acceptance_check`] instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account of the sender.
+    /// * `to` - Account of the recipient.
+    /// * `token_ids` - Array of all token id.
+    /// * `values` - Array of all amount of tokens to be transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`pausable::Error::EnforcedPause`] - If the contract is paused.
+    /// * [`erc1155::Error::InvalidArrayLength`] - If length of `ids` is not
+    ///   equal to length of `values`.
+    /// * [`erc1155::Error::InsufficientBalance`] - If `value` is greater than
+    ///   the balance of the `from` account.
+    ///
+    /// # Events
+    ///
+    /// * [`erc1155::TransferSingle`] - If the arrays contain one element.
+    /// * [`erc1155::TransferBatch`] - If the arrays contain more than one
+    ///   element.
+    ///
+    /// # Panics
+    ///
+    /// * If updated balance exceeds `U256::MAX`, may happen during the `mint`
+    ///   operation.
+    fn _update(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_ids: Vec<U256>,
+        values: Vec<U256>,
+    ) -> Result<(), Error> {
+        self.pausable.when_not_paused()?;
+        Ok(self.erc1155._update(from, to, token_ids, values)?)
+    }
+
+    fn _update_with_acceptance_check(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        self._update(from, to, ids.clone(), values.clone())?;
+
+        if !to.is_zero() {
+            self.erc1155._check_on_erc1155_received(
+                msg::sender(),
+                from,
+                to,
+                erc1155::Erc1155ReceiverData::new(ids, values),
+                data.to_vec().into(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn _do_mint(
+        &mut self,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc1155::Error::InvalidReceiver(
+                erc1155::ERC1155InvalidReceiver { receiver: to },
+            )
+            .into());
+        }
+        self._update_with_acceptance_check(Address::ZERO, to, ids, values, data)
+    }
+
+    fn _do_burn(
+        &mut self,
+        from: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+    ) -> Result<(), Error> {
+        if from.is_zero() {
+            return Err(erc1155::Error::InvalidSender(
+                erc1155::ERC1155InvalidSender { sender: from },
+            )
+            .into());
+        }
+        self._update_with_acceptance_check(
+            from,
+            Address::ZERO,
+            ids,
+            values,
+            &vec![].into(),
+        )
+    }
+
+    fn do_safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc1155::Error::InvalidReceiver(
+                erc1155::ERC1155InvalidReceiver { receiver: to },
+            )
+            .into());
+        }
+        if from.is_zero() {
+            return Err(erc1155::Error::InvalidSender(
+                erc1155::ERC1155InvalidSender { sender: from },
+            )
+            .into());
+        }
+        self._update_with_acceptance_check(from, to, ids, values, data)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+
+    use super::{Erc1155Pausable, Error};
+    use crate::{
+        token::erc1155::{extensions::IErc1155Burnable, IErc1155},
+        utils::pausable,
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+    const OTHER_TOKEN_ID: U256 = uint!(2_U256);
+    const VALUE: U256 = uint!(10_U256);
+
+    #[motsu::test]
+    fn every_update_reverts_when_paused(
+        contract: Contract<Erc1155Pausable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._mint(alice, TOKEN_ID, VALUE, &vec![].into())
+            .expect("should mint");
+        contract.sender(alice).pausable.pause().expect("should pause");
+
+        let err = contract
+            .sender(alice)
+            ._mint_batch(
+                alice,
+                vec![TOKEN_ID, OTHER_TOKEN_ID],
+                vec![VALUE, VALUE],
+                &vec![].into(),
+            )
+            .expect_err("should not mint while paused");
+        assert!(matches!(
+            err,
+            Error::Pausable(pausable::Error::EnforcedPause(_))
+        ));
+
+        let err = contract
+            .sender(alice)
+            .safe_transfer_from(alice, bob, TOKEN_ID, VALUE, vec![].into())
+            .expect_err("should not transfer while paused");
+        assert!(matches!(
+            err,
+            Error::Pausable(pausable::Error::EnforcedPause(_))
+        ));
+
+        let err = contract
+            .sender(alice)
+            .safe_batch_transfer_from(
+                alice,
+                bob,
+                vec![TOKEN_ID],
+                vec![VALUE],
+                vec![].into(),
+            )
+            .expect_err("should not transfer a batch while paused");
+        assert!(matches!(
+            err,
+            Error::Pausable(pausable::Error::EnforcedPause(_))
+        ));
+
+        let err = contract
+            .sender(alice)
+            .burn(alice, TOKEN_ID, VALUE)
+            .expect_err("should not burn while paused");
+        assert!(matches!(
+            err,
+            Error::Pausable(pausable::Error::EnforcedPause(_))
+        ));
+
+        assert_eq!(VALUE, contract.sender(alice).balance_of(alice, TOKEN_ID));
+    }
+
+    #[motsu::test]
+    fn transfers_after_unpause(
+        contract: Contract<Erc1155Pausable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._mint(alice, TOKEN_ID, VALUE, &vec![].into())
+            .expect("should mint");
+        contract.sender(alice).pausable.pause().expect("should pause");
+        contract.sender(alice).pausable.unpause().expect("should unpause");
+
+        contract
+            .sender(alice)
+            .safe_transfer_from(alice, bob, TOKEN_ID, VALUE, vec![].into())
+            .expect("should transfer");
+        assert_eq!(VALUE, contract.sender(alice).balance_of(bob, TOKEN_ID));
+    }
+}
@@ -0,0 +1,356 @@
+//! Extension of ERC-1155 with the ERC-2981 NFT Royalty Standard, a
+//! standardized way to retrieve royalty payment information.
+//!
+//! Royalty information can be specified globally for all token ids via
+//! [`Erc2981::_set_default_royalty`], and/or individually for specific token
+//! ids via [`Erc2981::_set_token_royalty`].
+//!
+//! The extension builds on [`Erc1155Supply`], so that the royalty of a token
+//! id is cleared once its whole supply is burned.
+//!
+//! IMPORTANT: ERC-2981 only specifies a way to signal royalty information and
+//! does not enforce its payment.
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use stylus_sdk::{
+    abi::Bytes,
+    prelude::{storage, TopLevelStorage},
+    stylus_proc::public,
+};
+
+use crate::{
+    token::{
+        common::erc2981::{Erc2981, IErc2981},
+        erc1155::{
+            self,
+            extensions::{Erc1155Supply, IErc1155Supply},
+            Erc1155, IErc1155,
+        },
+    },
+    utils::introspection::erc165::IErc165,
+};
+
+/// State of an [`Erc1155Royalty`] token.
+#[storage]
+pub struct Erc1155Royalty {
+    /// [`Erc1155Supply`] contract.
+    pub erc1155_supply: Erc1155Supply,
+    /// [`Erc2981`] contract.
+    pub erc2981: Erc2981,
+}
+
+impl Deref for Erc1155Royalty {
+    type Target = Erc1155Supply;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc1155_supply
+    }
+}
+
+impl DerefMut for Erc1155Royalty {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc1155_supply
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc1155Royalty {}
+
+/// ABI of an [`Erc1155Royalty`] token: the [`IErc1155`] functions together
+/// with [`IErc2981::royalty_info`], since a contract can only export a single
+/// `#[public]` block.
+#[public]
+impl Erc1155Royalty {
+    /// Re-export of [`IErc1155::balance_of`].
+    pub fn balance_of(&self, account: Address, id: U256) -> U256 {
+        self.erc1155_supply.balance_of(account, id)
+    }
+
+    /// Re-export of [`IErc1155::balance_of_batch`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn balance_of_batch(
+        &self,
+        accounts: Vec<Address>,
+        ids: Vec<U256>,
+    ) -> Result<Vec<U256>, erc1155::Error> {
+        self.erc1155_supply.balance_of_batch(accounts, ids)
+    }
+
+    /// Re-export of [`IErc1155::set_approval_for_all`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), erc1155::Error> {
+        self.erc1155_supply.set_approval_for_all(operator, approved)
+    }
+
+    /// Re-export of [`IErc1155::is_approved_for_all`].
+    pub fn is_approved_for_all(
+        &self,
+        account: Address,
+        operator: Address,
+    ) -> bool {
+        self.erc1155_supply.is_approved_for_all(account, operator)
+    }
+
+    /// Re-export of [`IErc1155::safe_transfer_from`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) -> Result<(), erc1155::Error> {
+        self.erc1155_supply.safe_transfer_from(from, to, id, value, data)
+    }
+
+    /// Re-export of [`IErc1155::safe_batch_transfer_from`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn safe_batch_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) -> Result<(), erc1155::Error> {
+        self.erc1155_supply
+            .safe_batch_transfer_from(from, to, ids, values, data)
+    }
+
+    /// Re-export of [`IErc2981::royalty_info`].
+    pub fn royalty_info(
+        &self,
+        token_id: U256,
+        sale_price: U256,
+    ) -> (Address, U256) {
+        self.erc2981.royalty_info(token_id, sale_price)
+    }
+}
+
+impl IErc1155 for Erc1155Royalty {
+    type Error = erc1155::Error;
+
+    fn balance_of(&self, account: Address, id: U256) -> U256 {
+        self.erc1155_supply.balance_of(account, id)
+    }
+
+    fn balance_of_batch(
+        &self,
+        accounts: Vec<Address>,
+        ids: Vec<U256>,
+    ) -> Result<Vec<U256>, erc1155::Error> {
+        self.erc1155_supply.balance_of_batch(accounts, ids)
+    }
+
+    fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), erc1155::Error> {
+        self.erc1155_supply.set_approval_for_all(operator, approved)
+    }
+
+    fn is_approved_for_all(&self, account: Address, operator: Address) -> bool {
+        self.erc1155_supply.is_approved_for_all(account, operator)
+    }
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) -> Result<(), erc1155::Error> {
+        self.erc1155_supply.safe_transfer_from(from, to, id, value, data)
+    }
+
+    fn safe_batch_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) -> Result<(), erc1155::Error> {
+        self.erc1155_supply
+            .safe_batch_transfer_from(from, to, ids, values, data)
+    }
+}
+
+impl IErc2981 for Erc1155Royalty {
+    fn royalty_info(
+        &self,
+        token_id: U256,
+        sale_price: U256,
+    ) -> (Address, U256) {
+        self.erc2981.royalty_info(token_id, sale_price)
+    }
+}
+
+impl IErc165 for Erc1155Royalty {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        Erc2981::supports_interface(interface_id)
+            || Erc1155::supports_interface(interface_id)
+    }
+}
+
+impl Erc1155Royalty {
+    /// Destroys a `value` amount of tokens of type `id` from `from`, and
+    /// clears the royalty information of `id` once none of it is left.
+    ///
+    /// Re-export of [`Erc1155Supply::_burn`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(
+        &mut self,
+        from: Address,
+        id: U256,
+        value: U256,
+    ) -> Result<(), erc1155::Error> {
+        self.erc1155_supply._burn(from, id, value)?;
+        self._reset_burned_royalty(id);
+        Ok(())
+    }
+
+    /// Batched version of [`Self::_burn`].
+    ///
+    /// Re-export of [`Erc1155Supply::_burn_batch`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn_batch(
+        &mut self,
+        from: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+    ) -> Result<(), erc1155::Error> {
+        self.erc1155_supply._burn_batch(from, ids.clone(), values)?;
+        for id in ids {
+            self._reset_burned_royalty(id);
+        }
+        Ok(())
+    }
+
+    /// Clears the royalty information of `id` if its supply is zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `id` - Token id as a number.
+    fn _reset_burned_royalty(&mut self, id: U256) {
+        if !self.erc1155_supply.exists(id) {
+            self.erc2981._reset_token_royalty(id);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+
+    use super::Erc1155Royalty;
+    use crate::{
+        token::erc1155::{Erc1155, IErc1155},
+        utils::introspection::erc165::IErc165,
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+    const OTHER_TOKEN_ID: U256 = uint!(2_U256);
+    const SALE_PRICE: U256 = uint!(1_000_U256);
+
+    #[motsu::test]
+    fn burn_clears_royalty_when_supply_is_gone(
+        contract: Contract<Erc1155Royalty>,
+        alice: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._mint(alice, TOKEN_ID, uint!(10_U256), &vec![].into())
+            .expect("should mint");
+        contract
+            .sender(alice)
+            .erc2981
+            ._set_token_royalty(TOKEN_ID, alice, uint!(500_U96))
+            .expect("should set the token royalty");
+
+        contract
+            .sender(alice)
+            ._burn(alice, TOKEN_ID, uint!(4_U256))
+            .expect("should burn");
+        assert_eq!(
+            (alice, uint!(50_U256)),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+
+        contract
+            .sender(alice)
+            ._burn(alice, TOKEN_ID, uint!(6_U256))
+            .expect("should burn");
+        assert_eq!(
+            (Address::ZERO, U256::ZERO),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+    }
+
+    #[motsu::test]
+    fn burn_batch_clears_royalty_when_supply_is_gone(
+        contract: Contract<Erc1155Royalty>,
+        alice: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._mint_batch(
+                alice,
+                vec![TOKEN_ID, OTHER_TOKEN_ID],
+                vec![uint!(1_U256), uint!(2_U256)],
+                &vec![].into(),
+            )
+            .expect("should mint");
+        for id in [TOKEN_ID, OTHER_TOKEN_ID] {
+            contract
+                .sender(alice)
+                .erc2981
+                ._set_token_royalty(id, alice, uint!(500_U96))
+                .expect("should set the token royalty");
+        }
+
+        contract
+            .sender(alice)
+            ._burn_batch(
+                alice,
+                vec![TOKEN_ID, OTHER_TOKEN_ID],
+                vec![uint!(1_U256), uint!(1_U256)],
+            )
+            .expect("should burn");
+
+        assert_eq!(
+            (Address::ZERO, U256::ZERO),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+        assert_eq!(
+            (alice, uint!(50_U256)),
+            contract.sender(alice).royalty_info(OTHER_TOKEN_ID, SALE_PRICE)
+        );
+        assert_eq!(
+            uint!(1_U256),
+            contract.sender(alice).balance_of(alice, OTHER_TOKEN_ID)
+        );
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc1155Royalty::supports_interface(0x2a55_205a_u32.into()));
+        assert!(Erc1155Royalty::supports_interface(
+            (<Erc1155 as IErc1155>::INTERFACE_ID).into()
+        ));
+        assert!(!Erc1155Royalty::supports_interface(0x1234_5678_u32.into()));
+    }
+}
@@ -10,7 +10,7 @@ use stylus_sdk::{
     storage::{StorageMap, StorageString},
 };
 
-use super::metadata_uri::{IErc1155MetadataUri, URI};
+use super::metadata_uri::{substitute_id, IErc1155MetadataUri, URI};
 
 /// State of an [`Erc1155UriStorage`] contract.
 #[storage]
@@ -24,6 +24,10 @@ pub struct Erc1155UriStorage {
 impl Erc1155UriStorage {
     /// Returns the Uniform Resource Identifier (URI) for `token_id` token.
     ///
+    /// A per-token URI is resolved by substituting `token_id` for any `{id}`
+    /// placeholder, following the same rule as
+    /// [`super::Erc1155MetadataUri::_set_id_substitution`].
+    ///
     /// NOTE: To expose this function in your contract's ABI, implement it as
     /// shown in the Examples section below, accepting only the `token_id`
     /// parameter. The `metadata_uri` reference should come from your contract's
@@ -54,7 +58,7 @@ impl Erc1155UriStorage {
         if token_uri.is_empty() {
             metadata_uri.uri(token_id)
         } else {
-            self.base_uri.get_string() + &token_uri
+            substitute_id(&(self.base_uri.get_string() + &token_uri), token_id)
         }
     }
 
@@ -235,4 +239,24 @@ mod tests {
             contract.sender(alice).uri_storage.base_uri.get_string()
         );
     }
+
+    #[motsu::test]
+    fn uri_substitutes_token_id_in_token_uri(
+        contract: Contract<Erc1155MetadataExample>,
+        alice: Address,
+    ) {
+        contract.init(alice, |contract| {
+            contract.uri_storage.base_uri.set_str("https://some.base.uri/");
+            contract
+                .uri_storage
+                .token_uris
+                .setter(TOKEN_ID)
+                .set_str("{id}.json");
+        });
+
+        assert_eq!(
+            format!("https://some.base.uri/{}1.json", "0".repeat(63)),
+            contract.sender(alice).uri(TOKEN_ID)
+        );
+    }
 }
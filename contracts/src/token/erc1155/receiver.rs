@@ -2,9 +2,11 @@
 //! ERC-1155 token transfers.
 #![allow(missing_docs)]
 #![cfg_attr(coverage_nightly, coverage(off))]
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
-use stylus_sdk::stylus_proc::sol_interface;
+use alloy_primitives::{Address, FixedBytes, U256};
+use openzeppelin_stylus_proc::interface_id;
+use stylus_sdk::{abi::Bytes, stylus_proc::sol_interface};
 
 sol_interface! {
     /// [`super::Erc1155`] token receiver interface.
@@ -67,3 +69,56 @@ sol_interface! {
         ) external returns (bytes4);
     }
 }
+
+/// Interface for any contract that wants to support transfers from
+/// [`super::Erc1155`] asset contracts, as the Rust counterpart of
+/// [`IERC1155Receiver`].
+#[interface_id]
+pub trait IErc1155Receiver {
+    /// Handles the receipt of a single [`super::Erc1155`] token type.
+    ///
+    /// It must return [`super::SINGLE_TRANSFER_FN_SELECTOR`] to confirm the
+    /// token transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `operator` - Account which initiated the transfer.
+    /// * `from` - Account which previously owned the token.
+    /// * `id` - Id of the token being transferred.
+    /// * `value` - Amount of tokens being transferred.
+    /// * `data` - Additional data with no specified format.
+    #[selector(name = "onERC1155Received")]
+    fn on_erc1155_received(
+        &mut self,
+        operator: Address,
+        from: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) -> FixedBytes<4>;
+
+    /// Handles the receipt of multiple [`super::Erc1155`] token types.
+    ///
+    /// It must return [`super::BATCH_TRANSFER_FN_SELECTOR`] to confirm the
+    /// token transfers.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `operator` - Account which initiated the batch transfer.
+    /// * `from` - Account which previously owned the tokens.
+    /// * `ids` - Ids of the tokens being transferred.
+    /// * `values` - Amounts of tokens being transferred, in the same order as
+    ///   `ids`.
+    /// * `data` - Additional data with no specified format.
+    #[selector(name = "onERC1155BatchReceived")]
+    fn on_erc1155_batch_received(
+        &mut self,
+        operator: Address,
+        from: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) -> FixedBytes<4>;
+}
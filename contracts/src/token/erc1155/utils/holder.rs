@@ -0,0 +1,137 @@
+//! Implementation of the [`IErc1155Receiver`] trait.
+//!
+//! Accepts all single and batch token transfers. Make sure the contract is
+//! able to use its tokens with
+//! [`crate::token::erc1155::IErc1155::safe_transfer_from`],
+//! [`crate::token::erc1155::IErc1155::safe_batch_transfer_from`] or
+//! [`crate::token::erc1155::IErc1155::set_approval_for_all`], otherwise the
+//! received tokens will be locked forever.
+
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use stylus_sdk::{
+    abi::Bytes,
+    prelude::{public, storage},
+};
+
+use crate::{
+    token::erc1155::{
+        IErc1155Receiver, BATCH_TRANSFER_FN_SELECTOR,
+        SINGLE_TRANSFER_FN_SELECTOR,
+    },
+    utils::introspection::erc165::{Erc165, IErc165},
+};
+
+/// State of an [`Erc1155Holder`] contract.
+#[storage]
+pub struct Erc1155Holder {}
+
+#[public]
+impl IErc1155Receiver for Erc1155Holder {
+    #[selector(name = "onERC1155Received")]
+    fn on_erc1155_received(
+        &mut self,
+        _operator: Address,
+        _from: Address,
+        _id: U256,
+        _value: U256,
+        _data: Bytes,
+    ) -> FixedBytes<4> {
+        SINGLE_TRANSFER_FN_SELECTOR.into()
+    }
+
+    #[selector(name = "onERC1155BatchReceived")]
+    fn on_erc1155_batch_received(
+        &mut self,
+        _operator: Address,
+        _from: Address,
+        _ids: Vec<U256>,
+        _values: Vec<U256>,
+        _data: Bytes,
+    ) -> FixedBytes<4> {
+        BATCH_TRANSFER_FN_SELECTOR.into()
+    }
+}
+
+impl IErc165 for Erc1155Holder {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IErc1155Receiver>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{fixed_bytes, uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::TopLevelStorage;
+
+    use super::{Erc1155Holder, IErc1155Receiver};
+    use crate::{
+        token::erc1155::{Erc1155, IErc1155},
+        utils::introspection::erc165::IErc165,
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+    const VALUE: U256 = uint!(10_U256);
+
+    unsafe impl TopLevelStorage for Erc1155Holder {}
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <Erc1155Holder as IErc1155Receiver>::INTERFACE_ID;
+        let expected = 0x4e23_12e0;
+        assert_eq!(actual, expected);
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc1155Holder::supports_interface(fixed_bytes!("4e2312e0")));
+        assert!(Erc1155Holder::supports_interface(fixed_bytes!("01ffc9a7")));
+        assert!(!Erc1155Holder::supports_interface(fixed_bytes!("d9b67a26")));
+    }
+
+    #[motsu::test]
+    fn receives_mint(
+        erc1155: Contract<Erc1155>,
+        holder: Contract<Erc1155Holder>,
+        alice: Address,
+    ) {
+        erc1155
+            .sender(alice)
+            ._mint(holder.address(), TOKEN_ID, VALUE, &vec![].into())
+            .expect("should accept the tokens");
+
+        let balance =
+            erc1155.sender(alice).balance_of(holder.address(), TOKEN_ID);
+        assert_eq!(balance, VALUE);
+    }
+
+    #[motsu::test]
+    fn receives_batch_mint(
+        erc1155: Contract<Erc1155>,
+        holder: Contract<Erc1155Holder>,
+        alice: Address,
+    ) {
+        let ids = vec![TOKEN_ID, uint!(2_U256)];
+        let values = vec![VALUE, uint!(20_U256)];
+
+        erc1155
+            .sender(alice)
+            ._mint_batch(
+                holder.address(),
+                ids.clone(),
+                values.clone(),
+                &vec![].into(),
+            )
+            .expect("should accept the tokens");
+
+        for (id, value) in ids.into_iter().zip(values) {
+            let balance =
+                erc1155.sender(alice).balance_of(holder.address(), id);
+            assert_eq!(balance, value);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+//! Utilities for the ERC-1155 standard.
+pub mod holder;
+
+pub use holder::Erc1155Holder;
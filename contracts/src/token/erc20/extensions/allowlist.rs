@@ -0,0 +1,737 @@
+//! Allowlist Contract.
+//!
+//! Extension of the ERC-20 standard that only lets allowed accounts send,
+//! receive or approve tokens. Accounts are disallowed by default.
+//!
+//! Accounts are managed through [`Erc20Allowlist::_allow_user`] and
+//! [`Erc20Allowlist::_disallow_user`], which are not exposed by default.
+//! You should expose them manually in your contract's abi, protected by an
+//! access control mechanism such as [`crate::access::ownable::Ownable`].
+//!
+//! NOTE: Restricting who can send or receive tokens is not a
+//! decentralization-friendly feature, and disallowing an account does not
+//! revoke the allowances it already granted.
+
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, U256};
+pub use sol::*;
+use stylus_sdk::{
+    evm, msg,
+    prelude::storage,
+    storage::{StorageBool, StorageMap},
+    stylus_proc::{public, SolidityError},
+};
+
+use crate::token::erc20::{
+    self, extensions::IErc20Burnable, ERC20InvalidReceiver, ERC20InvalidSender,
+    Erc20, IErc20,
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when `user` is allowed to transfer and approve tokens.
+        #[allow(missing_docs)]
+        event UserAllowed(address indexed user);
+
+        /// Emitted when `user` is disallowed to transfer and approve tokens.
+        #[allow(missing_docs)]
+        event UserDisallowed(address indexed user);
+    }
+
+    sol! {
+        /// Indicates that `user` is not allowed to transfer or approve
+        /// tokens.
+        ///
+        /// * `user` - Address of the disallowed account.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC20Disallowed(address user);
+    }
+}
+
+/// An [`Erc20Allowlist`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates that an account is not allowed to transfer or approve
+    /// tokens.
+    Disallowed(ERC20Disallowed),
+    /// Error type from [`Erc20`] contract [`erc20::Error`].
+    Erc20(erc20::Error),
+}
+
+/// State of an [`Erc20Allowlist`] Contract.
+#[storage]
+pub struct Erc20Allowlist {
+    /// [`Erc20`] contract.
+    // The parent ERC-20 contract instance is not public, so that inheriting
+    // contracts cannot bypass the checks of this extension by calling its
+    // internal functions directly.
+    pub(crate) erc20: Erc20,
+    /// Maps accounts to whether they are allowed.
+    pub(crate) allowed: StorageMap<Address, StorageBool>,
+}
+
+#[public]
+impl Erc20Allowlist {
+    /// Returns whether `account` is allowed to transfer and approve tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to check.
+    pub fn allowed(&self, account: Address) -> bool {
+        self.allowed.get(account)
+    }
+
+    /// Returns the number of tokens in existence.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn total_supply(&self) -> U256 {
+        self.erc20.total_supply()
+    }
+
+    /// Returns the number of tokens owned by `account`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get balance from.
+    pub fn balance_of(&self, account: Address) -> U256 {
+        self.erc20.balance_of(account)
+    }
+
+    /// Moves a `value` amount of tokens from the caller's account to `to`.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - Number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Disallowed`] - If the caller or `to` is not allowed.
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientBalance`] - If the caller doesn't have a
+    ///   balance of at least `value`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    pub fn transfer(
+        &mut self,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Error> {
+        let from = msg::sender();
+        self._transfer(from, to, value)?;
+        Ok(true)
+    }
+
+    /// Returns the remaining number of tokens that `spender` will be allowed
+    /// to spend on behalf of `owner` through `transfer_from`. This is zero by
+    /// default.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account that owns the tokens.
+    /// * `spender` - Account that will spend the tokens.
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.erc20.allowance(owner, spender)
+    }
+
+    /// Sets a `value` number of tokens as the allowance of `spender` over the
+    /// caller's tokens.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `spender` - Account that will spend the tokens.
+    /// * `value` - The number of tokens being allowed to transfer by `spender`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Disallowed`] - If the caller is not allowed.
+    /// * [`erc20::Error::InvalidSpender`] - If the `spender` address is
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Approval`]
+    pub fn approve(
+        &mut self,
+        spender: Address,
+        value: U256,
+    ) -> Result<bool, Error> {
+        let owner = msg::sender();
+        self._approve(owner, spender, value, true)
+    }
+
+    /// Moves a `value` number of tokens from `from` to `to` using the
+    /// allowance mechanism. `value` is then deducted from the caller's
+    /// allowance.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account to transfer tokens from.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - Number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Disallowed`] - If `from` or `to` is not allowed.
+    /// * [`erc20::Error::InvalidSender`] - If the `from` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientAllowance`] - If not enough allowance is
+    ///   available.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Error> {
+        let spender = msg::sender();
+        self.erc20._spend_allowance(from, spender, value)?;
+        self._transfer(from, to, value)?;
+        Ok(true)
+    }
+}
+
+impl Erc20Allowlist {
+    /// Allows `user` to transfer and approve tokens.
+    ///
+    /// Returns whether the status of `user` changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `user` - Account to allow.
+    ///
+    /// # Events
+    ///
+    /// * [`UserAllowed`] - If `user` was not allowed before.
+    pub fn _allow_user(&mut self, user: Address) -> bool {
+        if self.allowed.get(user) {
+            return false;
+        }
+        self.allowed.setter(user).set(true);
+        evm::log(UserAllowed { user });
+        true
+    }
+
+    /// Disallows `user` to transfer and approve tokens.
+    ///
+    /// Returns whether the status of `user` changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `user` - Account to disallow.
+    ///
+    /// # Events
+    ///
+    /// * [`UserDisallowed`] - If `user` was allowed before.
+    pub fn _disallow_user(&mut self, user: Address) -> bool {
+        if !self.allowed.get(user) {
+            return false;
+        }
+        self.allowed.setter(user).set(false);
+        evm::log(UserDisallowed { user });
+        true
+    }
+
+    /// Creates a `value` amount of tokens and assigns them to `account`,
+    /// by transferring it from `Address::ZERO`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account receiving the tokens.
+    /// * `value` - Amount to be minted.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Disallowed`] - If `account` is not allowed.
+    /// * [`erc20::Error::InvalidReceiver`] - If the `account` address is
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    ///
+    /// # Panics
+    ///
+    /// * If `total_supply` exceeds `U256::MAX`.
+    pub fn _mint(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if account.is_zero() {
+            return Err(erc20::Error::InvalidReceiver(ERC20InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+        self._update(Address::ZERO, account, value)
+    }
+
+    /// Destroys a `value` amount of tokens from `account`, lowering the
+    /// total supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Owner's address.
+    /// * `value` - Amount to be burnt.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Disallowed`] - If `account` is not allowed.
+    /// * [`erc20::Error::InvalidSender`] - If the `account` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientBalance`] - If `account` doesn't have
+    ///   enough tokens.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    pub fn _burn(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if account.is_zero() {
+            return Err(erc20::Error::InvalidSender(ERC20InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        self._update(account, Address::ZERO, value)
+    }
+
+    /// Extended version of [`Erc20::_update`] that checks that both `from`
+    /// and `to` are allowed. The zero address, used for mints and burns, is
+    /// not checked.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Owner's address.
+    /// * `to` - Recipient's address.
+    /// * `value` - Amount to be transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Disallowed`] - If `from` or `to` is not allowed.
+    /// * [`erc20::Error::InsufficientBalance`] - If the `from` address doesn't
+    ///   have enough tokens.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    ///
+    /// # Panics
+    ///
+    /// * If `total_supply` exceeds `U256::MAX`. It may happen during `mint`
+    ///   operation.
+    pub fn _update(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if !from.is_zero() {
+            self._check_allowed(from)?;
+        }
+        if !to.is_zero() {
+            self._check_allowed(to)?;
+        }
+        self.erc20._update(from, to, value)?;
+        Ok(())
+    }
+
+    /// Extended version of [`Erc20::_approve`] that checks that `owner` is
+    /// allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - Account that owns the tokens.
+    /// * `spender` - Account that will spend the tokens.
+    /// * `value` - The number of tokens being allowed to transfer by `spender`.
+    /// * `emit_event` - Emit an [`erc20::Approval`] event flag.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Disallowed`] - If `owner` is not allowed.
+    /// * [`erc20::Error::InvalidApprover`] - If the `owner` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InvalidSpender`] - If the `spender` address is
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Approval`]
+    pub fn _approve(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+        emit_event: bool,
+    ) -> Result<bool, Error> {
+        self._check_allowed(owner)?;
+        Ok(self.erc20._approve(owner, spender, value, emit_event)?)
+    }
+
+    /// Moves `value` tokens from `from` to `to` through
+    /// [`Self::_update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account to transfer tokens from.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - The number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Disallowed`] - If `from` or `to` is not allowed.
+    /// * [`erc20::Error::InvalidSender`] - If the `from` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientBalance`] - If the `from` address doesn't
+    ///   have enough tokens.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if from.is_zero() {
+            return Err(erc20::Error::InvalidSender(ERC20InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        if to.is_zero() {
+            return Err(erc20::Error::InvalidReceiver(ERC20InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+        self._update(from, to, value)
+    }
+
+    /// Checks that `user` is allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `user` - Account to check.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Disallowed`] - If `user` is not allowed.
+    fn _check_allowed(&self, user: Address) -> Result<(), Error> {
+        if !self.allowed.get(user) {
+            return Err(ERC20Disallowed { user }.into());
+        }
+        Ok(())
+    }
+
+    /// Updates `owner`'s allowance for `spender` based on spent `value`.
+    ///
+    /// Re-export of [`Erc20::_spend_allowance`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _spend_allowance(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        Ok(self.erc20._spend_allowance(owner, spender, value)?)
+    }
+}
+
+impl IErc20Burnable for Erc20Allowlist {
+    type Error = Error;
+
+    fn burn(&mut self, value: U256) -> Result<(), Self::Error> {
+        self._burn(msg::sender(), value)
+    }
+
+    fn burn_from(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Self::Error> {
+        self._spend_allowance(account, msg::sender(), value)?;
+        self._burn(account, value)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::storage::TopLevelStorage;
+
+    use super::*;
+
+    unsafe impl TopLevelStorage for Erc20Allowlist {}
+
+    #[motsu::test]
+    fn allow_and_disallow_user(
+        contract: Contract<Erc20Allowlist>,
+        alice: Address,
+    ) {
+        assert!(!contract.sender(alice).allowed(alice));
+
+        assert!(contract.sender(alice)._allow_user(alice));
+        assert!(!contract.sender(alice)._allow_user(alice));
+        assert!(contract.sender(alice).allowed(alice));
+
+        assert!(contract.sender(alice)._disallow_user(alice));
+        assert!(!contract.sender(alice)._disallow_user(alice));
+        assert!(!contract.sender(alice).allowed(alice));
+    }
+
+    #[motsu::test]
+    fn transfer_between_allowed_users(
+        contract: Contract<Erc20Allowlist>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._allow_user(alice);
+        contract.sender(alice)._allow_user(bob);
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint to an allowed user");
+
+        contract
+            .sender(alice)
+            .transfer(bob, uint!(4_U256))
+            .expect("should transfer between allowed users");
+
+        assert_eq!(uint!(6_U256), contract.sender(alice).balance_of(alice));
+        assert_eq!(uint!(4_U256), contract.sender(alice).balance_of(bob));
+    }
+
+    #[motsu::test]
+    fn mint_errors_when_receiver_disallowed(
+        contract: Contract<Erc20Allowlist>,
+        alice: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            ._mint(alice, uint!(1_U256))
+            .expect_err("should not mint to a disallowed user");
+
+        assert!(matches!(
+            err,
+            Error::Disallowed(ERC20Disallowed { user }) if user == alice
+        ));
+        assert_eq!(U256::ZERO, contract.sender(alice).total_supply());
+    }
+
+    #[motsu::test]
+    fn transfer_errors_when_receiver_disallowed(
+        contract: Contract<Erc20Allowlist>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._allow_user(alice);
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint to an allowed user");
+
+        let err = contract
+            .sender(alice)
+            .transfer(bob, uint!(1_U256))
+            .expect_err("should not transfer to a disallowed user");
+
+        assert!(matches!(
+            err,
+            Error::Disallowed(ERC20Disallowed { user }) if user == bob
+        ));
+    }
+
+    #[motsu::test]
+    fn transfer_errors_when_sender_disallowed(
+        contract: Contract<Erc20Allowlist>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._allow_user(alice);
+        contract.sender(alice)._allow_user(bob);
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint to an allowed user");
+        contract.sender(alice)._disallow_user(alice);
+
+        let err = contract
+            .sender(alice)
+            .transfer(bob, uint!(1_U256))
+            .expect_err("should not transfer from a disallowed user");
+
+        assert!(matches!(
+            err,
+            Error::Disallowed(ERC20Disallowed { user }) if user == alice
+        ));
+    }
+
+    #[motsu::test]
+    fn transfer_from_errors_when_sender_disallowed(
+        contract: Contract<Erc20Allowlist>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._allow_user(alice);
+        contract.sender(alice)._allow_user(bob);
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint to an allowed user");
+        contract.sender(alice).approve(bob, uint!(5_U256)).unwrap();
+        contract.sender(alice)._disallow_user(alice);
+
+        let err = contract
+            .sender(bob)
+            .transfer_from(alice, bob, uint!(1_U256))
+            .expect_err("should not transfer from a disallowed user");
+
+        assert!(matches!(
+            err,
+            Error::Disallowed(ERC20Disallowed { user }) if user == alice
+        ));
+    }
+
+    #[motsu::test]
+    fn approve_errors_when_owner_disallowed(
+        contract: Contract<Erc20Allowlist>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            .approve(bob, uint!(1_U256))
+            .expect_err("should not approve from a disallowed user");
+
+        assert!(matches!(
+            err,
+            Error::Disallowed(ERC20Disallowed { user }) if user == alice
+        ));
+        assert_eq!(U256::ZERO, contract.sender(alice).allowance(alice, bob));
+    }
+
+    #[motsu::test]
+    fn burn_errors_when_owner_disallowed(
+        contract: Contract<Erc20Allowlist>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._allow_user(alice);
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint to an allowed user");
+        contract.sender(alice)._disallow_user(alice);
+
+        let err = contract
+            .sender(alice)
+            ._burn(alice, uint!(1_U256))
+            .expect_err("should not burn from a disallowed user");
+
+        assert!(matches!(
+            err,
+            Error::Disallowed(ERC20Disallowed { user }) if user == alice
+        ));
+    }
+
+    #[motsu::test]
+    fn burnable_errors_when_owner_disallowed(
+        contract: Contract<Erc20Allowlist>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._allow_user(alice);
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint to an allowed user");
+        contract.sender(alice).approve(bob, uint!(5_U256)).unwrap();
+        contract.sender(alice)._disallow_user(alice);
+
+        let err = contract
+            .sender(alice)
+            .burn(uint!(1_U256))
+            .expect_err("should not burn from a disallowed user");
+        assert!(matches!(
+            err,
+            Error::Disallowed(ERC20Disallowed { user }) if user == alice
+        ));
+
+        let err = contract
+            .sender(bob)
+            .burn_from(alice, uint!(1_U256))
+            .expect_err("should not burn from a disallowed user");
+        assert!(matches!(
+            err,
+            Error::Disallowed(ERC20Disallowed { user }) if user == alice
+        ));
+        assert_eq!(uint!(10_U256), contract.sender(alice).total_supply());
+    }
+
+    #[motsu::test]
+    fn internal_transfer_errors_when_receiver_disallowed(
+        contract: Contract<Erc20Allowlist>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._allow_user(alice);
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint to an allowed user");
+
+        let err = contract
+            .sender(alice)
+            ._transfer(alice, bob, uint!(1_U256))
+            .expect_err("should not transfer to a disallowed user");
+
+        assert!(matches!(
+            err,
+            Error::Disallowed(ERC20Disallowed { user }) if user == bob
+        ));
+        assert_eq!(U256::ZERO, contract.sender(alice).balance_of(bob));
+    }
+}
@@ -0,0 +1,562 @@
+//! Bridgeable Contract.
+//!
+//! Extension of the ERC-20 standard that adds the cross-chain mint and burn
+//! entry points defined in [ERC-7802], used by token bridges such as the OP
+//! Superchain token bridge.
+//!
+//! [`Erc20Bridgeable::crosschain_mint`] and
+//! [`Erc20Bridgeable::crosschain_burn`] can only be called by accounts
+//! accepted by [`ITokenBridge::_check_token_bridge`], which every contract
+//! using this extension must implement.
+//!
+//! [ERC-7802]: https://eips.ethereum.org/EIPS/eip-7802
+
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    evm, msg,
+    prelude::{storage, StorageType},
+    stylus_proc::{public, SolidityError},
+};
+
+use crate::{
+    token::erc20::{self, Erc20, IErc20},
+    utils::introspection::erc165::IErc165,
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when a cross-chain transfer mints `amount` tokens to `to`.
+        ///
+        /// * `to` - Address receiving the minted tokens.
+        /// * `amount` - Number of tokens minted.
+        /// * `sender` - Token bridge that requested the mint.
+        #[allow(missing_docs)]
+        event CrosschainMint(
+            address indexed to,
+            uint256 amount,
+            address indexed sender
+        );
+
+        /// Emitted when a cross-chain transfer burns `amount` tokens from
+        /// `from`.
+        ///
+        /// * `from` - Address whose tokens are burnt.
+        /// * `amount` - Number of tokens burnt.
+        /// * `sender` - Token bridge that requested the burn.
+        #[allow(missing_docs)]
+        event CrosschainBurn(
+            address indexed from,
+            uint256 amount,
+            address indexed sender
+        );
+    }
+
+    sol! {
+        /// Indicates that `caller` is not a token bridge allowed to mint or
+        /// burn tokens.
+        ///
+        /// * `caller` - Address of the rejected caller.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7802UnauthorizedBridge(address caller);
+    }
+}
+
+/// An [`Erc20Bridgeable`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates that `caller` is not a token bridge allowed to mint or burn
+    /// tokens.
+    UnauthorizedBridge(ERC7802UnauthorizedBridge),
+    /// Error type from [`Erc20`] contract [`erc20::Error`].
+    Erc20(erc20::Error),
+}
+
+/// Hook deciding which accounts act as token bridges for an
+/// [`Erc20Bridgeable`] token.
+pub trait ITokenBridge {
+    /// Checks that `caller` is a trusted token bridge.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `caller` - Account calling a cross-chain entry point.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedBridge`] - If `caller` is not a token bridge.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// const SUPERCHAIN_TOKEN_BRIDGE: Address =
+    ///     address!("4200000000000000000000000000000000000028");
+    ///
+    /// #[storage]
+    /// struct SuperchainBridge {}
+    ///
+    /// impl ITokenBridge for SuperchainBridge {
+    ///     fn _check_token_bridge(&self, caller: Address) -> Result<(), Error> {
+    ///         if caller != SUPERCHAIN_TOKEN_BRIDGE {
+    ///             return Err(ERC7802UnauthorizedBridge { caller }.into());
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    /// ```
+    fn _check_token_bridge(&self, caller: Address) -> Result<(), Error>;
+}
+
+/// Interface of the cross-chain token standard, as defined in [ERC-7802].
+///
+/// [ERC-7802]: https://eips.ethereum.org/EIPS/eip-7802
+#[interface_id]
+pub trait IErc7802 {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Mints `value` tokens to `to` as part of a cross-chain transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account receiving the tokens.
+    /// * `value` - Number of tokens to mint.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedBridge`] - If the caller is not a token bridge.
+    /// * [`erc20::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    /// * [`CrosschainMint`]
+    fn crosschain_mint(
+        &mut self,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Self::Error>;
+
+    /// Burns `value` tokens from `from` as part of a cross-chain transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account whose tokens are burnt.
+    /// * `value` - Number of tokens to burn.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedBridge`] - If the caller is not a token bridge.
+    /// * [`erc20::Error::InvalidSender`] - If `from` is `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientBalance`] - If `from` holds less than
+    ///   `value` tokens.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    /// * [`CrosschainBurn`]
+    fn crosschain_burn(
+        &mut self,
+        from: Address,
+        value: U256,
+    ) -> Result<(), Self::Error>;
+}
+
+/// State of an [`Erc20Bridgeable`] Contract.
+#[storage]
+pub struct Erc20Bridgeable<T: ITokenBridge + StorageType> {
+    /// [`Erc20`] contract.
+    // We leave the parent ERC-20 contract instance public, so that inheritting
+    // contract have access to its internal functions.
+    pub erc20: Erc20,
+    /// Contract implementing [`ITokenBridge`] trait.
+    pub token_bridge: T,
+}
+
+impl<T: ITokenBridge + StorageType> Deref for Erc20Bridgeable<T> {
+    type Target = Erc20;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc20
+    }
+}
+
+impl<T: ITokenBridge + StorageType> DerefMut for Erc20Bridgeable<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc20
+    }
+}
+
+impl<T: ITokenBridge + StorageType> IErc7802 for Erc20Bridgeable<T> {
+    type Error = Error;
+
+    fn crosschain_mint(
+        &mut self,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Self::Error> {
+        let sender = msg::sender();
+        self.token_bridge._check_token_bridge(sender)?;
+        self.erc20._mint(to, value)?;
+        evm::log(CrosschainMint { to, amount: value, sender });
+        Ok(())
+    }
+
+    fn crosschain_burn(
+        &mut self,
+        from: Address,
+        value: U256,
+    ) -> Result<(), Self::Error> {
+        let sender = msg::sender();
+        self.token_bridge._check_token_bridge(sender)?;
+        self.erc20._burn(from, value)?;
+        evm::log(CrosschainBurn { from, amount: value, sender });
+        Ok(())
+    }
+}
+
+#[public]
+impl<T: ITokenBridge + StorageType> Erc20Bridgeable<T> {
+    /// Mints `value` tokens to `to` as part of a cross-chain transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account receiving the tokens.
+    /// * `value` - Number of tokens to mint.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedBridge`] - If the caller is not a token bridge.
+    /// * [`erc20::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    /// * [`CrosschainMint`]
+    pub fn crosschain_mint(
+        &mut self,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        IErc7802::crosschain_mint(self, to, value)
+    }
+
+    /// Burns `value` tokens from `from` as part of a cross-chain transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account whose tokens are burnt.
+    /// * `value` - Number of tokens to burn.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedBridge`] - If the caller is not a token bridge.
+    /// * [`erc20::Error::InvalidSender`] - If `from` is `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientBalance`] - If `from` holds less than
+    ///   `value` tokens.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    /// * [`CrosschainBurn`]
+    pub fn crosschain_burn(
+        &mut self,
+        from: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        IErc7802::crosschain_burn(self, from, value)
+    }
+
+    /// Returns the number of tokens in existence.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn total_supply(&self) -> U256 {
+        self.erc20.total_supply()
+    }
+
+    /// Returns the number of tokens owned by `account`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get balance from.
+    pub fn balance_of(&self, account: Address) -> U256 {
+        self.erc20.balance_of(account)
+    }
+
+    /// Moves a `value` amount of tokens from the caller's account to `to`.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - Number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientBalance`] - If the caller doesn't have a
+    ///   balance of at least `value`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    pub fn transfer(
+        &mut self,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, erc20::Error> {
+        self.erc20.transfer(to, value)
+    }
+
+    /// Returns the remaining number of tokens that `spender` will be allowed
+    /// to spend on behalf of `owner` through `transfer_from`. This is zero by
+    /// default.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account that owns the tokens.
+    /// * `spender` - Account that will spend the tokens.
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.erc20.allowance(owner, spender)
+    }
+
+    /// Sets a `value` number of tokens as the allowance of `spender` over the
+    /// caller's tokens.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `spender` - Account that will spend the tokens.
+    /// * `value` - The number of tokens being allowed to transfer by `spender`.
+    ///
+    /// # Errors
+    ///
+    /// [`erc20::Error::InvalidSpender`] - If the `spender`
+    /// address is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Approval`]
+    pub fn approve(
+        &mut self,
+        spender: Address,
+        value: U256,
+    ) -> Result<bool, erc20::Error> {
+        self.erc20.approve(spender, value)
+    }
+
+    /// Moves a `value` number of tokens from `from` to `to` using the
+    /// allowance mechanism. `value` is then deducted from the caller's
+    /// allowance.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account to transfer tokens from.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - Number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidSender`] - If the `from` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientAllowance`] - If not enough allowance is
+    ///   available.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, erc20::Error> {
+        self.erc20.transfer_from(from, to, value)
+    }
+}
+
+impl<T: ITokenBridge + StorageType> IErc165 for Erc20Bridgeable<T> {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IErc7802>::INTERFACE_ID == u32::from_be_bytes(*interface_id)
+            || Erc20::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::{
+        prelude::storage,
+        storage::{StorageAddress, TopLevelStorage},
+    };
+
+    use super::*;
+
+    #[storage]
+    struct TestBridge {
+        bridge: StorageAddress,
+    }
+
+    impl ITokenBridge for TestBridge {
+        fn _check_token_bridge(&self, caller: Address) -> Result<(), Error> {
+            if caller != self.bridge.get() {
+                return Err(ERC7802UnauthorizedBridge { caller }.into());
+            }
+            Ok(())
+        }
+    }
+
+    unsafe impl TopLevelStorage for Erc20Bridgeable<TestBridge> {}
+
+    #[motsu::test]
+    fn crosschain_mint_works(
+        contract: Contract<Erc20Bridgeable<TestBridge>>,
+        bridge: Address,
+        alice: Address,
+    ) {
+        contract.sender(bridge).token_bridge.bridge.set(bridge);
+
+        let value = uint!(10_U256);
+        contract
+            .sender(bridge)
+            .crosschain_mint(alice, value)
+            .expect("should mint through the bridge");
+
+        assert_eq!(value, contract.sender(alice).balance_of(alice));
+        assert_eq!(value, contract.sender(alice).total_supply());
+    }
+
+    #[motsu::test]
+    fn crosschain_burn_works(
+        contract: Contract<Erc20Bridgeable<TestBridge>>,
+        bridge: Address,
+        alice: Address,
+    ) {
+        contract.sender(bridge).token_bridge.bridge.set(bridge);
+        contract
+            .sender(bridge)
+            .erc20
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint tokens for Alice");
+
+        contract
+            .sender(bridge)
+            .crosschain_burn(alice, uint!(4_U256))
+            .expect("should burn through the bridge");
+
+        assert_eq!(uint!(6_U256), contract.sender(alice).balance_of(alice));
+        assert_eq!(uint!(6_U256), contract.sender(alice).total_supply());
+    }
+
+    #[motsu::test]
+    fn crosschain_mint_errors_unauthorized_bridge(
+        contract: Contract<Erc20Bridgeable<TestBridge>>,
+        bridge: Address,
+        alice: Address,
+    ) {
+        contract.sender(bridge).token_bridge.bridge.set(bridge);
+
+        let err = contract
+            .sender(alice)
+            .crosschain_mint(alice, uint!(1_U256))
+            .expect_err("should reject a caller that is not the bridge");
+
+        assert!(matches!(
+            err,
+            Error::UnauthorizedBridge(ERC7802UnauthorizedBridge { caller })
+                if caller == alice
+        ));
+        assert_eq!(U256::ZERO, contract.sender(alice).total_supply());
+    }
+
+    #[motsu::test]
+    fn crosschain_burn_errors_unauthorized_bridge(
+        contract: Contract<Erc20Bridgeable<TestBridge>>,
+        bridge: Address,
+        alice: Address,
+    ) {
+        contract.sender(bridge).token_bridge.bridge.set(bridge);
+        contract
+            .sender(bridge)
+            .erc20
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint tokens for Alice");
+
+        let err = contract
+            .sender(alice)
+            .crosschain_burn(alice, uint!(1_U256))
+            .expect_err("should reject a caller that is not the bridge");
+
+        assert!(matches!(
+            err,
+            Error::UnauthorizedBridge(ERC7802UnauthorizedBridge { caller })
+                if caller == alice
+        ));
+    }
+
+    #[motsu::test]
+    fn crosschain_burn_errors_insufficient_balance(
+        contract: Contract<Erc20Bridgeable<TestBridge>>,
+        bridge: Address,
+        alice: Address,
+    ) {
+        contract.sender(bridge).token_bridge.bridge.set(bridge);
+
+        let err = contract
+            .sender(bridge)
+            .crosschain_burn(alice, uint!(1_U256))
+            .expect_err("should not burn more than the balance");
+
+        assert!(matches!(
+            err,
+            Error::Erc20(erc20::Error::InsufficientBalance(_))
+        ));
+    }
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <Erc20Bridgeable<TestBridge> as IErc7802>::INTERFACE_ID;
+        let expected = 0x3333_1994;
+        assert_eq!(actual, expected);
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc20Bridgeable::<TestBridge>::supports_interface(
+            0x3333_1994_u32.to_be_bytes().into()
+        ));
+        assert!(Erc20Bridgeable::<TestBridge>::supports_interface(
+            0x3637_2b07_u32.to_be_bytes().into()
+        ));
+        assert!(!Erc20Bridgeable::<TestBridge>::supports_interface(
+            0xdead_beef_u32.to_be_bytes().into()
+        ));
+    }
+}
@@ -0,0 +1,711 @@
+//! Custodian Contract.
+//!
+//! Extension of the ERC-20 standard that lets a custodian freeze part of an
+//! account's balance. Frozen tokens stay in the account but cannot be
+//! transferred or burnt until they are unfrozen.
+//!
+//! Which accounts act as custodians is decided by
+//! [`ICustodian::_is_custodian`], which every contract using this extension
+//! must implement.
+
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, U256};
+pub use sol::*;
+use stylus_sdk::{
+    evm, msg,
+    prelude::{storage, StorageType},
+    storage::{StorageMap, StorageU256},
+    stylus_proc::{public, SolidityError},
+};
+
+use crate::token::erc20::{
+    self, extensions::IErc20Burnable, ERC20InsufficientBalance,
+    ERC20InvalidReceiver, ERC20InvalidSender, Erc20, IErc20,
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the frozen balance of `user` is set to `amount`.
+        #[allow(missing_docs)]
+        event TokensFrozen(address indexed user, uint256 amount);
+    }
+
+    sol! {
+        /// Indicates that `user` doesn't have enough unfrozen tokens for the
+        /// operation.
+        ///
+        /// * `user` - Address whose unfrozen balance is insufficient.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC20InsufficientUnfrozenBalance(address user);
+
+        /// Indicates that the caller is not a custodian.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC20NotCustodian();
+    }
+}
+
+/// An [`Erc20Custodian`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates that an account doesn't have enough unfrozen tokens for the
+    /// operation.
+    InsufficientUnfrozenBalance(ERC20InsufficientUnfrozenBalance),
+    /// Indicates that the caller is not a custodian.
+    NotCustodian(ERC20NotCustodian),
+    /// Error type from [`Erc20`] contract [`erc20::Error`].
+    Erc20(erc20::Error),
+}
+
+/// Hook deciding which accounts act as custodians for an [`Erc20Custodian`]
+/// token.
+pub trait ICustodian {
+    /// Returns whether `user` is a custodian.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `user` - Account to check.
+    fn _is_custodian(&self, user: Address) -> bool;
+}
+
+/// State of an [`Erc20Custodian`] Contract.
+#[storage]
+pub struct Erc20Custodian<T: ICustodian + StorageType> {
+    /// [`Erc20`] contract.
+    // The parent ERC-20 contract instance is not public, so that inheriting
+    // contracts cannot bypass the checks of this extension by calling its
+    // internal functions directly.
+    pub(crate) erc20: Erc20,
+    /// Maps accounts to their frozen balance.
+    pub(crate) frozen: StorageMap<Address, StorageU256>,
+    /// Contract implementing [`ICustodian`] trait.
+    pub custodian: T,
+}
+
+#[public]
+impl<T: ICustodian + StorageType> Erc20Custodian<T> {
+    /// Returns the number of tokens of `user` that are frozen.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `user` - Account to check.
+    pub fn frozen(&self, user: Address) -> U256 {
+        self.frozen.get(user)
+    }
+
+    /// Returns the number of tokens of `account` that can be transferred,
+    /// i.e. its balance minus its frozen tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to check.
+    pub fn available_balance(&self, account: Address) -> U256 {
+        self.erc20.balance_of(account).saturating_sub(self.frozen.get(account))
+    }
+
+    /// Sets the number of frozen tokens of `user` to `amount`. Setting it
+    /// to zero unfreezes all of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `user` - Account whose tokens are frozen.
+    /// * `amount` - Number of tokens to keep frozen.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::NotCustodian`] - If the caller is not a custodian.
+    /// * [`erc20::Error::InsufficientBalance`] - If `amount` exceeds the
+    ///   balance of `user`.
+    ///
+    /// # Events
+    ///
+    /// * [`TokensFrozen`]
+    pub fn freeze(&mut self, user: Address, amount: U256) -> Result<(), Error> {
+        if !self.custodian._is_custodian(msg::sender()) {
+            return Err(ERC20NotCustodian {}.into());
+        }
+
+        let balance = self.erc20.balance_of(user);
+        if balance < amount {
+            return Err(erc20::Error::InsufficientBalance(
+                ERC20InsufficientBalance {
+                    sender: user,
+                    balance,
+                    needed: amount,
+                },
+            )
+            .into());
+        }
+
+        self.frozen.setter(user).set(amount);
+        evm::log(TokensFrozen { user, amount });
+        Ok(())
+    }
+
+    /// Returns the number of tokens in existence.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn total_supply(&self) -> U256 {
+        self.erc20.total_supply()
+    }
+
+    /// Returns the number of tokens owned by `account`, frozen ones
+    /// included.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get balance from.
+    pub fn balance_of(&self, account: Address) -> U256 {
+        self.erc20.balance_of(account)
+    }
+
+    /// Moves a `value` amount of tokens from the caller's account to `to`.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - Number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientUnfrozenBalance`] - If the caller doesn't have at
+    ///   least `value` unfrozen tokens.
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    pub fn transfer(
+        &mut self,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Error> {
+        let from = msg::sender();
+        self._transfer(from, to, value)?;
+        Ok(true)
+    }
+
+    /// Returns the remaining number of tokens that `spender` will be allowed
+    /// to spend on behalf of `owner` through `transfer_from`. This is zero by
+    /// default.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account that owns the tokens.
+    /// * `spender` - Account that will spend the tokens.
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.erc20.allowance(owner, spender)
+    }
+
+    /// Sets a `value` number of tokens as the allowance of `spender` over the
+    /// caller's tokens.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `spender` - Account that will spend the tokens.
+    /// * `value` - The number of tokens being allowed to transfer by `spender`.
+    ///
+    /// # Errors
+    ///
+    /// [`erc20::Error::InvalidSpender`] - If the `spender`
+    /// address is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Approval`]
+    pub fn approve(
+        &mut self,
+        spender: Address,
+        value: U256,
+    ) -> Result<bool, erc20::Error> {
+        let owner = msg::sender();
+        self._approve(owner, spender, value, true)
+    }
+
+    /// Moves a `value` number of tokens from `from` to `to` using the
+    /// allowance mechanism. `value` is then deducted from the caller's
+    /// allowance.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account to transfer tokens from.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - Number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientUnfrozenBalance`] - If `from` doesn't have at
+    ///   least `value` unfrozen tokens.
+    /// * [`erc20::Error::InvalidSender`] - If the `from` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientAllowance`] - If not enough allowance is
+    ///   available.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Error> {
+        let spender = msg::sender();
+        self.erc20._spend_allowance(from, spender, value)?;
+        self._transfer(from, to, value)?;
+        Ok(true)
+    }
+}
+
+impl<T: ICustodian + StorageType> Erc20Custodian<T> {
+    /// Creates a `value` amount of tokens and assigns them to `account`,
+    /// by transferring it from `Address::ZERO`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account receiving the tokens.
+    /// * `value` - Amount to be minted.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidReceiver`] - If the `account` address is
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    ///
+    /// # Panics
+    ///
+    /// * If `total_supply` exceeds `U256::MAX`.
+    pub fn _mint(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if account.is_zero() {
+            return Err(erc20::Error::InvalidReceiver(ERC20InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+        self._update(Address::ZERO, account, value)
+    }
+
+    /// Destroys a `value` amount of unfrozen tokens from `account`, lowering
+    /// the total supply.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Owner's address.
+    /// * `value` - Amount to be burnt.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientUnfrozenBalance`] - If `account` doesn't have at
+    ///   least `value` unfrozen tokens.
+    /// * [`erc20::Error::InvalidSender`] - If the `account` address is
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    pub fn _burn(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if account.is_zero() {
+            return Err(erc20::Error::InvalidSender(ERC20InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        self._update(account, Address::ZERO, value)
+    }
+
+    /// Extended version of [`Erc20::_update`] that only lets `from` move its
+    /// unfrozen tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Owner's address.
+    /// * `to` - Recipient's address.
+    /// * `value` - Amount to be transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientUnfrozenBalance`] - If `from` doesn't have at
+    ///   least `value` unfrozen tokens.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    ///
+    /// # Panics
+    ///
+    /// * If `total_supply` exceeds `U256::MAX`. It may happen during `mint`
+    ///   operation.
+    pub fn _update(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if !from.is_zero() && self.available_balance(from) < value {
+            return Err(ERC20InsufficientUnfrozenBalance { user: from }.into());
+        }
+        self.erc20._update(from, to, value)?;
+        Ok(())
+    }
+
+    /// Moves `value` tokens from `from` to `to` through
+    /// [`Self::_update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account to transfer tokens from.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - The number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientUnfrozenBalance`] - If `from` doesn't have at
+    ///   least `value` unfrozen tokens.
+    /// * [`erc20::Error::InvalidSender`] - If the `from` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`]
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if from.is_zero() {
+            return Err(erc20::Error::InvalidSender(ERC20InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        if to.is_zero() {
+            return Err(erc20::Error::InvalidReceiver(ERC20InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+        self._update(from, to, value)
+    }
+
+    /// Sets a `value` number of tokens as the allowance of `spender` over the
+    /// `owner`'s tokens.
+    ///
+    /// Frozen tokens may be approved, since [`Self::_update`] still prevents
+    /// the spender from moving them.
+    ///
+    /// Re-export of [`Erc20::_approve`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _approve(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+        emit_event: bool,
+    ) -> Result<bool, erc20::Error> {
+        self.erc20._approve(owner, spender, value, emit_event)
+    }
+
+    /// Updates `owner`'s allowance for `spender` based on spent `value`.
+    ///
+    /// Re-export of [`Erc20::_spend_allowance`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _spend_allowance(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        Ok(self.erc20._spend_allowance(owner, spender, value)?)
+    }
+}
+
+impl<T: ICustodian + StorageType> IErc20Burnable for Erc20Custodian<T> {
+    type Error = Error;
+
+    fn burn(&mut self, value: U256) -> Result<(), Self::Error> {
+        self._burn(msg::sender(), value)
+    }
+
+    fn burn_from(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Self::Error> {
+        self._spend_allowance(account, msg::sender(), value)?;
+        self._burn(account, value)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::storage::{StorageAddress, TopLevelStorage};
+
+    use super::*;
+
+    #[storage]
+    struct TestCustodian {
+        custodian: StorageAddress,
+    }
+
+    impl ICustodian for TestCustodian {
+        fn _is_custodian(&self, user: Address) -> bool {
+            user == self.custodian.get()
+        }
+    }
+
+    unsafe impl TopLevelStorage for Erc20Custodian<TestCustodian> {}
+
+    fn setup(
+        contract: &Contract<Erc20Custodian<TestCustodian>>,
+        custodian: Address,
+        alice: Address,
+    ) {
+        contract.sender(custodian).custodian.custodian.set(custodian);
+        contract
+            .sender(custodian)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint tokens for Alice");
+    }
+
+    #[motsu::test]
+    fn freeze_works(
+        contract: Contract<Erc20Custodian<TestCustodian>>,
+        custodian: Address,
+        alice: Address,
+    ) {
+        setup(&contract, custodian, alice);
+
+        contract
+            .sender(custodian)
+            .freeze(alice, uint!(4_U256))
+            .expect("custodian should freeze tokens");
+
+        assert_eq!(uint!(4_U256), contract.sender(alice).frozen(alice));
+        assert_eq!(
+            uint!(6_U256),
+            contract.sender(alice).available_balance(alice)
+        );
+        assert_eq!(uint!(10_U256), contract.sender(alice).balance_of(alice));
+    }
+
+    #[motsu::test]
+    fn freeze_errors_when_not_custodian(
+        contract: Contract<Erc20Custodian<TestCustodian>>,
+        custodian: Address,
+        alice: Address,
+    ) {
+        setup(&contract, custodian, alice);
+
+        let err = contract
+            .sender(alice)
+            .freeze(alice, uint!(1_U256))
+            .expect_err("only the custodian should freeze tokens");
+
+        assert!(matches!(err, Error::NotCustodian(_)));
+        assert_eq!(U256::ZERO, contract.sender(alice).frozen(alice));
+    }
+
+    #[motsu::test]
+    fn freeze_errors_when_exceeding_balance(
+        contract: Contract<Erc20Custodian<TestCustodian>>,
+        custodian: Address,
+        alice: Address,
+    ) {
+        setup(&contract, custodian, alice);
+
+        let err = contract
+            .sender(custodian)
+            .freeze(alice, uint!(11_U256))
+            .expect_err("should not freeze more than the balance");
+
+        assert!(matches!(
+            err,
+            Error::Erc20(erc20::Error::InsufficientBalance(_))
+        ));
+    }
+
+    #[motsu::test]
+    fn transfer_respects_frozen_tokens(
+        contract: Contract<Erc20Custodian<TestCustodian>>,
+        custodian: Address,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup(&contract, custodian, alice);
+        contract.sender(custodian).freeze(alice, uint!(4_U256)).unwrap();
+
+        contract
+            .sender(alice)
+            .transfer(bob, uint!(6_U256))
+            .expect("should transfer unfrozen tokens");
+
+        let err = contract
+            .sender(alice)
+            .transfer(bob, uint!(1_U256))
+            .expect_err("should not transfer frozen tokens");
+
+        assert!(matches!(
+            err,
+            Error::InsufficientUnfrozenBalance(
+                ERC20InsufficientUnfrozenBalance { user }
+            ) if user == alice
+        ));
+        assert_eq!(uint!(4_U256), contract.sender(alice).balance_of(alice));
+    }
+
+    #[motsu::test]
+    fn transfer_from_respects_frozen_tokens(
+        contract: Contract<Erc20Custodian<TestCustodian>>,
+        custodian: Address,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup(&contract, custodian, alice);
+        contract.sender(custodian).freeze(alice, uint!(10_U256)).unwrap();
+        contract.sender(alice).approve(bob, uint!(10_U256)).unwrap();
+
+        let err = contract
+            .sender(bob)
+            .transfer_from(alice, bob, uint!(1_U256))
+            .expect_err("should not transfer frozen tokens");
+
+        assert!(matches!(err, Error::InsufficientUnfrozenBalance(_)));
+    }
+
+    #[motsu::test]
+    fn burn_respects_frozen_tokens(
+        contract: Contract<Erc20Custodian<TestCustodian>>,
+        custodian: Address,
+        alice: Address,
+    ) {
+        setup(&contract, custodian, alice);
+        contract.sender(custodian).freeze(alice, uint!(8_U256)).unwrap();
+
+        let err = contract
+            .sender(alice)
+            ._burn(alice, uint!(3_U256))
+            .expect_err("should not burn frozen tokens");
+        assert!(matches!(err, Error::InsufficientUnfrozenBalance(_)));
+
+        contract
+            .sender(alice)
+            ._burn(alice, uint!(2_U256))
+            .expect("should burn unfrozen tokens");
+        assert_eq!(uint!(8_U256), contract.sender(alice).total_supply());
+    }
+
+    #[motsu::test]
+    fn unfreeze_releases_tokens(
+        contract: Contract<Erc20Custodian<TestCustodian>>,
+        custodian: Address,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup(&contract, custodian, alice);
+        contract.sender(custodian).freeze(alice, uint!(10_U256)).unwrap();
+        contract.sender(custodian).freeze(alice, U256::ZERO).unwrap();
+
+        contract
+            .sender(alice)
+            .transfer(bob, uint!(10_U256))
+            .expect("should transfer unfrozen tokens");
+        assert_eq!(uint!(10_U256), contract.sender(alice).balance_of(bob));
+    }
+
+    #[motsu::test]
+    fn burnable_respects_frozen_tokens(
+        contract: Contract<Erc20Custodian<TestCustodian>>,
+        custodian: Address,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup(&contract, custodian, alice);
+        contract.sender(custodian).freeze(alice, uint!(8_U256)).unwrap();
+        contract
+            .sender(alice)
+            .approve(bob, uint!(10_U256))
+            .expect("should approve frozen tokens");
+
+        let err = contract
+            .sender(alice)
+            .burn(uint!(3_U256))
+            .expect_err("should not burn frozen tokens");
+        assert!(matches!(err, Error::InsufficientUnfrozenBalance(_)));
+
+        let err = contract
+            .sender(bob)
+            .burn_from(alice, uint!(3_U256))
+            .expect_err("should not burn frozen tokens");
+        assert!(matches!(err, Error::InsufficientUnfrozenBalance(_)));
+
+        contract
+            .sender(bob)
+            .burn_from(alice, uint!(2_U256))
+            .expect("should burn unfrozen tokens");
+        assert_eq!(uint!(8_U256), contract.sender(alice).total_supply());
+    }
+
+    #[motsu::test]
+    fn internal_transfer_respects_frozen_tokens(
+        contract: Contract<Erc20Custodian<TestCustodian>>,
+        custodian: Address,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup(&contract, custodian, alice);
+        contract.sender(custodian).freeze(alice, uint!(10_U256)).unwrap();
+
+        let err = contract
+            .sender(alice)
+            ._transfer(alice, bob, uint!(1_U256))
+            .expect_err("should not transfer frozen tokens");
+
+        assert!(matches!(err, Error::InsufficientUnfrozenBalance(_)));
+        assert_eq!(U256::ZERO, contract.sender(alice).balance_of(bob));
+    }
+}
@@ -10,7 +10,7 @@
 use alloy_primitives::{uint, Address, U256, U8};
 pub use sol::*;
 use stylus_sdk::{
-    call::Call,
+    call::{Call, MethodError},
     contract, evm, msg,
     prelude::storage,
     storage::{StorageAddress, StorageU8, TopLevelStorage},
@@ -122,6 +122,12 @@ pub enum Error {
     Erc20(erc20::Error),
 }
 
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
 /// State of an [`Erc4626`] token.
 #[storage]
 pub struct Erc4626 {
@@ -132,7 +138,7 @@ pub struct Erc4626 {
     /// Decimals offset.
     pub(crate) decimals_offset: StorageU8,
     /// [`SafeErc20`] contract.
-    safe_erc20: SafeErc20,
+    pub(crate) safe_erc20: SafeErc20,
 }
 
 /// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
@@ -153,6 +159,21 @@ pub trait IErc4626 {
     /// * `&self` - Read access to the contract's state.
     fn asset(&self) -> Address;
 
+    /// Returns the address of the share token, as defined in [ERC-7575].
+    ///
+    /// The shares of an [`Erc4626`] vault are the vault itself, so this
+    /// returns the address of the contract. See [`super::Erc7575Vault`] for
+    /// vaults whose share token is another contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    ///
+    /// [ERC-7575]: https://eips.ethereum.org/EIPS/eip-7575
+    fn share(&self) -> Address {
+        contract::address()
+    }
+
     /// Returns the total amount of the underlying asset that is “managed” by
     /// Vault.
     ///
@@ -738,6 +759,123 @@ pub trait IErc4626 {
     ) -> Result<U256, Self::Error>;
 }
 
+/// Share token of an [`Erc4626`] vault.
+///
+/// The deposit and withdrawal workflows mint and burn shares through this
+/// trait, so that they serve both vaults that are their own share token
+/// ([`Erc20`]) and vaults whose shares live in another contract (e.g.
+/// [ERC-7575] entry points).
+///
+/// [ERC-7575]: https://eips.ethereum.org/EIPS/eip-7575
+pub trait IErc4626Shares {
+    /// The error type associated to this trait implementation.
+    type Error;
+
+    /// Returns the total amount of shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * If the share token cannot be read.
+    fn share_total_supply(&mut self) -> Result<U256, Self::Error>;
+
+    /// Returns the shares owned by `account`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account to get balance from.
+    ///
+    /// # Errors
+    ///
+    /// * If the share token cannot be read.
+    fn share_balance_of(
+        &mut self,
+        account: Address,
+    ) -> Result<U256, Self::Error>;
+
+    /// Mints `shares` to `to` for `assets` deposited into the vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account receiving the shares.
+    /// * `shares` - Amount of shares to mint.
+    /// * `assets` - Amount of assets deposited.
+    ///
+    /// # Errors
+    ///
+    /// * If the shares cannot be minted.
+    fn mint_shares(
+        &mut self,
+        to: Address,
+        shares: U256,
+        assets: U256,
+    ) -> Result<(), Self::Error>;
+
+    /// Burns `shares` of `owner` for `assets` withdrawn from the vault,
+    /// spending the allowance of `spender` if it is not `owner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - Account owning the shares.
+    /// * `spender` - Account that requested the withdrawal.
+    /// * `shares` - Amount of shares to burn.
+    /// * `assets` - Amount of assets withdrawn.
+    ///
+    /// # Errors
+    ///
+    /// * If `spender` lacks allowance, or `owner` lacks shares.
+    fn burn_shares(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        shares: U256,
+        assets: U256,
+    ) -> Result<(), Self::Error>;
+}
+
+impl IErc4626Shares for Erc20 {
+    type Error = erc20::Error;
+
+    fn share_total_supply(&mut self) -> Result<U256, Self::Error> {
+        Ok(self.total_supply())
+    }
+
+    fn share_balance_of(
+        &mut self,
+        account: Address,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.balance_of(account))
+    }
+
+    fn mint_shares(
+        &mut self,
+        to: Address,
+        shares: U256,
+        _assets: U256,
+    ) -> Result<(), Self::Error> {
+        self._mint(to, shares)
+    }
+
+    fn burn_shares(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        shares: U256,
+        _assets: U256,
+    ) -> Result<(), Self::Error> {
+        if spender != owner {
+            self._spend_allowance(owner, spender, shares)?;
+        }
+        self._burn(owner, shares)
+    }
+}
+
 impl IErc4626 for Erc4626 {
     type Error = Error;
 
@@ -962,14 +1100,48 @@ impl Erc4626 {
     ///
     /// * If decimal offset calculation overflows in the power operation.
     /// * If multiplication or division operations overflow during conversion.
-    fn _convert_to_shares(
+    pub fn _convert_to_shares(
         &mut self,
         assets: U256,
         rounding: Rounding,
         erc20: &Erc20,
     ) -> Result<U256, Error> {
-        let total_supply = erc20.total_supply();
+        let total_assets = self.total_assets()?;
+        Ok(self._convert_to_shares_with(
+            assets,
+            total_assets,
+            erc20.total_supply(),
+            rounding,
+        ))
+    }
 
+    /// Converts a given amount of assets to shares using the specified
+    /// `rounding` mode, for a vault holding `total_assets` and with
+    /// `total_supply` shares.
+    ///
+    /// Extensions that do not count every asset held by the contract as
+    /// backing the shares use it with their own `total_assets`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `assets` - The amount of assets to convert.
+    /// * `total_assets` - Total amount of assets backing the shares.
+    /// * `total_supply` - Total amount of shares.
+    /// * `rounding` - The [`Rounding`] mode to use for the conversion.
+    ///
+    /// # Panics
+    ///
+    /// * If decimal offset calculation overflows in the power operation.
+    /// * If multiplication or division operations overflow during conversion.
+    #[must_use]
+    pub fn _convert_to_shares_with(
+        &self,
+        assets: U256,
+        total_assets: U256,
+        total_supply: U256,
+        rounding: Rounding,
+    ) -> U256 {
         let multiplier = total_supply
             .checked_add(
                 TEN.checked_pow(U256::from(self._decimals_offset())).expect(
@@ -978,14 +1150,11 @@ impl Erc4626 {
             )
             .expect("multiplier overflow in `Erc4626::_convert_to_shares`");
 
-        let denominator = self
-            .total_assets()?
+        let denominator = total_assets
             .checked_add(ONE)
             .expect("denominator overflow in `Erc4626::_convert_to_shares`");
 
-        let shares = assets.mul_div(multiplier, denominator, rounding);
-
-        Ok(shares)
+        assets.mul_div(multiplier, denominator, rounding)
     }
 
     /// Converts a given amount of shares to assets using the specified
@@ -1007,19 +1176,49 @@ impl Erc4626 {
     ///
     /// * If decimal offset calculation overflows.
     /// * If multiplication or division operations overflow.
-    fn _convert_to_assets(
+    pub fn _convert_to_assets(
         &mut self,
         shares: U256,
         rounding: Rounding,
         erc20: &Erc20,
     ) -> Result<U256, Error> {
-        let multiplier = self
-            .total_assets()?
+        let total_assets = self.total_assets()?;
+        Ok(self._convert_to_assets_with(
+            shares,
+            total_assets,
+            erc20.total_supply(),
+            rounding,
+        ))
+    }
+
+    /// Converts a given amount of shares to assets using the specified
+    /// `rounding` mode, for a vault holding `total_assets` and with
+    /// `total_supply` shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `shares` - The amount of shares to convert.
+    /// * `total_assets` - Total amount of assets backing the shares.
+    /// * `total_supply` - Total amount of shares.
+    /// * `rounding` - The [`Rounding`] mode to use for the conversion.
+    ///
+    /// # Panics
+    ///
+    /// * If decimal offset calculation overflows.
+    /// * If multiplication or division operations overflow.
+    #[must_use]
+    pub fn _convert_to_assets_with(
+        &self,
+        shares: U256,
+        total_assets: U256,
+        total_supply: U256,
+        rounding: Rounding,
+    ) -> U256 {
+        let multiplier = total_assets
             .checked_add(ONE)
             .expect("multiplier overflow in `Erc4626::_convert_to_assets`");
 
-        let total_supply = erc20.total_supply();
-
         let denominator = total_supply
             .checked_add(
                 TEN.checked_pow(U256::from(self._decimals_offset())).expect(
@@ -1028,9 +1227,7 @@ impl Erc4626 {
             )
             .expect("denominator overflow in `Erc4626::_convert_to_assets`");
 
-        let assets = shares.mul_div(multiplier, denominator, rounding);
-
-        Ok(assets)
+        shares.mul_div(multiplier, denominator, rounding)
     }
 
     /// Deposit/mint common workflow.
@@ -1052,7 +1249,7 @@ impl Erc4626 {
     /// # Events
     ///
     /// * [`Deposit`]
-    fn _deposit(
+    pub fn _deposit(
         &mut self,
         caller: Address,
         receiver: Address,
@@ -1060,6 +1257,40 @@ impl Erc4626 {
         shares: U256,
         erc20: &mut Erc20,
     ) -> Result<(), Error> {
+        self._deposit_with(caller, receiver, assets, shares, erc20)
+    }
+
+    /// Deposit/mint common workflow, minting the shares of `share_token`.
+    ///
+    /// # Arguments
+    ///
+    /// * `caller` - Address initiating the deposit.
+    /// * `receiver` - Address receiving the minted shares.
+    /// * `assets` - Amount of underlying tokens to transfer.
+    /// * `shares` - Amount of shares to mint.
+    /// * `share_token` - Write access to the [`IErc4626Shares`] of the vault.
+    ///
+    /// # Errors
+    ///
+    /// * [`safe_erc20::Error::SafeErc20FailedOperation`] - If token transfer
+    ///   fails.
+    /// * If `share_token` fails to mint the shares.
+    ///
+    /// # Events
+    ///
+    /// * [`Deposit`]
+    pub fn _deposit_with<S, E>(
+        &mut self,
+        caller: Address,
+        receiver: Address,
+        assets: U256,
+        shares: U256,
+        share_token: &mut S,
+    ) -> Result<(), E>
+    where
+        S: IErc4626Shares,
+        E: From<safe_erc20::Error> + From<S::Error>,
+    {
         // If asset() is ERC-777, `transfer_from` can trigger a reentrancy
         // BEFORE the transfer happens through the `tokens_to_send` hook. On the
         // other hand, the `token_received` hook, that is triggered after the
@@ -1076,7 +1307,7 @@ impl Erc4626 {
             assets,
         )?;
 
-        erc20._mint(receiver, shares)?;
+        share_token.mint_shares(receiver, shares, assets)?;
 
         evm::log(Deposit { sender: caller, owner: receiver, assets, shares });
 
@@ -1104,7 +1335,7 @@ impl Erc4626 {
     /// # Events
     ///
     /// * [`Withdraw`]
-    fn _withdraw(
+    pub fn _withdraw(
         &mut self,
         caller: Address,
         receiver: Address,
@@ -1113,10 +1344,42 @@ impl Erc4626 {
         shares: U256,
         erc20: &mut Erc20,
     ) -> Result<(), Error> {
-        if caller != owner {
-            erc20._spend_allowance(owner, caller, shares)?;
-        }
+        self._withdraw_with(caller, receiver, owner, assets, shares, erc20)
+    }
 
+    /// Withdraw/redeem common workflow, burning the shares of `share_token`.
+    ///
+    /// # Arguments
+    ///
+    /// * `caller` - Address initiating the withdrawal.
+    /// * `receiver` - Address receiving the assets.
+    /// * `owner` - Address owning the shares.
+    /// * `assets` - Amount of underlying tokens to transfer.
+    /// * `shares` - Amount of shares to burn.
+    /// * `share_token` - Write access to the [`IErc4626Shares`] of the vault.
+    ///
+    /// # Errors
+    ///
+    /// * If `share_token` fails to burn the shares, e.g. when `caller` lacks
+    ///   allowance.
+    /// * [`safe_erc20::Error::SafeErc20FailedOperation`] - If transfer fails.
+    ///
+    /// # Events
+    ///
+    /// * [`Withdraw`]
+    pub fn _withdraw_with<S, E>(
+        &mut self,
+        caller: Address,
+        receiver: Address,
+        owner: Address,
+        assets: U256,
+        shares: U256,
+        share_token: &mut S,
+    ) -> Result<(), E>
+    where
+        S: IErc4626Shares,
+        E: From<safe_erc20::Error> + From<S::Error>,
+    {
         // If asset() is ERC-777, `transfer` can trigger a reentrancy AFTER the
         // transfer happens through the `tokens_received` hook. On the other
         // hand, the `tokens_to_send` hook, that is triggered before the
@@ -1126,7 +1389,7 @@ impl Erc4626 {
         // reentrancy would happen after the shares are burned and after the
         // assets are transferred, which is a valid state.
 
-        erc20._burn(owner, shares)?;
+        share_token.burn_shares(owner, caller, shares, assets)?;
 
         self.safe_erc20.safe_transfer(self.asset(), receiver, assets)?;
 
@@ -1138,7 +1401,7 @@ impl Erc4626 {
     /// Returns the decimals offset between the underlying asset and vault
     /// shares.
     /// Currently, always returns `U8::ZERO`.
-    fn _decimals_offset(&self) -> U8 {
+    pub fn _decimals_offset(&self) -> U8 {
         self.decimals_offset.get()
     }
 }
@@ -0,0 +1,787 @@
+//! ERC-4626 vault with entry and exit fees, expressed in [basis points].
+//!
+//! The entry fee is charged on deposits and mints, and the exit fee on
+//! withdrawals and redemptions. Each fee is sent to its own recipient. If the
+//! recipient is the zero address or the vault itself, the fee stays in the
+//! vault and accrues to the shareholders.
+//!
+//! As required by [ERC-4626], the `preview_*` functions include the fees, and
+//! every fee is rounded up, in favor of the vault.
+//!
+//! [basis points]: https://en.wikipedia.org/wiki/Basis_point
+//! [ERC-4626]: https://eips.ethereum.org/EIPS/eip-4626
+
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{uint, Address, U256};
+pub use sol::*;
+use stylus_sdk::{
+    contract, msg,
+    prelude::storage,
+    storage::{StorageAddress, StorageU256, TopLevelStorage},
+    stylus_proc::SolidityError,
+};
+
+use crate::{
+    token::erc20::{
+        extensions::{
+            erc4626::{
+                self, ERC4626ExceededMaxDeposit, ERC4626ExceededMaxMint,
+                ERC4626ExceededMaxRedeem, ERC4626ExceededMaxWithdraw,
+            },
+            Erc4626, IErc4626,
+        },
+        utils::ISafeErc20,
+        Erc20,
+    },
+    utils::math::alloy::{Math, Rounding},
+};
+
+/// Denominator of the fees, which are expressed in basis points.
+pub const FEE_DENOMINATOR: U256 = uint!(10_000_U256);
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Indicates that a fee is greater than 100%.
+        ///
+        /// * `basis_points` - Invalid fee, in basis points.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4626InvalidFee(uint256 basis_points);
+    }
+}
+
+/// An [`Erc4626Fees`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates that a fee is greater than 100%.
+    InvalidFee(ERC4626InvalidFee),
+    /// Error type from [`Erc4626`] contract [`erc4626::Error`].
+    Erc4626(erc4626::Error),
+}
+
+/// State of an [`Erc4626Fees`] Contract.
+#[storage]
+pub struct Erc4626Fees {
+    /// [`Erc4626`] contract.
+    pub erc4626: Erc4626,
+    /// Fee charged on deposits and mints, in basis points.
+    pub(crate) entry_fee_basis_points: StorageU256,
+    /// Fee charged on withdrawals and redemptions, in basis points.
+    pub(crate) exit_fee_basis_points: StorageU256,
+    /// Recipient of the entry fee.
+    pub(crate) entry_fee_recipient: StorageAddress,
+    /// Recipient of the exit fee.
+    pub(crate) exit_fee_recipient: StorageAddress,
+}
+
+impl Deref for Erc4626Fees {
+    type Target = Erc4626;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc4626
+    }
+}
+
+impl DerefMut for Erc4626Fees {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc4626
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc4626Fees {}
+
+impl IErc4626 for Erc4626Fees {
+    type Error = Error;
+
+    fn asset(&self) -> Address {
+        self.erc4626.asset()
+    }
+
+    fn total_assets(&mut self) -> Result<U256, Self::Error> {
+        Ok(self.erc4626.total_assets()?)
+    }
+
+    fn convert_to_shares(
+        &mut self,
+        assets: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.erc4626.convert_to_shares(assets, erc20)?)
+    }
+
+    fn convert_to_assets(
+        &mut self,
+        shares: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.erc4626.convert_to_assets(shares, erc20)?)
+    }
+
+    fn max_deposit(&self, receiver: Address) -> U256 {
+        self.erc4626.max_deposit(receiver)
+    }
+
+    fn max_mint(&self, receiver: Address) -> U256 {
+        self.erc4626.max_mint(receiver)
+    }
+
+    fn max_withdraw(
+        &mut self,
+        owner: Address,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.erc4626.max_withdraw(owner, erc20)?)
+    }
+
+    fn max_redeem(&self, owner: Address, erc20: &Erc20) -> U256 {
+        self.erc4626.max_redeem(owner, erc20)
+    }
+
+    fn preview_deposit(
+        &mut self,
+        assets: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        let fee = Self::_fee_on_total(assets, self.entry_fee_basis_points());
+        Ok(self.erc4626.preview_deposit(assets - fee, erc20)?)
+    }
+
+    fn preview_mint(
+        &mut self,
+        shares: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        let assets = self.erc4626.preview_mint(shares, erc20)?;
+        let fee = Self::_fee_on_raw(assets, self.entry_fee_basis_points());
+        Ok(assets
+            .checked_add(fee)
+            .expect("assets and fee should not exceed `U256::MAX`"))
+    }
+
+    fn preview_withdraw(
+        &mut self,
+        assets: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        let fee = Self::_fee_on_raw(assets, self.exit_fee_basis_points());
+        let assets = assets
+            .checked_add(fee)
+            .expect("assets and fee should not exceed `U256::MAX`");
+        Ok(self.erc4626.preview_withdraw(assets, erc20)?)
+    }
+
+    fn preview_redeem(
+        &mut self,
+        shares: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        let assets = self.erc4626.preview_redeem(shares, erc20)?;
+        let fee = Self::_fee_on_total(assets, self.exit_fee_basis_points());
+        Ok(assets - fee)
+    }
+
+    fn deposit(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        let max_assets = self.max_deposit(receiver);
+        if assets > max_assets {
+            return Err(erc4626::Error::ExceededMaxDeposit(
+                ERC4626ExceededMaxDeposit { receiver, assets, max: max_assets },
+            )
+            .into());
+        }
+
+        let shares = self.preview_deposit(assets, erc20)?;
+        self._deposit(msg::sender(), receiver, assets, shares, erc20)?;
+
+        Ok(shares)
+    }
+
+    fn mint(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        let max_shares = self.max_mint(receiver);
+        if shares > max_shares {
+            return Err(erc4626::Error::ExceededMaxMint(
+                ERC4626ExceededMaxMint { receiver, shares, max: max_shares },
+            )
+            .into());
+        }
+
+        let assets = self.preview_mint(shares, erc20)?;
+        self._deposit(msg::sender(), receiver, assets, shares, erc20)?;
+
+        Ok(assets)
+    }
+
+    fn withdraw(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        owner: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        let max_assets = self.max_withdraw(owner, erc20)?;
+        if assets > max_assets {
+            return Err(erc4626::Error::ExceededMaxWithdraw(
+                ERC4626ExceededMaxWithdraw { owner, assets, max: max_assets },
+            )
+            .into());
+        }
+
+        let shares = self.preview_withdraw(assets, erc20)?;
+        self._withdraw(msg::sender(), receiver, owner, assets, shares, erc20)?;
+
+        Ok(shares)
+    }
+
+    fn redeem(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        owner: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        let max_shares = self.max_redeem(owner, erc20);
+        if shares > max_shares {
+            return Err(erc4626::Error::ExceededMaxRedeem(
+                ERC4626ExceededMaxRedeem { owner, shares, max: max_shares },
+            )
+            .into());
+        }
+
+        let assets = self.preview_redeem(shares, erc20)?;
+        self._withdraw(msg::sender(), receiver, owner, assets, shares, erc20)?;
+
+        Ok(assets)
+    }
+}
+
+impl Erc4626Fees {
+    /// Returns the fee charged on deposits and mints, in basis points.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn entry_fee_basis_points(&self) -> U256 {
+        self.entry_fee_basis_points.get()
+    }
+
+    /// Returns the fee charged on withdrawals and redemptions, in basis
+    /// points.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn exit_fee_basis_points(&self) -> U256 {
+        self.exit_fee_basis_points.get()
+    }
+
+    /// Returns the recipient of the entry fee.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn entry_fee_recipient(&self) -> Address {
+        self.entry_fee_recipient.get()
+    }
+
+    /// Returns the recipient of the exit fee.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn exit_fee_recipient(&self) -> Address {
+        self.exit_fee_recipient.get()
+    }
+
+    /// Sets the fee charged on deposits and mints, and its recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `basis_points` - Fee, in basis points.
+    /// * `recipient` - Recipient of the fee.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidFee`] - If `basis_points` is greater than
+    ///   [`FEE_DENOMINATOR`].
+    pub fn _set_entry_fee(
+        &mut self,
+        basis_points: U256,
+        recipient: Address,
+    ) -> Result<(), Error> {
+        Self::_check_fee(basis_points)?;
+        self.entry_fee_basis_points.set(basis_points);
+        self.entry_fee_recipient.set(recipient);
+        Ok(())
+    }
+
+    /// Sets the fee charged on withdrawals and redemptions, and its
+    /// recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `basis_points` - Fee, in basis points.
+    /// * `recipient` - Recipient of the fee.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidFee`] - If `basis_points` is greater than
+    ///   [`FEE_DENOMINATOR`].
+    pub fn _set_exit_fee(
+        &mut self,
+        basis_points: U256,
+        recipient: Address,
+    ) -> Result<(), Error> {
+        Self::_check_fee(basis_points)?;
+        self.exit_fee_basis_points.set(basis_points);
+        self.exit_fee_recipient.set(recipient);
+        Ok(())
+    }
+
+    /// Deposit/mint common workflow, sending the entry fee to its recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `caller` - Address initiating the deposit.
+    /// * `receiver` - Address receiving the minted shares.
+    /// * `assets` - Amount of underlying tokens to transfer, fee included.
+    /// * `shares` - Amount of shares to mint.
+    /// * `erc20` - Write access to an [`Erc20`] contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc4626::Error::SafeErc20`] - If a token transfer fails.
+    /// * [`erc4626::Error::Erc20`] - If `receiver` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc4626::Deposit`]
+    pub fn _deposit(
+        &mut self,
+        caller: Address,
+        receiver: Address,
+        assets: U256,
+        shares: U256,
+        erc20: &mut Erc20,
+    ) -> Result<(), Error> {
+        let fee = Self::_fee_on_total(assets, self.entry_fee_basis_points());
+        let recipient = self.entry_fee_recipient();
+
+        self.erc4626._deposit(caller, receiver, assets, shares, erc20)?;
+        self._transfer_fee(recipient, fee)
+    }
+
+    /// Withdraw/redeem common workflow, sending the exit fee to its
+    /// recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `caller` - Address initiating the withdrawal.
+    /// * `receiver` - Address receiving the assets.
+    /// * `owner` - Address owning the shares.
+    /// * `assets` - Amount of underlying tokens sent to `receiver`, fee
+    ///   excluded.
+    /// * `shares` - Amount of shares to burn.
+    /// * `erc20` - Write access to an [`Erc20`] contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc4626::Error::Erc20`] - If `caller` needs allowance or `owner`
+    ///   lacks shares.
+    /// * [`erc4626::Error::SafeErc20`] - If a token transfer fails.
+    ///
+    /// # Events
+    ///
+    /// * [`erc4626::Withdraw`]
+    pub fn _withdraw(
+        &mut self,
+        caller: Address,
+        receiver: Address,
+        owner: Address,
+        assets: U256,
+        shares: U256,
+        erc20: &mut Erc20,
+    ) -> Result<(), Error> {
+        let fee = Self::_fee_on_raw(assets, self.exit_fee_basis_points());
+        let recipient = self.exit_fee_recipient();
+
+        self.erc4626
+            ._withdraw(caller, receiver, owner, assets, shares, erc20)?;
+        self._transfer_fee(recipient, fee)
+    }
+
+    /// Returns the fee to add to `assets` that do not include it yet, rounded
+    /// up.
+    ///
+    /// # Arguments
+    ///
+    /// * `assets` - Amount of assets, fee excluded.
+    /// * `basis_points` - Fee, in basis points.
+    #[must_use]
+    pub fn _fee_on_raw(assets: U256, basis_points: U256) -> U256 {
+        assets.mul_div(basis_points, FEE_DENOMINATOR, Rounding::Ceil)
+    }
+
+    /// Returns the part of `assets` that is a fee, rounded up.
+    ///
+    /// # Arguments
+    ///
+    /// * `assets` - Amount of assets, fee included.
+    /// * `basis_points` - Fee, in basis points.
+    #[must_use]
+    pub fn _fee_on_total(assets: U256, basis_points: U256) -> U256 {
+        assets.mul_div(
+            basis_points,
+            basis_points + FEE_DENOMINATOR,
+            Rounding::Ceil,
+        )
+    }
+
+    /// Sends `fee` assets to `recipient`, unless the fee stays in the vault.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `recipient` - Recipient of the fee.
+    /// * `fee` - Amount of assets.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc4626::Error::SafeErc20`] - If the transfer fails.
+    fn _transfer_fee(
+        &mut self,
+        recipient: Address,
+        fee: U256,
+    ) -> Result<(), Error> {
+        if fee.is_zero()
+            || recipient.is_zero()
+            || recipient == contract::address()
+        {
+            return Ok(());
+        }
+
+        let asset = self.asset();
+        self.erc4626
+            .safe_erc20
+            .safe_transfer(asset, recipient, fee)
+            .map_err(|e| Error::Erc4626(erc4626::Error::SafeErc20(e)))
+    }
+
+    /// Checks that `basis_points` is a valid fee.
+    ///
+    /// # Arguments
+    ///
+    /// * `basis_points` - Fee, in basis points.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidFee`] - If `basis_points` is greater than
+    ///   [`FEE_DENOMINATOR`].
+    fn _check_fee(basis_points: U256) -> Result<(), Error> {
+        if basis_points > FEE_DENOMINATOR {
+            return Err(Error::InvalidFee(ERC4626InvalidFee { basis_points }));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::*;
+
+    use super::{ERC4626InvalidFee, Erc4626Fees, Error};
+    use crate::token::erc20::{extensions::IErc4626, Erc20, IErc20};
+
+    #[storage]
+    struct Erc4626FeesTestExample {
+        vault: Erc4626Fees,
+        erc20: Erc20,
+    }
+
+    #[public]
+    impl Erc4626FeesTestExample {
+        fn preview_deposit(&mut self, assets: U256) -> Result<U256, Error> {
+            self.vault.preview_deposit(assets, &self.erc20)
+        }
+
+        fn preview_mint(&mut self, shares: U256) -> Result<U256, Error> {
+            self.vault.preview_mint(shares, &self.erc20)
+        }
+
+        fn preview_withdraw(&mut self, assets: U256) -> Result<U256, Error> {
+            self.vault.preview_withdraw(assets, &self.erc20)
+        }
+
+        fn preview_redeem(&mut self, shares: U256) -> Result<U256, Error> {
+            self.vault.preview_redeem(shares, &self.erc20)
+        }
+
+        fn deposit(
+            &mut self,
+            assets: U256,
+            receiver: Address,
+        ) -> Result<U256, Error> {
+            self.vault.deposit(assets, receiver, &mut self.erc20)
+        }
+
+        fn mint(
+            &mut self,
+            shares: U256,
+            receiver: Address,
+        ) -> Result<U256, Error> {
+            self.vault.mint(shares, receiver, &mut self.erc20)
+        }
+
+        fn withdraw(
+            &mut self,
+            assets: U256,
+            receiver: Address,
+            owner: Address,
+        ) -> Result<U256, Error> {
+            self.vault.withdraw(assets, receiver, owner, &mut self.erc20)
+        }
+
+        fn redeem(
+            &mut self,
+            shares: U256,
+            receiver: Address,
+            owner: Address,
+        ) -> Result<U256, Error> {
+            self.vault.redeem(shares, receiver, owner, &mut self.erc20)
+        }
+    }
+
+    unsafe impl TopLevelStorage for Erc4626FeesTestExample {}
+
+    const ALICE_ASSETS: U256 = uint!(100_000_U256);
+    // 1%
+    const ENTRY_FEE: U256 = uint!(100_U256);
+    // 2%
+    const EXIT_FEE: U256 = uint!(200_U256);
+
+    fn setup(
+        vault: &Contract<Erc4626FeesTestExample>,
+        asset: &Contract<Erc20>,
+        alice: Address,
+        entry_recipient: Address,
+        exit_recipient: Address,
+    ) {
+        let asset_addr = asset.address();
+        vault.init(alice, |vault| {
+            vault.vault.erc4626.asset.set(asset_addr);
+            vault
+                .vault
+                ._set_entry_fee(ENTRY_FEE, entry_recipient)
+                .expect("should set the entry fee");
+            vault
+                .vault
+                ._set_exit_fee(EXIT_FEE, exit_recipient)
+                .expect("should set the exit fee");
+        });
+        asset.init(alice, |asset| {
+            asset._mint(alice, ALICE_ASSETS).expect("should mint assets");
+        });
+        asset
+            .sender(alice)
+            .approve(vault.address(), U256::MAX)
+            .expect("should approve the vault");
+    }
+
+    #[motsu::test]
+    fn fee_rounding_favors_the_vault() {
+        assert_eq!(
+            Erc4626Fees::_fee_on_raw(uint!(101_U256), ENTRY_FEE),
+            uint!(2_U256)
+        );
+        assert_eq!(
+            Erc4626Fees::_fee_on_total(uint!(1010_U256), ENTRY_FEE),
+            uint!(10_U256)
+        );
+        assert_eq!(
+            Erc4626Fees::_fee_on_total(uint!(1011_U256), ENTRY_FEE),
+            uint!(11_U256)
+        );
+        assert_eq!(Erc4626Fees::_fee_on_raw(U256::MAX, U256::ZERO), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn set_fee_reverts_when_above_denominator(
+        contract: Contract<Erc4626FeesTestExample>,
+        alice: Address,
+    ) {
+        let invalid = uint!(10_001_U256);
+        let err = contract
+            .sender(alice)
+            .vault
+            ._set_entry_fee(invalid, alice)
+            .expect_err("should return Error::InvalidFee");
+        assert!(matches!(
+            err,
+            Error::InvalidFee(ERC4626InvalidFee { basis_points })
+                if basis_points == invalid
+        ));
+
+        let err = contract
+            .sender(alice)
+            .vault
+            ._set_exit_fee(invalid, alice)
+            .expect_err("should return Error::InvalidFee");
+        assert!(matches!(err, Error::InvalidFee(_)));
+    }
+
+    #[motsu::test]
+    fn deposit_charges_entry_fee(
+        vault: Contract<Erc4626FeesTestExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+        charlie: Address,
+    ) {
+        setup(&vault, &asset, alice, bob, charlie);
+
+        let assets = uint!(1010_U256);
+        let preview = vault
+            .sender(alice)
+            .preview_deposit(assets)
+            .expect("should preview the deposit");
+        assert_eq!(preview, uint!(1000_U256));
+
+        let shares = vault
+            .sender(alice)
+            .deposit(assets, alice)
+            .expect("should deposit assets");
+        assert_eq!(shares, preview);
+
+        assert_eq!(vault.sender(alice).erc20.balance_of(alice), shares);
+        assert_eq!(asset.sender(alice).balance_of(bob), uint!(10_U256));
+        assert_eq!(
+            asset.sender(alice).balance_of(vault.address()),
+            uint!(1000_U256)
+        );
+        assert_eq!(
+            asset.sender(alice).balance_of(alice),
+            ALICE_ASSETS - assets
+        );
+    }
+
+    #[motsu::test]
+    fn mint_charges_entry_fee(
+        vault: Contract<Erc4626FeesTestExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+        charlie: Address,
+    ) {
+        setup(&vault, &asset, alice, bob, charlie);
+
+        let shares = uint!(1000_U256);
+        let preview = vault
+            .sender(alice)
+            .preview_mint(shares)
+            .expect("should preview the mint");
+        assert_eq!(preview, uint!(1010_U256));
+
+        let assets =
+            vault.sender(alice).mint(shares, alice).expect("should mint");
+        assert_eq!(assets, preview);
+
+        assert_eq!(vault.sender(alice).erc20.balance_of(alice), shares);
+        assert_eq!(asset.sender(alice).balance_of(bob), uint!(10_U256));
+        assert_eq!(
+            asset.sender(alice).balance_of(alice),
+            ALICE_ASSETS - assets
+        );
+    }
+
+    #[motsu::test]
+    fn withdraw_and_redeem_charge_exit_fee(
+        vault: Contract<Erc4626FeesTestExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+        charlie: Address,
+    ) {
+        setup(&vault, &asset, alice, bob, charlie);
+        vault
+            .sender(alice)
+            .deposit(uint!(1010_U256), alice)
+            .expect("should deposit assets");
+
+        let assets = uint!(500_U256);
+        let preview = vault
+            .sender(alice)
+            .preview_withdraw(assets)
+            .expect("should preview the withdrawal");
+        assert_eq!(preview, uint!(510_U256));
+
+        let shares = vault
+            .sender(alice)
+            .withdraw(assets, alice, alice)
+            .expect("should withdraw assets");
+        assert_eq!(shares, preview);
+        assert_eq!(asset.sender(alice).balance_of(charlie), uint!(10_U256));
+        assert_eq!(
+            vault.sender(alice).erc20.balance_of(alice),
+            uint!(490_U256)
+        );
+
+        let preview = vault
+            .sender(alice)
+            .preview_redeem(uint!(490_U256))
+            .expect("should preview the redemption");
+        assert_eq!(preview, uint!(480_U256));
+
+        let assets = vault
+            .sender(alice)
+            .redeem(uint!(490_U256), alice, alice)
+            .expect("should redeem shares");
+        assert_eq!(assets, preview);
+        assert_eq!(asset.sender(alice).balance_of(charlie), uint!(20_U256));
+        assert_eq!(vault.sender(alice).erc20.balance_of(alice), U256::ZERO);
+        assert_eq!(asset.sender(alice).balance_of(vault.address()), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn fee_stays_in_vault_without_recipient(
+        vault: Contract<Erc4626FeesTestExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+    ) {
+        setup(&vault, &asset, alice, Address::ZERO, Address::ZERO);
+
+        vault
+            .sender(alice)
+            .deposit(uint!(1010_U256), alice)
+            .expect("should deposit assets");
+
+        assert_eq!(
+            asset.sender(alice).balance_of(vault.address()),
+            uint!(1010_U256)
+        );
+    }
+}
@@ -0,0 +1,736 @@
+//! ERC-4626 vault with protection against the [inflation attack].
+//!
+//! An attacker front-running the first deposit of an empty vault can deposit
+//! a single asset and donate assets to the vault, so that the shares of the
+//! victim round down to zero and the donation is shared with the attacker.
+//! [`Erc4626Protected`] combines three opt-in defenses, configured at
+//! deployment through [`Erc4626Protected::_set_inflation_protection`]:
+//!
+//! * A decimals offset, which adds virtual shares and assets to the conversions
+//!   (see [`Erc4626::_decimals_offset`]). The attack stays possible but costs
+//!   the attacker `10^offset` times what it makes the victim lose.
+//! * Dead shares, taken from the first deposit and minted to
+//!   [`DEAD_SHARES_HOLDER`], so that the vault never goes back to an empty
+//!   supply.
+//! * A minimum initial deposit, so that the first depositor cannot start the
+//!   vault with a dust amount.
+//!
+//! As required by [ERC-4626], the `preview_*` functions account for the dead
+//! shares of the first deposit.
+//!
+//! [inflation attack]: https://docs.openzeppelin.com/contracts/5.x/erc4626#inflation-attack
+//! [ERC-4626]: https://eips.ethereum.org/EIPS/eip-4626
+
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{address, Address, U256, U8};
+pub use sol::*;
+use stylus_sdk::{
+    msg,
+    prelude::storage,
+    storage::{StorageU256, TopLevelStorage},
+    stylus_proc::SolidityError,
+};
+
+use crate::token::erc20::{
+    self,
+    extensions::{
+        erc4626::{
+            self, ERC4626ExceededMaxDeposit, ERC4626ExceededMaxMint,
+            ERC4626ExceededMaxRedeem, ERC4626ExceededMaxWithdraw,
+        },
+        Erc4626, IErc4626,
+    },
+    Erc20, IErc20,
+};
+
+/// Holder of the dead shares, which can never be redeemed.
+pub const DEAD_SHARES_HOLDER: Address =
+    address!("000000000000000000000000000000000000dEaD");
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Indicates a first deposit below the minimum initial deposit.
+        ///
+        /// * `assets` - Amount of assets deposited.
+        /// * `min` - Minimum initial deposit.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4626InsufficientInitialDeposit(uint256 assets, uint256 min);
+
+        /// Indicates a first deposit whose shares do not cover the dead
+        /// shares.
+        ///
+        /// * `shares` - Amount of shares of the deposit.
+        /// * `dead_shares` - Amount of dead shares.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4626InsufficientInitialShares(uint256 shares, uint256 dead_shares);
+    }
+}
+
+/// An [`Erc4626Protected`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates a first deposit below the minimum initial deposit.
+    InsufficientInitialDeposit(ERC4626InsufficientInitialDeposit),
+    /// Indicates a first deposit whose shares do not cover the dead shares.
+    InsufficientInitialShares(ERC4626InsufficientInitialShares),
+    /// Error type from [`Erc4626`] contract [`erc4626::Error`].
+    Erc4626(erc4626::Error),
+}
+
+impl From<erc20::Error> for Error {
+    fn from(value: erc20::Error) -> Self {
+        Error::Erc4626(erc4626::Error::Erc20(value))
+    }
+}
+
+/// Inflation attack protection policy of an [`Erc4626Protected`] vault.
+///
+/// The default policy disables every defense, which behaves like
+/// [`Erc4626`] with a zero decimals offset.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InflationProtection {
+    /// Decimals offset between the asset and the shares.
+    pub decimals_offset: U8,
+    /// Shares taken from the first deposit and burned forever.
+    pub dead_shares: U256,
+    /// Minimum amount of assets of the first deposit.
+    pub min_initial_deposit: U256,
+}
+
+impl InflationProtection {
+    /// Policy combining the three defenses, which suits assets with 6 or
+    /// more decimals.
+    ///
+    /// A decimals offset of 3 makes an attack cost a thousand times what it
+    /// makes the victim lose, while the dead shares and the minimum initial
+    /// deposit keep the vault from starting empty or with dust.
+    pub const RECOMMENDED: Self = Self {
+        decimals_offset: U8::from_limbs([3]),
+        dead_shares: U256::from_limbs([1000, 0, 0, 0]),
+        min_initial_deposit: U256::from_limbs([1000, 0, 0, 0]),
+    };
+}
+
+/// State of an [`Erc4626Protected`] Contract.
+#[storage]
+pub struct Erc4626Protected {
+    /// [`Erc4626`] contract.
+    pub erc4626: Erc4626,
+    /// Shares taken from the first deposit.
+    pub(crate) dead_shares: StorageU256,
+    /// Minimum amount of assets of the first deposit.
+    pub(crate) min_initial_deposit: StorageU256,
+}
+
+impl Deref for Erc4626Protected {
+    type Target = Erc4626;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc4626
+    }
+}
+
+impl DerefMut for Erc4626Protected {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc4626
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc4626Protected {}
+
+impl IErc4626 for Erc4626Protected {
+    type Error = Error;
+
+    fn asset(&self) -> Address {
+        self.erc4626.asset()
+    }
+
+    fn total_assets(&mut self) -> Result<U256, Self::Error> {
+        Ok(self.erc4626.total_assets()?)
+    }
+
+    fn convert_to_shares(
+        &mut self,
+        assets: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.erc4626.convert_to_shares(assets, erc20)?)
+    }
+
+    fn convert_to_assets(
+        &mut self,
+        shares: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.erc4626.convert_to_assets(shares, erc20)?)
+    }
+
+    fn max_deposit(&self, receiver: Address) -> U256 {
+        self.erc4626.max_deposit(receiver)
+    }
+
+    fn max_mint(&self, receiver: Address) -> U256 {
+        self.erc4626.max_mint(receiver)
+    }
+
+    fn max_withdraw(
+        &mut self,
+        owner: Address,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.erc4626.max_withdraw(owner, erc20)?)
+    }
+
+    fn max_redeem(&self, owner: Address, erc20: &Erc20) -> U256 {
+        self.erc4626.max_redeem(owner, erc20)
+    }
+
+    /// Returns the shares of a deposit of `assets`, minus the dead shares on
+    /// the first deposit.
+    fn preview_deposit(
+        &mut self,
+        assets: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        let shares = self.erc4626.preview_deposit(assets, erc20)?;
+        if !erc20.total_supply().is_zero() {
+            return Ok(shares);
+        }
+
+        let dead_shares = self.dead_shares();
+        if !dead_shares.is_zero() && shares <= dead_shares {
+            return Err(Error::InsufficientInitialShares(
+                ERC4626InsufficientInitialShares { shares, dead_shares },
+            ));
+        }
+        Ok(shares - dead_shares)
+    }
+
+    /// Returns the assets of a mint of `shares`, plus the dead shares on the
+    /// first deposit.
+    fn preview_mint(
+        &mut self,
+        shares: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        let shares = if erc20.total_supply().is_zero() {
+            shares
+                .checked_add(self.dead_shares())
+                .expect("shares and dead shares should not exceed `U256::MAX`")
+        } else {
+            shares
+        };
+        Ok(self.erc4626.preview_mint(shares, erc20)?)
+    }
+
+    fn preview_withdraw(
+        &mut self,
+        assets: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.erc4626.preview_withdraw(assets, erc20)?)
+    }
+
+    fn preview_redeem(
+        &mut self,
+        shares: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.erc4626.preview_redeem(shares, erc20)?)
+    }
+
+    fn deposit(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        let max_assets = self.max_deposit(receiver);
+        if assets > max_assets {
+            return Err(erc4626::Error::ExceededMaxDeposit(
+                ERC4626ExceededMaxDeposit { receiver, assets, max: max_assets },
+            )
+            .into());
+        }
+
+        self._check_initial_deposit(assets, erc20)?;
+        let shares = self.preview_deposit(assets, erc20)?;
+        self._deposit(msg::sender(), receiver, assets, shares, erc20)?;
+
+        Ok(shares)
+    }
+
+    fn mint(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        let max_shares = self.max_mint(receiver);
+        if shares > max_shares {
+            return Err(erc4626::Error::ExceededMaxMint(
+                ERC4626ExceededMaxMint { receiver, shares, max: max_shares },
+            )
+            .into());
+        }
+
+        let assets = self.preview_mint(shares, erc20)?;
+        self._deposit(msg::sender(), receiver, assets, shares, erc20)?;
+
+        Ok(assets)
+    }
+
+    fn withdraw(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        owner: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        let max_assets = self.max_withdraw(owner, erc20)?;
+        if assets > max_assets {
+            return Err(erc4626::Error::ExceededMaxWithdraw(
+                ERC4626ExceededMaxWithdraw { owner, assets, max: max_assets },
+            )
+            .into());
+        }
+
+        let shares = self.preview_withdraw(assets, erc20)?;
+        self.erc4626._withdraw(
+            msg::sender(),
+            receiver,
+            owner,
+            assets,
+            shares,
+            erc20,
+        )?;
+
+        Ok(shares)
+    }
+
+    fn redeem(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        owner: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        let max_shares = self.max_redeem(owner, erc20);
+        if shares > max_shares {
+            return Err(erc4626::Error::ExceededMaxRedeem(
+                ERC4626ExceededMaxRedeem { owner, shares, max: max_shares },
+            )
+            .into());
+        }
+
+        let assets = self.preview_redeem(shares, erc20)?;
+        self.erc4626._withdraw(
+            msg::sender(),
+            receiver,
+            owner,
+            assets,
+            shares,
+            erc20,
+        )?;
+
+        Ok(assets)
+    }
+}
+
+impl Erc4626Protected {
+    /// Returns the shares taken from the first deposit.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn dead_shares(&self) -> U256 {
+        self.dead_shares.get()
+    }
+
+    /// Returns the minimum amount of assets of the first deposit.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn min_initial_deposit(&self) -> U256 {
+        self.min_initial_deposit.get()
+    }
+
+    /// Sets the inflation attack protection `policy`.
+    ///
+    /// NOTE: This function should only be called at deployment, before the
+    /// first deposit, since changing the decimals offset changes the price of
+    /// existing shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `policy` - Protection policy, e.g.
+    ///   [`InflationProtection::RECOMMENDED`].
+    pub fn _set_inflation_protection(&mut self, policy: InflationProtection) {
+        self.erc4626.decimals_offset.set(policy.decimals_offset);
+        self.dead_shares.set(policy.dead_shares);
+        self.min_initial_deposit.set(policy.min_initial_deposit);
+    }
+
+    /// Deposit/mint common workflow, minting the dead shares on the first
+    /// deposit.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `caller` - Address initiating the deposit.
+    /// * `receiver` - Address receiving the minted shares.
+    /// * `assets` - Amount of underlying tokens to transfer.
+    /// * `shares` - Amount of shares to mint to `receiver`, dead shares
+    ///   excluded.
+    /// * `erc20` - Write access to an [`Erc20`] contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientInitialDeposit`] - If this is the first deposit
+    ///   and `assets` is below [`Self::min_initial_deposit`].
+    /// * [`erc4626::Error::SafeErc20`] - If a token transfer fails.
+    /// * [`erc4626::Error::Erc20`] - If `receiver` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc4626::Deposit`]
+    pub fn _deposit(
+        &mut self,
+        caller: Address,
+        receiver: Address,
+        assets: U256,
+        shares: U256,
+        erc20: &mut Erc20,
+    ) -> Result<(), Error> {
+        let first_deposit = erc20.total_supply().is_zero();
+        self._check_initial_deposit(assets, erc20)?;
+
+        self.erc4626._deposit(caller, receiver, assets, shares, erc20)?;
+
+        let dead_shares = self.dead_shares();
+        if first_deposit && !dead_shares.is_zero() {
+            erc20._mint(DEAD_SHARES_HOLDER, dead_shares)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a deposit of `assets` is at least the minimum initial
+    /// deposit, if it is the first one.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientInitialDeposit`] - Otherwise.
+    fn _check_initial_deposit(
+        &self,
+        assets: U256,
+        erc20: &Erc20,
+    ) -> Result<(), Error> {
+        let min = self.min_initial_deposit();
+        if erc20.total_supply().is_zero() && assets < min {
+            return Err(Error::InsufficientInitialDeposit(
+                ERC4626InsufficientInitialDeposit { assets, min },
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256, U8};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::*;
+
+    use super::{
+        ERC4626InsufficientInitialDeposit, ERC4626InsufficientInitialShares,
+        Erc4626Protected, Error, InflationProtection, DEAD_SHARES_HOLDER,
+    };
+    use crate::token::erc20::{extensions::IErc4626, Erc20, IErc20};
+
+    #[storage]
+    struct Erc4626ProtectedTestExample {
+        vault: Erc4626Protected,
+        erc20: Erc20,
+    }
+
+    #[public]
+    impl Erc4626ProtectedTestExample {
+        fn preview_mint(&mut self, shares: U256) -> Result<U256, Error> {
+            self.vault.preview_mint(shares, &self.erc20)
+        }
+
+        fn deposit(
+            &mut self,
+            assets: U256,
+            receiver: Address,
+        ) -> Result<U256, Error> {
+            self.vault.deposit(assets, receiver, &mut self.erc20)
+        }
+
+        fn mint(
+            &mut self,
+            shares: U256,
+            receiver: Address,
+        ) -> Result<U256, Error> {
+            self.vault.mint(shares, receiver, &mut self.erc20)
+        }
+
+        fn redeem(
+            &mut self,
+            shares: U256,
+            receiver: Address,
+            owner: Address,
+        ) -> Result<U256, Error> {
+            self.vault.redeem(shares, receiver, owner, &mut self.erc20)
+        }
+
+        fn max_withdraw(&mut self, owner: Address) -> Result<U256, Error> {
+            self.vault.max_withdraw(owner, &self.erc20)
+        }
+    }
+
+    unsafe impl TopLevelStorage for Erc4626ProtectedTestExample {}
+
+    const VICTIM_DEPOSIT: U256 = uint!(10_000_U256);
+    const DONATION: U256 = uint!(20_000_U256);
+    const ATTACKER_ASSETS: U256 = uint!(100_000_U256);
+
+    fn setup(
+        vault: &Contract<Erc4626ProtectedTestExample>,
+        asset: &Contract<Erc20>,
+        policy: InflationProtection,
+        attacker: Address,
+        victim: Address,
+    ) {
+        let asset_addr = asset.address();
+        vault.init(attacker, |vault| {
+            vault.vault.erc4626.asset.set(asset_addr);
+            vault.vault._set_inflation_protection(policy);
+        });
+        asset.init(attacker, |asset| {
+            asset._mint(attacker, ATTACKER_ASSETS).expect("should mint");
+            asset._mint(victim, VICTIM_DEPOSIT).expect("should mint");
+        });
+        for account in [attacker, victim] {
+            asset
+                .sender(account)
+                .approve(vault.address(), U256::MAX)
+                .expect("should approve the vault");
+        }
+    }
+
+    /// Front-runs the deposit of `victim` with a deposit of `first_deposit`
+    /// assets followed by a donation, then returns the shares of `victim`.
+    fn donation_attack(
+        vault: &Contract<Erc4626ProtectedTestExample>,
+        asset: &Contract<Erc20>,
+        first_deposit: U256,
+        attacker: Address,
+        victim: Address,
+    ) -> U256 {
+        vault
+            .sender(attacker)
+            .deposit(first_deposit, attacker)
+            .expect("attacker should deposit");
+        asset
+            .sender(attacker)
+            .transfer(vault.address(), DONATION)
+            .expect("attacker should donate");
+
+        vault
+            .sender(victim)
+            .deposit(VICTIM_DEPOSIT, victim)
+            .expect("victim should deposit")
+    }
+
+    #[motsu::test]
+    fn donation_attack_succeeds_without_protection(
+        vault: Contract<Erc4626ProtectedTestExample>,
+        asset: Contract<Erc20>,
+        attacker: Address,
+        victim: Address,
+    ) {
+        setup(&vault, &asset, InflationProtection::default(), attacker, victim);
+
+        let shares =
+            donation_attack(&vault, &asset, uint!(1_U256), attacker, victim);
+
+        assert_eq!(shares, U256::ZERO);
+        assert_eq!(
+            vault.sender(victim).max_withdraw(victim).expect("should read"),
+            U256::ZERO
+        );
+    }
+
+    #[motsu::test]
+    fn decimals_offset_makes_donation_attack_fail(
+        vault: Contract<Erc4626ProtectedTestExample>,
+        asset: Contract<Erc20>,
+        attacker: Address,
+        victim: Address,
+    ) {
+        let policy = InflationProtection {
+            decimals_offset: U8::from(6),
+            ..InflationProtection::default()
+        };
+        setup(&vault, &asset, policy, attacker, victim);
+
+        let shares =
+            donation_attack(&vault, &asset, uint!(1_U256), attacker, victim);
+        assert!(!shares.is_zero());
+
+        // The victim keeps more than 99.9% of the deposit.
+        let assets =
+            vault.sender(victim).max_withdraw(victim).expect("should read");
+        assert!(assets >= VICTIM_DEPOSIT - uint!(10_U256));
+
+        // The attack costs the attacker part of the donation.
+        let attacker_shares = vault.sender(attacker).erc20.balance_of(attacker);
+        vault
+            .sender(attacker)
+            .redeem(attacker_shares, attacker, attacker)
+            .expect("attacker should redeem");
+        assert!(
+            asset.sender(attacker).balance_of(attacker)
+                < ATTACKER_ASSETS - DONATION / uint!(4_U256)
+        );
+    }
+
+    #[motsu::test]
+    fn dead_shares_make_donation_attack_fail(
+        vault: Contract<Erc4626ProtectedTestExample>,
+        asset: Contract<Erc20>,
+        attacker: Address,
+        victim: Address,
+    ) {
+        setup(
+            &vault,
+            &asset,
+            InflationProtection::RECOMMENDED,
+            attacker,
+            victim,
+        );
+
+        // A dust first deposit is rejected.
+        let err = vault
+            .sender(attacker)
+            .deposit(uint!(1_U256), attacker)
+            .expect_err("should reject a dust first deposit");
+        assert!(matches!(
+            err,
+            Error::InsufficientInitialDeposit(
+                ERC4626InsufficientInitialDeposit { assets, min }
+            ) if assets == uint!(1_U256) && min == uint!(1000_U256)
+        ));
+
+        let shares =
+            donation_attack(&vault, &asset, uint!(1000_U256), attacker, victim);
+        assert!(!shares.is_zero());
+        assert_eq!(
+            vault.sender(victim).erc20.balance_of(DEAD_SHARES_HOLDER),
+            uint!(1000_U256)
+        );
+
+        let assets =
+            vault.sender(victim).max_withdraw(victim).expect("should read");
+        assert!(assets >= VICTIM_DEPOSIT - uint!(10_U256));
+    }
+
+    #[motsu::test]
+    fn first_deposit_must_cover_dead_shares(
+        vault: Contract<Erc4626ProtectedTestExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let policy = InflationProtection {
+            dead_shares: uint!(1000_U256),
+            ..InflationProtection::default()
+        };
+        setup(&vault, &asset, policy, alice, bob);
+
+        let err = vault
+            .sender(alice)
+            .deposit(uint!(1000_U256), alice)
+            .expect_err("should not cover the dead shares");
+        assert!(matches!(
+            err,
+            Error::InsufficientInitialShares(
+                ERC4626InsufficientInitialShares { shares, dead_shares }
+            ) if shares == uint!(1000_U256) && dead_shares == uint!(1000_U256)
+        ));
+
+        let shares = vault
+            .sender(alice)
+            .deposit(uint!(1500_U256), alice)
+            .expect("should deposit");
+        assert_eq!(shares, uint!(500_U256));
+        assert_eq!(vault.sender(alice).erc20.total_supply(), uint!(1500_U256));
+
+        // Dead shares are only taken once.
+        let shares = vault
+            .sender(bob)
+            .deposit(uint!(1500_U256), bob)
+            .expect("should deposit");
+        assert_eq!(shares, uint!(1500_U256));
+    }
+
+    #[motsu::test]
+    fn first_mint_pays_for_dead_shares(
+        vault: Contract<Erc4626ProtectedTestExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let policy = InflationProtection {
+            dead_shares: uint!(1000_U256),
+            ..InflationProtection::default()
+        };
+        setup(&vault, &asset, policy, alice, bob);
+
+        let shares = uint!(500_U256);
+        let preview =
+            vault.sender(alice).preview_mint(shares).expect("should preview");
+        assert_eq!(preview, uint!(1500_U256));
+
+        let assets =
+            vault.sender(alice).mint(shares, alice).expect("should mint");
+        assert_eq!(assets, preview);
+        assert_eq!(vault.sender(alice).erc20.balance_of(alice), shares);
+        assert_eq!(
+            vault.sender(alice).erc20.balance_of(DEAD_SHARES_HOLDER),
+            uint!(1000_U256)
+        );
+    }
+
+    #[motsu::test]
+    fn recommended_policy_sets_decimals_offset(
+        vault: Contract<Erc4626ProtectedTestExample>,
+        alice: Address,
+    ) {
+        vault.init(alice, |vault| {
+            vault
+                .vault
+                ._set_inflation_protection(InflationProtection::RECOMMENDED);
+        });
+
+        let vault = vault.sender(alice);
+        assert_eq!(vault.vault._decimals_offset(), U8::from(3));
+        assert_eq!(vault.vault.dead_shares(), uint!(1000_U256));
+        assert_eq!(vault.vault.min_initial_deposit(), uint!(1000_U256));
+    }
+}
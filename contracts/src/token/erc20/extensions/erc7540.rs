@@ -0,0 +1,1441 @@
+//! Asynchronous ERC-4626 vault, as defined in [ERC-7540].
+//!
+//! Deposits and redemptions happen in two steps. A request moves the assets
+//! (or shares) into the vault, where they stay *pending* until the vault
+//! fulfills the request through [`Erc7540::_fulfill_deposit`] or
+//! [`Erc7540::_fulfill_redeem`]. Fulfilled requests become *claimable*, and
+//! are claimed through the standard `deposit`, `mint`, `withdraw` and `redeem`
+//! functions.
+//!
+//! Requests are aggregated per controller, so every request has the id `0`.
+//! Fulfillment is left to the contract using this extension, which must
+//! restrict who can call the fulfillment functions and decide the exchange
+//! rate (e.g. after an off-chain settlement).
+//!
+//! Since the exchange rate of a request is only known once it is fulfilled,
+//! the `preview_*` functions revert, as required by [ERC-7540].
+//!
+//! [ERC-7540]: https://eips.ethereum.org/EIPS/eip-7540
+
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256};
+pub use sol::*;
+use stylus_sdk::{
+    contract, evm, msg,
+    prelude::storage,
+    storage::{StorageBool, StorageMap, StorageU256, TopLevelStorage},
+    stylus_proc::SolidityError,
+};
+
+use crate::{
+    token::erc20::{
+        self,
+        extensions::{
+            erc4626::{
+                self, Deposit, ERC4626ExceededMaxDeposit,
+                ERC4626ExceededMaxMint, ERC4626ExceededMaxRedeem,
+                ERC4626ExceededMaxWithdraw, Withdraw,
+            },
+            Erc4626, IErc4626,
+        },
+        utils::{safe_erc20, ISafeErc20},
+        Erc20, IErc20,
+    },
+    utils::{
+        introspection::erc165::IErc165,
+        math::{
+            alloy::{Math, Rounding},
+            storage::AddAssignChecked,
+        },
+    },
+};
+
+/// Id of the aggregated request of a controller.
+pub const REQUEST_ID: U256 = U256::ZERO;
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when `owner` requests a deposit of `assets`, claimable by
+        /// `controller`.
+        #[allow(missing_docs)]
+        event DepositRequest(
+            address indexed controller,
+            address indexed owner,
+            uint256 indexed request_id,
+            address sender,
+            uint256 assets
+        );
+
+        /// Emitted when `owner` requests the redemption of `shares`,
+        /// claimable by `controller`.
+        #[allow(missing_docs)]
+        event RedeemRequest(
+            address indexed controller,
+            address indexed owner,
+            uint256 indexed request_id,
+            address sender,
+            uint256 shares
+        );
+
+        /// Emitted when `controller` approves or revokes `operator`.
+        #[allow(missing_docs)]
+        event OperatorSet(
+            address indexed controller,
+            address indexed operator,
+            bool approved
+        );
+    }
+
+    sol! {
+        /// Indicates that `caller` is neither `owner` nor one of its
+        /// operators.
+        ///
+        /// * `caller` - Address of the caller.
+        /// * `owner` - Address of the controller or owner.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7540Unauthorized(address caller, address owner);
+
+        /// Indicates an attempt to fulfill more than the pending request of
+        /// `controller`.
+        ///
+        /// * `controller` - Address of the controller of the request.
+        /// * `requested` - Amount requested to be fulfilled.
+        /// * `pending` - Amount pending.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7540ExceededPendingRequest(
+            address controller,
+            uint256 requested,
+            uint256 pending
+        );
+
+        /// Indicates a call to a `preview_*` function of an asynchronous
+        /// flow.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7540PreviewDisabled();
+    }
+}
+
+/// An [`Erc7540`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates that the caller is neither the owner nor one of its
+    /// operators.
+    Unauthorized(ERC7540Unauthorized),
+    /// Indicates an attempt to fulfill more than the pending request of a
+    /// controller.
+    ExceededPendingRequest(ERC7540ExceededPendingRequest),
+    /// Indicates a call to a `preview_*` function of an asynchronous flow.
+    PreviewDisabled(ERC7540PreviewDisabled),
+    /// Error type from [`Erc4626`] contract [`erc4626::Error`].
+    Erc4626(erc4626::Error),
+}
+
+impl From<erc20::Error> for Error {
+    fn from(value: erc20::Error) -> Self {
+        Error::Erc4626(erc4626::Error::Erc20(value))
+    }
+}
+
+impl From<safe_erc20::Error> for Error {
+    fn from(value: safe_erc20::Error) -> Self {
+        Error::Erc4626(erc4626::Error::SafeErc20(value))
+    }
+}
+
+/// State of an [`Erc7540`] Contract.
+#[storage]
+pub struct Erc7540 {
+    /// [`Erc4626`] contract.
+    pub erc4626: Erc4626,
+    /// Assets waiting for a deposit request of a controller to be fulfilled.
+    pub(crate) pending_deposit_assets: StorageMap<Address, StorageU256>,
+    /// Assets of fulfilled deposit requests of a controller.
+    pub(crate) claimable_deposit_assets: StorageMap<Address, StorageU256>,
+    /// Shares owed for the fulfilled deposit requests of a controller.
+    pub(crate) claimable_deposit_shares: StorageMap<Address, StorageU256>,
+    /// Shares waiting for a redeem request of a controller to be fulfilled.
+    pub(crate) pending_redeem_shares: StorageMap<Address, StorageU256>,
+    /// Shares of fulfilled redeem requests of a controller.
+    pub(crate) claimable_redeem_shares: StorageMap<Address, StorageU256>,
+    /// Assets owed for the fulfilled redeem requests of a controller.
+    pub(crate) claimable_redeem_assets: StorageMap<Address, StorageU256>,
+    /// Sum of the assets of all pending deposit requests.
+    pub(crate) total_pending_deposit_assets: StorageU256,
+    /// Sum of the assets owed for all claimable redeem requests.
+    pub(crate) total_claimable_redeem_assets: StorageU256,
+    /// Operators approved by a controller.
+    pub(crate) operators: StorageMap<Address, StorageMap<Address, StorageBool>>,
+}
+
+impl Deref for Erc7540 {
+    type Target = Erc4626;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc4626
+    }
+}
+
+impl DerefMut for Erc7540 {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc4626
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc7540 {}
+
+/// Interface of an asynchronous ERC-4626 vault, as defined in [ERC-7540].
+///
+/// The three argument `deposit` and `mint` claim functions overload the
+/// ERC-4626 ones, so they should be exposed with an explicit selector.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[selector(name = "deposit")]
+/// fn deposit_with_controller(
+///     &mut self,
+///     assets: U256,
+///     receiver: Address,
+///     controller: Address,
+/// ) -> Result<U256, Vec<u8>> {
+///     Ok(self.vault.claim_deposit(assets, receiver, controller, &mut self.erc20)?)
+/// }
+/// ```
+///
+/// [ERC-7540]: https://eips.ethereum.org/EIPS/eip-7540
+pub trait IErc7540 {
+    /// The error type associated to this trait implementation.
+    type Error: Into<Vec<u8>>;
+
+    /// Requests a deposit of `assets` from `owner`, claimable by
+    /// `controller` once fulfilled. Returns the request id.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `assets` - Amount of assets to deposit.
+    /// * `controller` - Account that controls the request.
+    /// * `owner` - Account that owns the assets.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is neither `owner` nor one of
+    ///   its operators.
+    /// * [`erc4626::Error::SafeErc20`] - If the assets cannot be transferred
+    ///   from `owner`.
+    ///
+    /// # Panics
+    ///
+    /// * If the pending assets of `controller` exceed `U256::MAX`.
+    ///
+    /// # Events
+    ///
+    /// * [`DepositRequest`].
+    fn request_deposit(
+        &mut self,
+        assets: U256,
+        controller: Address,
+        owner: Address,
+    ) -> Result<U256, Self::Error>;
+
+    /// Returns the amount of assets of `controller` waiting for their
+    /// deposit request to be fulfilled.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `request_id` - Id of the request, ignored since requests are
+    ///   aggregated.
+    /// * `controller` - Account that controls the request.
+    fn pending_deposit_request(
+        &self,
+        request_id: U256,
+        controller: Address,
+    ) -> U256;
+
+    /// Returns the amount of assets of `controller` whose deposit request
+    /// can be claimed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `request_id` - Id of the request, ignored since requests are
+    ///   aggregated.
+    /// * `controller` - Account that controls the request.
+    fn claimable_deposit_request(
+        &self,
+        request_id: U256,
+        controller: Address,
+    ) -> U256;
+
+    /// Requests the redemption of `shares` from `owner`, claimable by
+    /// `controller` once fulfilled. Returns the request id.
+    ///
+    /// The shares are transferred to the vault until the request is
+    /// fulfilled.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `shares` - Amount of shares to redeem.
+    /// * `controller` - Account that controls the request.
+    /// * `owner` - Account that owns the shares.
+    /// * `erc20` - Write access to the shares [`Erc20`] contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc4626::Error::Erc20`] - If the caller is neither `owner` nor one
+    ///   of its operators and lacks allowance, or `owner` lacks shares.
+    ///
+    /// # Events
+    ///
+    /// * [`RedeemRequest`].
+    fn request_redeem(
+        &mut self,
+        shares: U256,
+        controller: Address,
+        owner: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error>;
+
+    /// Returns the amount of shares of `controller` waiting for their
+    /// redeem request to be fulfilled.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `request_id` - Id of the request, ignored since requests are
+    ///   aggregated.
+    /// * `controller` - Account that controls the request.
+    fn pending_redeem_request(
+        &self,
+        request_id: U256,
+        controller: Address,
+    ) -> U256;
+
+    /// Returns the amount of shares of `controller` whose redeem request
+    /// can be claimed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `request_id` - Id of the request, ignored since requests are
+    ///   aggregated.
+    /// * `controller` - Account that controls the request.
+    fn claimable_redeem_request(
+        &self,
+        request_id: U256,
+        controller: Address,
+    ) -> U256;
+
+    /// Approves or revokes `operator` to manage the requests of the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `operator` - Account to approve or revoke.
+    /// * `approved` - Whether `operator` is approved.
+    ///
+    /// # Events
+    ///
+    /// * [`OperatorSet`].
+    fn set_operator(&mut self, operator: Address, approved: bool) -> bool;
+
+    /// Returns whether `operator` is approved by `controller`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `controller` - Account that approves operators.
+    /// * `operator` - Account to check.
+    fn is_operator(&self, controller: Address, operator: Address) -> bool;
+
+    /// Claims the shares of `assets` from the fulfilled deposit requests of
+    /// `controller`, sending them to `receiver`. Returns the amount of
+    /// shares.
+    ///
+    /// Should be exposed as `deposit(uint256,address,address)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `assets` - Amount of claimable assets.
+    /// * `receiver` - Account receiving the shares.
+    /// * `controller` - Account that controls the requests.
+    /// * `erc20` - Write access to the shares [`Erc20`] contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is neither `controller` nor
+    ///   one of its operators.
+    /// * [`erc4626::Error::ExceededMaxDeposit`] - If `assets` exceeds the
+    ///   claimable assets of `controller`.
+    ///
+    /// # Events
+    ///
+    /// * [`Deposit`].
+    fn claim_deposit(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        controller: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error>;
+
+    /// Claims `shares` from the fulfilled deposit requests of `controller`,
+    /// sending them to `receiver`. Returns the amount of assets.
+    ///
+    /// Should be exposed as `mint(uint256,address,address)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `shares` - Amount of claimable shares.
+    /// * `receiver` - Account receiving the shares.
+    /// * `controller` - Account that controls the requests.
+    /// * `erc20` - Write access to the shares [`Erc20`] contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is neither `controller` nor
+    ///   one of its operators.
+    /// * [`erc4626::Error::ExceededMaxMint`] - If `shares` exceeds the
+    ///   claimable shares of `controller`.
+    ///
+    /// # Events
+    ///
+    /// * [`Deposit`].
+    fn claim_mint(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        controller: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error>;
+}
+
+impl IErc7540 for Erc7540 {
+    type Error = Error;
+
+    fn request_deposit(
+        &mut self,
+        assets: U256,
+        controller: Address,
+        owner: Address,
+    ) -> Result<U256, Self::Error> {
+        let sender = msg::sender();
+        self._check_operator(owner, sender)?;
+
+        let asset = self.asset();
+        self.erc4626.safe_erc20.safe_transfer_from(
+            asset,
+            owner,
+            contract::address(),
+            assets,
+        )?;
+
+        let pending = self.pending_deposit_assets.get(controller);
+        self.pending_deposit_assets.setter(controller).set(
+            pending
+                .checked_add(assets)
+                .expect("pending assets should not exceed `U256::MAX`"),
+        );
+        self.total_pending_deposit_assets.add_assign_checked(
+            assets,
+            "total pending assets should not exceed `U256::MAX`",
+        );
+
+        evm::log(DepositRequest {
+            controller,
+            owner,
+            request_id: REQUEST_ID,
+            sender,
+            assets,
+        });
+
+        Ok(REQUEST_ID)
+    }
+
+    fn pending_deposit_request(
+        &self,
+        _request_id: U256,
+        controller: Address,
+    ) -> U256 {
+        self.pending_deposit_assets.get(controller)
+    }
+
+    fn claimable_deposit_request(
+        &self,
+        _request_id: U256,
+        controller: Address,
+    ) -> U256 {
+        self.claimable_deposit_assets.get(controller)
+    }
+
+    fn request_redeem(
+        &mut self,
+        shares: U256,
+        controller: Address,
+        owner: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        let sender = msg::sender();
+        if sender != owner && !self.is_operator(owner, sender) {
+            erc20._spend_allowance(owner, sender, shares)?;
+        }
+
+        erc20._transfer(owner, contract::address(), shares)?;
+
+        self.pending_redeem_shares.setter(controller).add_assign_checked(
+            shares,
+            "pending shares should not exceed `U256::MAX`",
+        );
+
+        evm::log(RedeemRequest {
+            controller,
+            owner,
+            request_id: REQUEST_ID,
+            sender,
+            shares,
+        });
+
+        Ok(REQUEST_ID)
+    }
+
+    fn pending_redeem_request(
+        &self,
+        _request_id: U256,
+        controller: Address,
+    ) -> U256 {
+        self.pending_redeem_shares.get(controller)
+    }
+
+    fn claimable_redeem_request(
+        &self,
+        _request_id: U256,
+        controller: Address,
+    ) -> U256 {
+        self.claimable_redeem_shares.get(controller)
+    }
+
+    fn set_operator(&mut self, operator: Address, approved: bool) -> bool {
+        let controller = msg::sender();
+        self.operators.setter(controller).setter(operator).set(approved);
+        evm::log(OperatorSet { controller, operator, approved });
+        true
+    }
+
+    fn is_operator(&self, controller: Address, operator: Address) -> bool {
+        self.operators.getter(controller).get(operator)
+    }
+
+    fn claim_deposit(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        controller: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        self._check_operator(controller, msg::sender())?;
+
+        let max_assets = self.max_deposit(controller);
+        if assets > max_assets {
+            return Err(erc4626::Error::ExceededMaxDeposit(
+                ERC4626ExceededMaxDeposit { receiver, assets, max: max_assets },
+            )
+            .into());
+        }
+
+        let claimable_shares = self.claimable_deposit_shares.get(controller);
+        let shares = Self::_pro_rata(
+            assets,
+            claimable_shares,
+            max_assets,
+            Rounding::Floor,
+        );
+        self._claim_deposit(controller, receiver, assets, shares, erc20)?;
+
+        Ok(shares)
+    }
+
+    fn claim_mint(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        controller: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        self._check_operator(controller, msg::sender())?;
+
+        let max_shares = self.max_mint(controller);
+        if shares > max_shares {
+            return Err(erc4626::Error::ExceededMaxMint(
+                ERC4626ExceededMaxMint { receiver, shares, max: max_shares },
+            )
+            .into());
+        }
+
+        let claimable_assets = self.claimable_deposit_assets.get(controller);
+        let assets = Self::_pro_rata(
+            shares,
+            claimable_assets,
+            max_shares,
+            Rounding::Ceil,
+        );
+        self._claim_deposit(controller, receiver, assets, shares, erc20)?;
+
+        Ok(assets)
+    }
+}
+
+impl IErc4626 for Erc7540 {
+    type Error = Error;
+
+    fn asset(&self) -> Address {
+        self.erc4626.asset()
+    }
+
+    /// Returns the assets backing the shares, which excludes the assets of
+    /// pending deposit requests and the assets owed to claimable redeem
+    /// requests.
+    fn total_assets(&mut self) -> Result<U256, Self::Error> {
+        let balance = self.erc4626.total_assets()?;
+        Ok(balance
+            .saturating_sub(self.total_pending_deposit_assets.get())
+            .saturating_sub(self.total_claimable_redeem_assets.get()))
+    }
+
+    fn convert_to_shares(
+        &mut self,
+        assets: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        let total_assets = self.total_assets()?;
+        Ok(self.erc4626._convert_to_shares_with(
+            assets,
+            total_assets,
+            erc20.total_supply(),
+            Rounding::Floor,
+        ))
+    }
+
+    fn convert_to_assets(
+        &mut self,
+        shares: U256,
+        erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        let total_assets = self.total_assets()?;
+        Ok(self.erc4626._convert_to_assets_with(
+            shares,
+            total_assets,
+            erc20.total_supply(),
+            Rounding::Floor,
+        ))
+    }
+
+    /// Returns the claimable assets of the deposit requests of `receiver`,
+    /// which acts as the controller.
+    fn max_deposit(&self, receiver: Address) -> U256 {
+        self.claimable_deposit_assets.get(receiver)
+    }
+
+    /// Returns the claimable shares of the deposit requests of `receiver`,
+    /// which acts as the controller.
+    fn max_mint(&self, receiver: Address) -> U256 {
+        self.claimable_deposit_shares.get(receiver)
+    }
+
+    /// Returns the claimable assets of the redeem requests of `owner`, which
+    /// acts as the controller.
+    fn max_withdraw(
+        &mut self,
+        owner: Address,
+        _erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.claimable_redeem_assets.get(owner))
+    }
+
+    /// Returns the claimable shares of the redeem requests of `owner`, which
+    /// acts as the controller.
+    fn max_redeem(&self, owner: Address, _erc20: &Erc20) -> U256 {
+        self.claimable_redeem_shares.get(owner)
+    }
+
+    fn preview_deposit(
+        &mut self,
+        _assets: U256,
+        _erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Err(Error::PreviewDisabled(ERC7540PreviewDisabled {}))
+    }
+
+    fn preview_mint(
+        &mut self,
+        _shares: U256,
+        _erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Err(Error::PreviewDisabled(ERC7540PreviewDisabled {}))
+    }
+
+    fn preview_withdraw(
+        &mut self,
+        _assets: U256,
+        _erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Err(Error::PreviewDisabled(ERC7540PreviewDisabled {}))
+    }
+
+    fn preview_redeem(
+        &mut self,
+        _shares: U256,
+        _erc20: &Erc20,
+    ) -> Result<U256, Self::Error> {
+        Err(Error::PreviewDisabled(ERC7540PreviewDisabled {}))
+    }
+
+    /// Claims a deposit, using the caller as the controller.
+    fn deposit(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        self.claim_deposit(assets, receiver, msg::sender(), erc20)
+    }
+
+    /// Claims a mint, using the caller as the controller.
+    fn mint(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        self.claim_mint(shares, receiver, msg::sender(), erc20)
+    }
+
+    /// Claims `assets` from the fulfilled redeem requests of `owner`, which
+    /// acts as the controller.
+    fn withdraw(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        owner: Address,
+        _erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        self._check_operator(owner, msg::sender())?;
+
+        let max_assets = self.claimable_redeem_assets.get(owner);
+        if assets > max_assets {
+            return Err(erc4626::Error::ExceededMaxWithdraw(
+                ERC4626ExceededMaxWithdraw { owner, assets, max: max_assets },
+            )
+            .into());
+        }
+
+        let claimable_shares = self.claimable_redeem_shares.get(owner);
+        let shares = Self::_pro_rata(
+            assets,
+            claimable_shares,
+            max_assets,
+            Rounding::Ceil,
+        );
+        self._claim_redeem(owner, receiver, assets, shares)?;
+
+        Ok(shares)
+    }
+
+    /// Claims `shares` from the fulfilled redeem requests of `owner`, which
+    /// acts as the controller.
+    fn redeem(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        owner: Address,
+        _erc20: &mut Erc20,
+    ) -> Result<U256, Self::Error> {
+        self._check_operator(owner, msg::sender())?;
+
+        let max_shares = self.claimable_redeem_shares.get(owner);
+        if shares > max_shares {
+            return Err(erc4626::Error::ExceededMaxRedeem(
+                ERC4626ExceededMaxRedeem { owner, shares, max: max_shares },
+            )
+            .into());
+        }
+
+        let claimable_assets = self.claimable_redeem_assets.get(owner);
+        let assets = Self::_pro_rata(
+            shares,
+            claimable_assets,
+            max_shares,
+            Rounding::Floor,
+        );
+        self._claim_redeem(owner, receiver, assets, shares)?;
+
+        Ok(assets)
+    }
+}
+
+impl Erc7540 {
+    /// Fulfills `assets` of the pending deposit requests of `controller`,
+    /// which can then claim `shares`.
+    ///
+    /// The shares are minted to the vault, which holds them until they are
+    /// claimed.
+    ///
+    /// NOTE: This function does not check who calls it. The contract using
+    /// this extension must restrict it, e.g. to the settlement operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `controller` - Account that controls the requests.
+    /// * `assets` - Amount of pending assets to fulfill.
+    /// * `shares` - Amount of shares issued for `assets`.
+    /// * `erc20` - Write access to the shares [`Erc20`] contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ExceededPendingRequest`] - If `assets` exceeds the pending
+    ///   assets of `controller`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`].
+    pub fn _fulfill_deposit(
+        &mut self,
+        controller: Address,
+        assets: U256,
+        shares: U256,
+        erc20: &mut Erc20,
+    ) -> Result<(), Error> {
+        let pending = self.pending_deposit_assets.get(controller);
+        if assets > pending {
+            return Err(Error::ExceededPendingRequest(
+                ERC7540ExceededPendingRequest {
+                    controller,
+                    requested: assets,
+                    pending,
+                },
+            ));
+        }
+
+        self.pending_deposit_assets.setter(controller).set(pending - assets);
+        let total = self.total_pending_deposit_assets.get();
+        self.total_pending_deposit_assets.set(total - assets);
+
+        let claimable_assets = self.claimable_deposit_assets.get(controller);
+        self.claimable_deposit_assets
+            .setter(controller)
+            .set(claimable_assets + assets);
+        let claimable_shares = self.claimable_deposit_shares.get(controller);
+        self.claimable_deposit_shares
+            .setter(controller)
+            .set(claimable_shares + shares);
+
+        erc20._mint(contract::address(), shares)?;
+
+        Ok(())
+    }
+
+    /// Fulfills `shares` of the pending redeem requests of `controller`,
+    /// which can then claim `assets`.
+    ///
+    /// The shares are burned, and `assets` are set aside from
+    /// [`IErc4626::total_assets`] until they are claimed.
+    ///
+    /// NOTE: This function does not check who calls it. The contract using
+    /// this extension must restrict it, e.g. to the settlement operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `controller` - Account that controls the requests.
+    /// * `shares` - Amount of pending shares to fulfill.
+    /// * `assets` - Amount of assets paid for `shares`.
+    /// * `erc20` - Write access to the shares [`Erc20`] contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ExceededPendingRequest`] - If `shares` exceeds the pending
+    ///   shares of `controller`.
+    ///
+    /// # Panics
+    ///
+    /// * If the total claimable assets exceed `U256::MAX`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`].
+    pub fn _fulfill_redeem(
+        &mut self,
+        controller: Address,
+        shares: U256,
+        assets: U256,
+        erc20: &mut Erc20,
+    ) -> Result<(), Error> {
+        let pending = self.pending_redeem_shares.get(controller);
+        if shares > pending {
+            return Err(Error::ExceededPendingRequest(
+                ERC7540ExceededPendingRequest {
+                    controller,
+                    requested: shares,
+                    pending,
+                },
+            ));
+        }
+
+        self.pending_redeem_shares.setter(controller).set(pending - shares);
+
+        let claimable_shares = self.claimable_redeem_shares.get(controller);
+        self.claimable_redeem_shares
+            .setter(controller)
+            .set(claimable_shares + shares);
+        let claimable_assets = self.claimable_redeem_assets.get(controller);
+        self.claimable_redeem_assets
+            .setter(controller)
+            .set(claimable_assets + assets);
+        let total = self.total_claimable_redeem_assets.get();
+        self.total_claimable_redeem_assets.set(
+            total
+                .checked_add(assets)
+                .expect("claimable assets should not exceed `U256::MAX`"),
+        );
+
+        erc20._burn(contract::address(), shares)?;
+
+        Ok(())
+    }
+
+    /// Moves `assets` and `shares` out of the claimable deposit of
+    /// `controller` and sends the shares to `receiver`.
+    fn _claim_deposit(
+        &mut self,
+        controller: Address,
+        receiver: Address,
+        assets: U256,
+        shares: U256,
+        erc20: &mut Erc20,
+    ) -> Result<(), Error> {
+        let claimable_assets = self.claimable_deposit_assets.get(controller);
+        self.claimable_deposit_assets
+            .setter(controller)
+            .set(claimable_assets - assets);
+        let claimable_shares = self.claimable_deposit_shares.get(controller);
+        self.claimable_deposit_shares
+            .setter(controller)
+            .set(claimable_shares - shares);
+
+        erc20._transfer(contract::address(), receiver, shares)?;
+
+        evm::log(Deposit {
+            sender: controller,
+            owner: receiver,
+            assets,
+            shares,
+        });
+
+        Ok(())
+    }
+
+    /// Moves `assets` and `shares` out of the claimable redemption of
+    /// `controller` and sends the assets to `receiver`.
+    fn _claim_redeem(
+        &mut self,
+        controller: Address,
+        receiver: Address,
+        assets: U256,
+        shares: U256,
+    ) -> Result<(), Error> {
+        let claimable_assets = self.claimable_redeem_assets.get(controller);
+        self.claimable_redeem_assets
+            .setter(controller)
+            .set(claimable_assets - assets);
+        let claimable_shares = self.claimable_redeem_shares.get(controller);
+        self.claimable_redeem_shares
+            .setter(controller)
+            .set(claimable_shares - shares);
+        let total = self.total_claimable_redeem_assets.get();
+        self.total_claimable_redeem_assets.set(total - assets);
+
+        let asset = self.asset();
+        self.erc4626.safe_erc20.safe_transfer(asset, receiver, assets)?;
+
+        evm::log(Withdraw {
+            sender: msg::sender(),
+            receiver,
+            owner: controller,
+            assets,
+            shares,
+        });
+
+        Ok(())
+    }
+
+    /// Checks that `caller` is `owner` or one of its operators.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - Otherwise.
+    fn _check_operator(
+        &self,
+        owner: Address,
+        caller: Address,
+    ) -> Result<(), Error> {
+        if caller == owner || self.is_operator(owner, caller) {
+            return Ok(());
+        }
+        Err(Error::Unauthorized(ERC7540Unauthorized { caller, owner }))
+    }
+
+    /// Returns the share of `total` matching `amount` out of `claimable`.
+    fn _pro_rata(
+        amount: U256,
+        total: U256,
+        claimable: U256,
+        rounding: Rounding,
+    ) -> U256 {
+        if claimable.is_zero() {
+            return U256::ZERO;
+        }
+        amount.mul_div(total, claimable, rounding)
+    }
+}
+
+impl IErc165 for Erc7540 {
+    /// Supports the ERC-7540 operator, asynchronous deposit and asynchronous
+    /// redeem interfaces, as well as the ERC-7575 vault interface required by
+    /// [ERC-7540].
+    ///
+    /// [ERC-7540]: https://eips.ethereum.org/EIPS/eip-7540
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        const OPERATOR: [u8; 4] = [0xe3, 0xbc, 0x4e, 0x65];
+        const ASYNC_DEPOSIT: [u8; 4] = [0xce, 0x3b, 0xbe, 0x50];
+        const ASYNC_REDEEM: [u8; 4] = [0x62, 0x0e, 0xe8, 0xe4];
+        const ERC7575: [u8; 4] = [0x2f, 0x0a, 0x18, 0xc5];
+
+        [OPERATOR, ASYNC_DEPOSIT, ASYNC_REDEEM, ERC7575]
+            .contains(&interface_id.0)
+            || Erc20::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, FixedBytes, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::*;
+
+    use super::{
+        ERC7540ExceededPendingRequest, ERC7540Unauthorized, Erc7540, Error,
+        IErc7540,
+    };
+    use crate::{
+        token::erc20::{
+            extensions::{erc4626, IErc4626},
+            Erc20, IErc20,
+        },
+        utils::introspection::erc165::IErc165,
+    };
+
+    #[storage]
+    struct Erc7540TestExample {
+        vault: Erc7540,
+        erc20: Erc20,
+    }
+
+    #[public]
+    impl Erc7540TestExample {
+        fn request_deposit(
+            &mut self,
+            assets: U256,
+            controller: Address,
+            owner: Address,
+        ) -> Result<U256, Error> {
+            self.vault.request_deposit(assets, controller, owner)
+        }
+
+        fn request_redeem(
+            &mut self,
+            shares: U256,
+            controller: Address,
+            owner: Address,
+        ) -> Result<U256, Error> {
+            self.vault.request_redeem(
+                shares,
+                controller,
+                owner,
+                &mut self.erc20,
+            )
+        }
+
+        fn set_operator(&mut self, operator: Address, approved: bool) -> bool {
+            self.vault.set_operator(operator, approved)
+        }
+
+        fn deposit(
+            &mut self,
+            assets: U256,
+            receiver: Address,
+        ) -> Result<U256, Error> {
+            self.vault.deposit(assets, receiver, &mut self.erc20)
+        }
+
+        #[selector(name = "deposit")]
+        fn deposit_with_controller(
+            &mut self,
+            assets: U256,
+            receiver: Address,
+            controller: Address,
+        ) -> Result<U256, Error> {
+            self.vault.claim_deposit(
+                assets,
+                receiver,
+                controller,
+                &mut self.erc20,
+            )
+        }
+
+        fn mint(
+            &mut self,
+            shares: U256,
+            receiver: Address,
+        ) -> Result<U256, Error> {
+            self.vault.mint(shares, receiver, &mut self.erc20)
+        }
+
+        fn withdraw(
+            &mut self,
+            assets: U256,
+            receiver: Address,
+            owner: Address,
+        ) -> Result<U256, Error> {
+            self.vault.withdraw(assets, receiver, owner, &mut self.erc20)
+        }
+
+        fn redeem(
+            &mut self,
+            shares: U256,
+            receiver: Address,
+            owner: Address,
+        ) -> Result<U256, Error> {
+            self.vault.redeem(shares, receiver, owner, &mut self.erc20)
+        }
+
+        fn total_assets(&mut self) -> Result<U256, Error> {
+            self.vault.total_assets()
+        }
+
+        fn preview_deposit(&mut self, assets: U256) -> Result<U256, Error> {
+            self.vault.preview_deposit(assets, &self.erc20)
+        }
+    }
+
+    unsafe impl TopLevelStorage for Erc7540TestExample {}
+
+    const ALICE_ASSETS: U256 = uint!(10_000_U256);
+
+    fn setup(
+        vault: &Contract<Erc7540TestExample>,
+        asset: &Contract<Erc20>,
+        alice: Address,
+    ) {
+        let asset_addr = asset.address();
+        vault.init(alice, |vault| vault.vault.erc4626.asset.set(asset_addr));
+        asset.init(alice, |asset| {
+            asset._mint(alice, ALICE_ASSETS).expect("should mint assets");
+        });
+        asset
+            .sender(alice)
+            .approve(vault.address(), U256::MAX)
+            .expect("should approve the vault");
+    }
+
+    fn fulfill_deposit(
+        vault: &Contract<Erc7540TestExample>,
+        controller: Address,
+        assets: U256,
+        shares: U256,
+    ) {
+        vault.init(controller, |vault| {
+            vault
+                .vault
+                ._fulfill_deposit(controller, assets, shares, &mut vault.erc20)
+                .expect("should fulfill the deposit");
+        });
+    }
+
+    #[motsu::test]
+    fn deposit_request_is_pending_until_fulfilled(
+        vault: Contract<Erc7540TestExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+    ) {
+        setup(&vault, &asset, alice);
+        let assets = uint!(1000_U256);
+
+        let request_id = vault
+            .sender(alice)
+            .request_deposit(assets, alice, alice)
+            .expect("should request a deposit");
+        assert_eq!(request_id, U256::ZERO);
+
+        assert_eq!(
+            vault
+                .sender(alice)
+                .vault
+                .pending_deposit_request(request_id, alice),
+            assets
+        );
+        assert_eq!(
+            vault
+                .sender(alice)
+                .vault
+                .claimable_deposit_request(request_id, alice),
+            U256::ZERO
+        );
+        assert_eq!(asset.sender(alice).balance_of(vault.address()), assets);
+        // Pending assets do not back any share.
+        assert_eq!(
+            vault.sender(alice).total_assets().expect("should read assets"),
+            U256::ZERO
+        );
+
+        let err = vault
+            .sender(alice)
+            .deposit(assets, alice)
+            .expect_err("should not claim a pending request");
+        assert!(matches!(
+            err,
+            Error::Erc4626(erc4626::Error::ExceededMaxDeposit(_))
+        ));
+
+        fulfill_deposit(&vault, alice, assets, uint!(900_U256));
+
+        assert_eq!(
+            vault
+                .sender(alice)
+                .vault
+                .pending_deposit_request(request_id, alice),
+            U256::ZERO
+        );
+        assert_eq!(
+            vault
+                .sender(alice)
+                .vault
+                .claimable_deposit_request(request_id, alice),
+            assets
+        );
+        assert_eq!(
+            vault.sender(alice).total_assets().expect("should read assets"),
+            assets
+        );
+
+        let shares = vault
+            .sender(alice)
+            .deposit(uint!(500_U256), alice)
+            .expect("should claim part of the deposit");
+        assert_eq!(shares, uint!(450_U256));
+
+        let assets_claimed = vault
+            .sender(alice)
+            .mint(uint!(450_U256), alice)
+            .expect("should claim the rest of the deposit");
+        assert_eq!(assets_claimed, uint!(500_U256));
+
+        assert_eq!(
+            vault.sender(alice).erc20.balance_of(alice),
+            uint!(900_U256)
+        );
+        assert_eq!(vault.sender(alice).vault.max_deposit(alice), U256::ZERO);
+        assert_eq!(vault.sender(alice).vault.max_mint(alice), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn redeem_request_is_pending_until_fulfilled(
+        vault: Contract<Erc7540TestExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup(&vault, &asset, alice);
+        let assets = uint!(1000_U256);
+        vault
+            .sender(alice)
+            .request_deposit(assets, alice, alice)
+            .expect("should request a deposit");
+        fulfill_deposit(&vault, alice, assets, assets);
+        vault.sender(alice).deposit(assets, alice).expect("should claim");
+
+        let shares = uint!(400_U256);
+        vault
+            .sender(alice)
+            .request_redeem(shares, alice, alice)
+            .expect("should request a redemption");
+        assert_eq!(
+            vault.sender(alice).vault.pending_redeem_request(U256::ZERO, alice),
+            shares
+        );
+        assert_eq!(
+            vault.sender(alice).erc20.balance_of(alice),
+            assets - shares
+        );
+
+        vault.init(alice, |vault| {
+            vault
+                .vault
+                ._fulfill_redeem(
+                    alice,
+                    shares,
+                    uint!(420_U256),
+                    &mut vault.erc20,
+                )
+                .expect("should fulfill the redemption");
+        });
+        assert_eq!(
+            vault
+                .sender(alice)
+                .vault
+                .claimable_redeem_request(U256::ZERO, alice),
+            shares
+        );
+        // Assets owed to the redemption no longer back the shares.
+        assert_eq!(
+            vault.sender(alice).total_assets().expect("should read assets"),
+            uint!(580_U256)
+        );
+
+        let redeemed = vault
+            .sender(alice)
+            .withdraw(uint!(210_U256), bob, alice)
+            .expect("should claim part of the redemption");
+        assert_eq!(redeemed, uint!(200_U256));
+
+        let assets_claimed = vault
+            .sender(alice)
+            .redeem(uint!(200_U256), bob, alice)
+            .expect("should claim the rest of the redemption");
+        assert_eq!(assets_claimed, uint!(210_U256));
+
+        assert_eq!(asset.sender(alice).balance_of(bob), uint!(420_U256));
+        assert_eq!(vault.sender(alice).erc20.total_supply(), uint!(600_U256));
+    }
+
+    #[motsu::test]
+    fn operator_can_act_for_controller(
+        vault: Contract<Erc7540TestExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup(&vault, &asset, alice);
+        let assets = uint!(1000_U256);
+
+        let err = vault
+            .sender(bob)
+            .request_deposit(assets, bob, alice)
+            .expect_err("should not request for alice");
+        assert!(matches!(
+            err,
+            Error::Unauthorized(ERC7540Unauthorized { caller, owner })
+                if caller == bob && owner == alice
+        ));
+
+        assert!(vault.sender(alice).set_operator(bob, true));
+        assert!(vault.sender(alice).vault.is_operator(alice, bob));
+
+        vault
+            .sender(bob)
+            .request_deposit(assets, alice, alice)
+            .expect("operator should request for alice");
+        fulfill_deposit(&vault, alice, assets, assets);
+
+        let shares = vault
+            .sender(bob)
+            .deposit_with_controller(assets, bob, alice)
+            .expect("operator should claim for alice");
+        assert_eq!(vault.sender(alice).erc20.balance_of(bob), shares);
+
+        vault.sender(alice).set_operator(bob, false);
+        let err = vault
+            .sender(bob)
+            .redeem(U256::ZERO, bob, alice)
+            .expect_err("revoked operator should not claim");
+        assert!(matches!(err, Error::Unauthorized(_)));
+    }
+
+    #[motsu::test]
+    fn fulfill_reverts_when_exceeding_pending(
+        vault: Contract<Erc7540TestExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+    ) {
+        setup(&vault, &asset, alice);
+        let assets = uint!(1000_U256);
+        vault
+            .sender(alice)
+            .request_deposit(assets, alice, alice)
+            .expect("should request a deposit");
+
+        let mut example = vault.sender(alice);
+        let example = &mut *example;
+        let err = example
+            .vault
+            ._fulfill_deposit(
+                alice,
+                assets + uint!(1_U256),
+                assets,
+                &mut example.erc20,
+            )
+            .expect_err("should not fulfill more than pending");
+        assert!(matches!(
+            err,
+            Error::ExceededPendingRequest(ERC7540ExceededPendingRequest {
+                controller,
+                requested,
+                pending,
+            }) if controller == alice
+                && requested == assets + uint!(1_U256)
+                && pending == assets
+        ));
+    }
+
+    #[motsu::test]
+    fn preview_reverts(vault: Contract<Erc7540TestExample>, alice: Address) {
+        let err = vault
+            .sender(alice)
+            .preview_deposit(uint!(1_U256))
+            .expect_err("should not preview asynchronous deposits");
+        assert!(matches!(err, Error::PreviewDisabled(_)));
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc7540::supports_interface(FixedBytes([
+            0xe3, 0xbc, 0x4e, 0x65
+        ])));
+        assert!(Erc7540::supports_interface(FixedBytes([
+            0xce, 0x3b, 0xbe, 0x50
+        ])));
+        assert!(Erc7540::supports_interface(FixedBytes([
+            0x62, 0x0e, 0xe8, 0xe4
+        ])));
+        assert!(Erc7540::supports_interface(FixedBytes([
+            0x2f, 0x0a, 0x18, 0xc5
+        ])));
+        assert!(!Erc7540::supports_interface(FixedBytes([
+            0xff, 0xff, 0xff, 0xff
+        ])));
+    }
+}
@@ -0,0 +1,1203 @@
+//! Multi-asset vaults with an external share token, as defined in [ERC-7575].
+//!
+//! [`Erc4626`] assumes that the vault is its own share token. [ERC-7575]
+//! separates both roles: an [`Erc7575Vault`] is a single-asset entry point
+//! that mints and burns shares of an [`Erc7575Share`] token living in another
+//! contract, and several entry points (one per asset) can share the same
+//! share token. The share token lists its entry points through
+//! [`Erc7575Share::vault`].
+//!
+//! Since the shares of every entry point are fungible, their price is computed
+//! from pooled accounting kept by the share token: the total supply of shares
+//! and [`Erc7575Share::total_assets`], the assets deposited through all entry
+//! points. Assets of the different entry points are therefore counted one to
+//! one, which suits assets of equal value and decimals such as stablecoins.
+//! Gains and losses of the pool are reported through
+//! [`Erc7575Share::_set_total_assets`].
+//!
+//! The entry points reuse the conversion, deposit and withdrawal workflows of
+//! [`Erc4626`], minting and burning shares through the [`IErc4626Shares`]
+//! implementation of the external share token.
+//!
+//! [ERC-7575]: https://eips.ethereum.org/EIPS/eip-7575
+
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256};
+pub use sol::*;
+use stylus_sdk::{
+    call::{self, Call},
+    contract, evm, msg,
+    prelude::storage,
+    storage::{
+        StorageAddress, StorageBool, StorageMap, StorageU256, TopLevelStorage,
+    },
+    stylus_proc::SolidityError,
+};
+
+use self::share::IErc7575Share;
+use crate::{
+    token::erc20::{
+        self,
+        extensions::{
+            erc4626::{
+                self, ERC4626ExceededMaxDeposit, ERC4626ExceededMaxMint,
+                ERC4626ExceededMaxRedeem, ERC4626ExceededMaxWithdraw,
+                InvalidAsset,
+            },
+            Erc4626, IErc4626, IErc4626Shares,
+        },
+        utils::{safe_erc20, IErc20 as IErc20Solidity},
+        Erc20,
+    },
+    utils::{
+        introspection::erc165::{Erc165, IErc165},
+        math::alloy::Rounding,
+    },
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the entry point of `asset` is set to `vault`.
+        #[allow(missing_docs)]
+        event VaultUpdate(address indexed asset, address vault);
+    }
+
+    sol! {
+        /// Indicates that `caller` is not an entry point of the share token.
+        ///
+        /// * `caller` - Address of the caller.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7575UnauthorizedVault(address caller);
+
+        /// Indicates a failed call to the share token.
+        ///
+        /// * `share` - Address of the share token.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7575InvalidShare(address share);
+    }
+}
+
+mod share {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::stylus_proc::sol_interface;
+
+    sol_interface! {
+        /// Solidity Interface of an [`super::Erc7575Share`] token, as used by
+        /// its entry points.
+        interface IErc7575Share {
+            function totalSupply() external view returns (uint256);
+            function balanceOf(address account) external view returns (uint256);
+            function totalAssets() external view returns (uint256);
+            function vaultMint(address to, uint256 shares, uint256 assets) external;
+            function vaultBurn(
+                address owner,
+                address spender,
+                uint256 shares,
+                uint256 assets
+            ) external;
+        }
+    }
+}
+
+/// An [`Erc7575Vault`] or [`Erc7575Share`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates that the caller is not an entry point of the share token.
+    UnauthorizedVault(ERC7575UnauthorizedVault),
+    /// Indicates a failed call to the share token.
+    InvalidShare(ERC7575InvalidShare),
+    /// Indicates a failed call to the share token, with the reason specified
+    /// by it.
+    ///
+    /// Since encoding [`stylus_sdk::call::Error`] returns the underlying
+    /// return data, the error of the share token (e.g.
+    /// [`erc20::ERC20InsufficientAllowance`]) is forwarded as is.
+    InvalidShareWithReason(call::Error),
+    /// Error type from [`Erc4626`] contract [`erc4626::Error`].
+    Erc4626(erc4626::Error),
+}
+
+impl From<erc20::Error> for Error {
+    fn from(value: erc20::Error) -> Self {
+        Error::Erc4626(erc4626::Error::Erc20(value))
+    }
+}
+
+impl From<safe_erc20::Error> for Error {
+    fn from(value: safe_erc20::Error) -> Self {
+        Error::Erc4626(erc4626::Error::SafeErc20(value))
+    }
+}
+
+/// State of an [`Erc7575Share`] token.
+///
+/// Lives next to the [`Erc20`] of the share token, which is passed to the
+/// functions updating balances.
+#[storage]
+pub struct Erc7575Share {
+    /// Entry point of an asset.
+    pub(crate) vaults: StorageMap<Address, StorageAddress>,
+    /// Whether an address is a current entry point.
+    pub(crate) is_vault: StorageMap<Address, StorageBool>,
+    /// Assets deposited through all entry points.
+    pub(crate) total_assets: StorageU256,
+}
+
+impl Erc7575Share {
+    /// Returns the entry point of `asset`, or [`Address::ZERO`] if there is
+    /// none.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `asset` - Address of the asset.
+    #[must_use]
+    pub fn vault(&self, asset: Address) -> Address {
+        self.vaults.get(asset)
+    }
+
+    /// Returns the assets deposited through all entry points, including
+    /// reported gains and losses.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn total_assets(&self) -> U256 {
+        self.total_assets.get()
+    }
+
+    /// Mints `shares` to `to` for `assets` deposited through the calling
+    /// entry point.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account receiving the shares.
+    /// * `shares` - Amount of shares to mint.
+    /// * `assets` - Amount of assets deposited.
+    /// * `erc20` - Write access to the share [`Erc20`] contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedVault`] - If the caller is not an entry point.
+    /// * [`erc20::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    ///
+    /// # Panics
+    ///
+    /// * If the total assets exceed `U256::MAX`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`].
+    pub fn vault_mint(
+        &mut self,
+        to: Address,
+        shares: U256,
+        assets: U256,
+        erc20: &mut Erc20,
+    ) -> Result<(), Error> {
+        self._check_vault(msg::sender())?;
+
+        erc20._mint(to, shares)?;
+
+        let total = self.total_assets.get();
+        self.total_assets.set(
+            total
+                .checked_add(assets)
+                .expect("total assets should not exceed `U256::MAX`"),
+        );
+
+        Ok(())
+    }
+
+    /// Burns `shares` of `owner` for `assets` withdrawn through the calling
+    /// entry point, spending the allowance of `spender` if it is not `owner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - Account owning the shares.
+    /// * `spender` - Account that requested the withdrawal.
+    /// * `shares` - Amount of shares to burn.
+    /// * `assets` - Amount of assets withdrawn.
+    /// * `erc20` - Write access to the share [`Erc20`] contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedVault`] - If the caller is not an entry point.
+    /// * [`erc20::Error::InsufficientAllowance`] - If `spender` lacks
+    ///   allowance.
+    /// * [`erc20::Error::InsufficientBalance`] - If `owner` lacks shares.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`].
+    pub fn vault_burn(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        shares: U256,
+        assets: U256,
+        erc20: &mut Erc20,
+    ) -> Result<(), Error> {
+        self._check_vault(msg::sender())?;
+
+        if spender != owner {
+            erc20._spend_allowance(owner, spender, shares)?;
+        }
+        erc20._burn(owner, shares)?;
+
+        let total = self.total_assets.get();
+        self.total_assets.set(total.saturating_sub(assets));
+
+        Ok(())
+    }
+
+    /// Sets `vault` as the entry point of `asset`, replacing the previous
+    /// one. Setting [`Address::ZERO`] removes the entry point.
+    ///
+    /// NOTE: This function does not check who calls it. The share token must
+    /// restrict it, e.g. to its owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `asset` - Address of the asset.
+    /// * `vault` - Address of the entry point.
+    ///
+    /// # Events
+    ///
+    /// * [`VaultUpdate`].
+    pub fn _update_vault(&mut self, asset: Address, vault: Address) {
+        let previous = self.vaults.get(asset);
+        self.is_vault.setter(previous).set(false);
+
+        self.vaults.setter(asset).set(vault);
+        if !vault.is_zero() {
+            self.is_vault.setter(vault).set(true);
+        }
+
+        evm::log(VaultUpdate { asset, vault });
+    }
+
+    /// Sets the assets managed by the entry points, e.g. to report gains or
+    /// losses of the pool.
+    ///
+    /// NOTE: This function does not check who calls it. The share token must
+    /// restrict it, e.g. to its owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `assets` - Assets managed by the entry points.
+    pub fn _set_total_assets(&mut self, assets: U256) {
+        self.total_assets.set(assets);
+    }
+
+    /// Checks that `caller` is an entry point.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedVault`] - Otherwise.
+    fn _check_vault(&self, caller: Address) -> Result<(), Error> {
+        if self.is_vault.get(caller) {
+            return Ok(());
+        }
+        Err(Error::UnauthorizedVault(ERC7575UnauthorizedVault { caller }))
+    }
+}
+
+impl IErc165 for Erc7575Share {
+    /// Supports the ERC-7575 share interface.
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        0xf815_c03d_u32 == u32::from_be_bytes(*interface_id)
+            || Erc20::supports_interface(interface_id)
+    }
+}
+
+/// External share token of an [`Erc7575Vault`].
+#[storage]
+pub(crate) struct ShareToken {
+    /// Address of the share token.
+    pub(crate) address: StorageAddress,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for ShareToken {}
+
+impl ShareToken {
+    /// Returns the assets deposited through all entry points of the share
+    /// token.
+    fn total_assets(&mut self) -> Result<U256, Error> {
+        let share = self.address.get();
+        IErc7575Share::new(share)
+            .total_assets(Call::new_in(self))
+            .map_err(|e| Self::error(share, e))
+    }
+
+    /// Converts a failed call to the share token into an [`Error`], keeping
+    /// the revert reason of the share token if there is one.
+    fn error(share: Address, e: call::Error) -> Error {
+        if let call::Error::Revert(ref reason) = e {
+            if !reason.is_empty() {
+                return Error::InvalidShareWithReason(e);
+            }
+        }
+        Error::InvalidShare(ERC7575InvalidShare { share })
+    }
+}
+
+impl IErc4626Shares for ShareToken {
+    type Error = Error;
+
+    fn share_total_supply(&mut self) -> Result<U256, Self::Error> {
+        let share = self.address.get();
+        IErc7575Share::new(share)
+            .total_supply(Call::new_in(self))
+            .map_err(|e| Self::error(share, e))
+    }
+
+    fn share_balance_of(
+        &mut self,
+        account: Address,
+    ) -> Result<U256, Self::Error> {
+        let share = self.address.get();
+        IErc7575Share::new(share)
+            .balance_of(Call::new_in(self), account)
+            .map_err(|e| Self::error(share, e))
+    }
+
+    fn mint_shares(
+        &mut self,
+        to: Address,
+        shares: U256,
+        assets: U256,
+    ) -> Result<(), Self::Error> {
+        let share = self.address.get();
+        IErc7575Share::new(share)
+            .vault_mint(Call::new_in(self), to, shares, assets)
+            .map_err(|e| Self::error(share, e))
+    }
+
+    fn burn_shares(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        shares: U256,
+        assets: U256,
+    ) -> Result<(), Self::Error> {
+        let share = self.address.get();
+        IErc7575Share::new(share)
+            .vault_burn(Call::new_in(self), owner, spender, shares, assets)
+            .map_err(|e| Self::error(share, e))
+    }
+}
+
+/// State of an [`Erc7575Vault`] Contract.
+#[storage]
+pub struct Erc7575Vault {
+    /// [`Erc4626`] contract, holding the asset and decimals offset.
+    pub erc4626: Erc4626,
+    /// External share token.
+    pub(crate) share: ShareToken,
+}
+
+impl Deref for Erc7575Vault {
+    type Target = Erc4626;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc4626
+    }
+}
+
+impl DerefMut for Erc7575Vault {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc4626
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc7575Vault {}
+
+/// Interface of a single-asset entry point of an ERC-7575 vault.
+///
+/// Mirrors [`IErc4626`], with shares held by the share token instead of the
+/// vault.
+pub trait IErc7575 {
+    /// The error type associated to this trait implementation.
+    type Error: Into<Vec<u8>>;
+
+    /// Returns the address of the share token.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn share(&self) -> Address;
+
+    /// Returns the address of the asset of this entry point.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn asset(&self) -> Address;
+
+    /// Returns the assets managed by all the entry points of the share
+    /// token.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    fn total_assets(&mut self) -> Result<U256, Self::Error>;
+
+    /// See [`IErc4626::convert_to_shares`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    fn convert_to_shares(&mut self, assets: U256) -> Result<U256, Self::Error>;
+
+    /// See [`IErc4626::convert_to_assets`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    fn convert_to_assets(&mut self, shares: U256) -> Result<U256, Self::Error>;
+
+    /// See [`IErc4626::max_deposit`].
+    fn max_deposit(&self, receiver: Address) -> U256;
+
+    /// See [`IErc4626::max_mint`].
+    fn max_mint(&self, receiver: Address) -> U256;
+
+    /// Returns the assets `owner` can withdraw through this entry point,
+    /// which is bounded by the assets it holds.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    /// * [`erc4626::Error::InvalidAsset`] - If the asset cannot be read.
+    fn max_withdraw(&mut self, owner: Address) -> Result<U256, Self::Error>;
+
+    /// Returns the shares `owner` can redeem through this entry point, which
+    /// is bounded by the assets it holds.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    /// * [`erc4626::Error::InvalidAsset`] - If the asset cannot be read.
+    fn max_redeem(&mut self, owner: Address) -> Result<U256, Self::Error>;
+
+    /// See [`IErc4626::preview_deposit`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    fn preview_deposit(&mut self, assets: U256) -> Result<U256, Self::Error>;
+
+    /// See [`IErc4626::preview_mint`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    fn preview_mint(&mut self, shares: U256) -> Result<U256, Self::Error>;
+
+    /// See [`IErc4626::preview_withdraw`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    fn preview_withdraw(&mut self, assets: U256) -> Result<U256, Self::Error>;
+
+    /// See [`IErc4626::preview_redeem`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    fn preview_redeem(&mut self, shares: U256) -> Result<U256, Self::Error>;
+
+    /// Deposits `assets` into this entry point and mints the matching shares
+    /// of the share token to `receiver`. See [`IErc4626::deposit`].
+    ///
+    /// # Errors
+    ///
+    /// * [`erc4626::Error::ExceededMaxDeposit`] - If `assets` exceeds
+    ///   [`IErc7575::max_deposit`].
+    /// * [`erc4626::Error::SafeErc20`] - If the assets cannot be transferred.
+    /// * [`Error::InvalidShare`] - If the shares cannot be minted.
+    ///
+    /// # Events
+    ///
+    /// * [`erc4626::Deposit`].
+    fn deposit(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+    ) -> Result<U256, Self::Error>;
+
+    /// Mints `shares` of the share token to `receiver` for the matching
+    /// assets. See [`IErc4626::mint`].
+    ///
+    /// # Errors
+    ///
+    /// * [`erc4626::Error::ExceededMaxMint`] - If `shares` exceeds
+    ///   [`IErc7575::max_mint`].
+    /// * [`erc4626::Error::SafeErc20`] - If the assets cannot be transferred.
+    /// * [`Error::InvalidShare`] - If the shares cannot be minted.
+    ///
+    /// # Events
+    ///
+    /// * [`erc4626::Deposit`].
+    fn mint(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+    ) -> Result<U256, Self::Error>;
+
+    /// Burns the shares of `owner` matching `assets` and sends `assets` to
+    /// `receiver`. See [`IErc4626::withdraw`].
+    ///
+    /// The caller needs an allowance on the share token if it is not `owner`.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc4626::Error::ExceededMaxWithdraw`] - If `assets` exceeds
+    ///   [`IErc7575::max_withdraw`].
+    /// * [`Error::InvalidShare`] - If the shares cannot be burned.
+    /// * [`erc4626::Error::SafeErc20`] - If the assets cannot be transferred.
+    ///
+    /// # Events
+    ///
+    /// * [`erc4626::Withdraw`].
+    fn withdraw(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<U256, Self::Error>;
+
+    /// Burns `shares` of `owner` and sends the matching assets to
+    /// `receiver`. See [`IErc4626::redeem`].
+    ///
+    /// The caller needs an allowance on the share token if it is not `owner`.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc4626::Error::ExceededMaxRedeem`] - If `shares` exceeds
+    ///   [`IErc7575::max_redeem`].
+    /// * [`Error::InvalidShare`] - If the shares cannot be burned.
+    /// * [`erc4626::Error::SafeErc20`] - If the assets cannot be transferred.
+    ///
+    /// # Events
+    ///
+    /// * [`erc4626::Withdraw`].
+    fn redeem(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<U256, Self::Error>;
+}
+
+impl IErc7575 for Erc7575Vault {
+    type Error = Error;
+
+    fn share(&self) -> Address {
+        self.share.address.get()
+    }
+
+    fn asset(&self) -> Address {
+        self.erc4626.asset()
+    }
+
+    fn total_assets(&mut self) -> Result<U256, Self::Error> {
+        self.share.total_assets()
+    }
+
+    fn convert_to_shares(&mut self, assets: U256) -> Result<U256, Self::Error> {
+        self._convert_to_shares(assets, Rounding::Floor)
+    }
+
+    fn convert_to_assets(&mut self, shares: U256) -> Result<U256, Self::Error> {
+        self._convert_to_assets(shares, Rounding::Floor)
+    }
+
+    fn max_deposit(&self, _receiver: Address) -> U256 {
+        U256::MAX
+    }
+
+    fn max_mint(&self, _receiver: Address) -> U256 {
+        U256::MAX
+    }
+
+    fn max_withdraw(&mut self, owner: Address) -> Result<U256, Self::Error> {
+        let balance = self.share.share_balance_of(owner)?;
+        let assets = self._convert_to_assets(balance, Rounding::Floor)?;
+        Ok(assets.min(self._held_assets()?))
+    }
+
+    fn max_redeem(&mut self, owner: Address) -> Result<U256, Self::Error> {
+        let balance = self.share.share_balance_of(owner)?;
+        let held = self._held_assets()?;
+        let held_shares = self._convert_to_shares(held, Rounding::Floor)?;
+        Ok(balance.min(held_shares))
+    }
+
+    fn preview_deposit(&mut self, assets: U256) -> Result<U256, Self::Error> {
+        self._convert_to_shares(assets, Rounding::Floor)
+    }
+
+    fn preview_mint(&mut self, shares: U256) -> Result<U256, Self::Error> {
+        self._convert_to_assets(shares, Rounding::Ceil)
+    }
+
+    fn preview_withdraw(&mut self, assets: U256) -> Result<U256, Self::Error> {
+        self._convert_to_shares(assets, Rounding::Ceil)
+    }
+
+    fn preview_redeem(&mut self, shares: U256) -> Result<U256, Self::Error> {
+        self._convert_to_assets(shares, Rounding::Floor)
+    }
+
+    fn deposit(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+    ) -> Result<U256, Self::Error> {
+        let max_assets = self.max_deposit(receiver);
+        if assets > max_assets {
+            return Err(erc4626::Error::ExceededMaxDeposit(
+                ERC4626ExceededMaxDeposit { receiver, assets, max: max_assets },
+            )
+            .into());
+        }
+
+        let shares = self.preview_deposit(assets)?;
+        self._deposit(msg::sender(), receiver, assets, shares)?;
+
+        Ok(shares)
+    }
+
+    fn mint(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+    ) -> Result<U256, Self::Error> {
+        let max_shares = self.max_mint(receiver);
+        if shares > max_shares {
+            return Err(erc4626::Error::ExceededMaxMint(
+                ERC4626ExceededMaxMint { receiver, shares, max: max_shares },
+            )
+            .into());
+        }
+
+        let assets = self.preview_mint(shares)?;
+        self._deposit(msg::sender(), receiver, assets, shares)?;
+
+        Ok(assets)
+    }
+
+    fn withdraw(
+        &mut self,
+        assets: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<U256, Self::Error> {
+        let max_assets = self.max_withdraw(owner)?;
+        if assets > max_assets {
+            return Err(erc4626::Error::ExceededMaxWithdraw(
+                ERC4626ExceededMaxWithdraw { owner, assets, max: max_assets },
+            )
+            .into());
+        }
+
+        let shares = self.preview_withdraw(assets)?;
+        self._withdraw(msg::sender(), receiver, owner, assets, shares)?;
+
+        Ok(shares)
+    }
+
+    fn redeem(
+        &mut self,
+        shares: U256,
+        receiver: Address,
+        owner: Address,
+    ) -> Result<U256, Self::Error> {
+        let max_shares = self.max_redeem(owner)?;
+        if shares > max_shares {
+            return Err(erc4626::Error::ExceededMaxRedeem(
+                ERC4626ExceededMaxRedeem { owner, shares, max: max_shares },
+            )
+            .into());
+        }
+
+        let assets = self.preview_redeem(shares)?;
+        self._withdraw(msg::sender(), receiver, owner, assets, shares)?;
+
+        Ok(assets)
+    }
+}
+
+impl Erc7575Vault {
+    /// Converts `assets` to shares with the pooled accounting of the share
+    /// token, using the specified `rounding` mode.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    fn _convert_to_shares(
+        &mut self,
+        assets: U256,
+        rounding: Rounding,
+    ) -> Result<U256, Error> {
+        let total_assets = self.total_assets()?;
+        let total_supply = self.share.share_total_supply()?;
+        Ok(self.erc4626._convert_to_shares_with(
+            assets,
+            total_assets,
+            total_supply,
+            rounding,
+        ))
+    }
+
+    /// Converts `shares` to assets with the pooled accounting of the share
+    /// token, using the specified `rounding` mode.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShare`] - If the share token cannot be read.
+    fn _convert_to_assets(
+        &mut self,
+        shares: U256,
+        rounding: Rounding,
+    ) -> Result<U256, Error> {
+        let total_assets = self.total_assets()?;
+        let total_supply = self.share.share_total_supply()?;
+        Ok(self.erc4626._convert_to_assets_with(
+            shares,
+            total_assets,
+            total_supply,
+            rounding,
+        ))
+    }
+
+    /// Deposit/mint common workflow. See [`Erc4626::_deposit_with`].
+    ///
+    /// # Errors
+    ///
+    /// * [`erc4626::Error::SafeErc20`] - If the assets cannot be transferred.
+    /// * [`Error::InvalidShareWithReason`] - If the share token rejects the
+    ///   mint.
+    /// * [`Error::InvalidShare`] - If the share token cannot be called.
+    ///
+    /// # Events
+    ///
+    /// * [`erc4626::Deposit`].
+    pub fn _deposit(
+        &mut self,
+        caller: Address,
+        receiver: Address,
+        assets: U256,
+        shares: U256,
+    ) -> Result<(), Error> {
+        self.erc4626._deposit_with(
+            caller,
+            receiver,
+            assets,
+            shares,
+            &mut self.share,
+        )
+    }
+
+    /// Withdraw/redeem common workflow. See [`Erc4626::_withdraw_with`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidShareWithReason`] - If the share token rejects the
+    ///   burn, e.g. when `caller` lacks allowance.
+    /// * [`Error::InvalidShare`] - If the share token cannot be called.
+    /// * [`erc4626::Error::SafeErc20`] - If the assets cannot be transferred.
+    ///
+    /// # Events
+    ///
+    /// * [`erc4626::Withdraw`].
+    pub fn _withdraw(
+        &mut self,
+        caller: Address,
+        receiver: Address,
+        owner: Address,
+        assets: U256,
+        shares: U256,
+    ) -> Result<(), Error> {
+        self.erc4626._withdraw_with(
+            caller,
+            receiver,
+            owner,
+            assets,
+            shares,
+            &mut self.share,
+        )
+    }
+
+    /// Returns the assets held by this entry point.
+    fn _held_assets(&mut self) -> Result<U256, Error> {
+        let asset = self.asset();
+        IErc20Solidity::new(asset)
+            .balance_of(Call::new_in(self), contract::address())
+            .map_err(|_| {
+                erc4626::Error::InvalidAsset(InvalidAsset { asset }).into()
+            })
+    }
+}
+
+impl IErc165 for Erc7575Vault {
+    /// Supports the ERC-7575 vault interface.
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        0x2f0a_18c5_u32 == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::{call, prelude::*};
+
+    use super::{
+        ERC7575UnauthorizedVault, Erc7575Share, Erc7575Vault, Error, IErc7575,
+    };
+    use crate::{
+        token::erc20::{self, ERC20InsufficientAllowance, Erc20, IErc20},
+        utils::introspection::erc165::IErc165,
+    };
+
+    #[storage]
+    struct ShareExample {
+        erc20: Erc20,
+        share: Erc7575Share,
+    }
+
+    #[public]
+    impl ShareExample {
+        fn vault(&self, asset: Address) -> Address {
+            self.share.vault(asset)
+        }
+
+        fn total_supply(&self) -> U256 {
+            self.erc20.total_supply()
+        }
+
+        fn balance_of(&self, account: Address) -> U256 {
+            self.erc20.balance_of(account)
+        }
+
+        fn total_assets(&self) -> U256 {
+            self.share.total_assets()
+        }
+
+        fn vault_mint(
+            &mut self,
+            to: Address,
+            shares: U256,
+            assets: U256,
+        ) -> Result<(), Error> {
+            self.share.vault_mint(to, shares, assets, &mut self.erc20)
+        }
+
+        fn vault_burn(
+            &mut self,
+            owner: Address,
+            spender: Address,
+            shares: U256,
+            assets: U256,
+        ) -> Result<(), Error> {
+            self.share.vault_burn(
+                owner,
+                spender,
+                shares,
+                assets,
+                &mut self.erc20,
+            )
+        }
+    }
+
+    unsafe impl TopLevelStorage for ShareExample {}
+
+    #[storage]
+    struct VaultExample {
+        vault: Erc7575Vault,
+    }
+
+    #[public]
+    impl VaultExample {
+        fn deposit(
+            &mut self,
+            assets: U256,
+            receiver: Address,
+        ) -> Result<U256, Error> {
+            self.vault.deposit(assets, receiver)
+        }
+
+        fn redeem(
+            &mut self,
+            shares: U256,
+            receiver: Address,
+            owner: Address,
+        ) -> Result<U256, Error> {
+            self.vault.redeem(shares, receiver, owner)
+        }
+
+        fn withdraw(
+            &mut self,
+            assets: U256,
+            receiver: Address,
+            owner: Address,
+        ) -> Result<U256, Error> {
+            self.vault.withdraw(assets, receiver, owner)
+        }
+
+        fn max_redeem(&mut self, owner: Address) -> Result<U256, Error> {
+            self.vault.max_redeem(owner)
+        }
+    }
+
+    unsafe impl TopLevelStorage for VaultExample {}
+
+    const AMOUNT: U256 = uint!(1000_U256);
+
+    fn setup_vault(
+        vault: &Contract<VaultExample>,
+        asset: &Contract<Erc20>,
+        share: &Contract<ShareExample>,
+        holder: Address,
+    ) {
+        let asset_addr = asset.address();
+        let share_addr = share.address();
+        vault.init(holder, |vault| {
+            vault.vault.erc4626.asset.set(asset_addr);
+            vault.vault.share.address.set(share_addr);
+        });
+        share.init(holder, |share| {
+            share.share._update_vault(asset_addr, vault.address());
+        });
+        asset.init(holder, |asset| {
+            asset._mint(holder, AMOUNT).expect("should mint assets");
+        });
+        asset
+            .sender(holder)
+            .approve(vault.address(), U256::MAX)
+            .expect("should approve the vault");
+    }
+
+    #[motsu::test]
+    fn entry_points_mint_the_same_share(
+        share: Contract<ShareExample>,
+        usdc_vault: Contract<VaultExample>,
+        usdt_vault: Contract<VaultExample>,
+        usdc: Contract<Erc20>,
+        usdt: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup_vault(&usdc_vault, &usdc, &share, alice);
+        setup_vault(&usdt_vault, &usdt, &share, bob);
+
+        assert_eq!(
+            share.sender(alice).vault(usdc.address()),
+            usdc_vault.address()
+        );
+        assert_eq!(
+            share.sender(alice).vault(usdt.address()),
+            usdt_vault.address()
+        );
+        assert_eq!(usdc_vault.sender(alice).vault.share(), share.address());
+
+        let shares = usdc_vault
+            .sender(alice)
+            .deposit(AMOUNT, alice)
+            .expect("should deposit USDC");
+        assert_eq!(shares, AMOUNT);
+
+        let shares = usdt_vault
+            .sender(bob)
+            .deposit(AMOUNT, bob)
+            .expect("should deposit USDT");
+        assert_eq!(shares, AMOUNT);
+
+        assert_eq!(share.sender(alice).total_supply(), AMOUNT * uint!(2_U256));
+        assert_eq!(share.sender(alice).total_assets(), AMOUNT * uint!(2_U256));
+        assert_eq!(usdc.sender(alice).balance_of(usdc_vault.address()), AMOUNT);
+        assert_eq!(usdt.sender(alice).balance_of(usdt_vault.address()), AMOUNT);
+    }
+
+    #[motsu::test]
+    fn redeem_through_another_entry_point(
+        share: Contract<ShareExample>,
+        usdc_vault: Contract<VaultExample>,
+        usdt_vault: Contract<VaultExample>,
+        usdc: Contract<Erc20>,
+        usdt: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup_vault(&usdc_vault, &usdc, &share, alice);
+        setup_vault(&usdt_vault, &usdt, &share, bob);
+        usdc_vault
+            .sender(alice)
+            .deposit(AMOUNT, alice)
+            .expect("should deposit");
+        let half = AMOUNT / uint!(2_U256);
+        usdt_vault.sender(bob).deposit(half, bob).expect("should deposit");
+
+        // The USDT entry point only holds `half` assets.
+        assert_eq!(
+            usdt_vault.sender(alice).max_redeem(alice).expect("should read"),
+            half
+        );
+
+        let assets = usdt_vault
+            .sender(alice)
+            .redeem(half, alice, alice)
+            .expect("should redeem through the USDT entry point");
+        assert_eq!(assets, half);
+        assert_eq!(usdt.sender(alice).balance_of(alice), half);
+        assert_eq!(share.sender(alice).balance_of(alice), AMOUNT - half);
+        assert_eq!(share.sender(alice).total_assets(), AMOUNT);
+
+        let err = usdt_vault
+            .sender(alice)
+            .redeem(uint!(1_U256), alice, alice)
+            .expect_err("should not redeem more than held");
+        assert!(matches!(
+            err,
+            Error::Erc4626(
+                crate::token::erc20::extensions::erc4626::Error::ExceededMaxRedeem(_)
+            )
+        ));
+    }
+
+    #[motsu::test]
+    fn withdraw_spends_share_allowance(
+        share: Contract<ShareExample>,
+        vault: Contract<VaultExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup_vault(&vault, &asset, &share, alice);
+        vault.sender(alice).deposit(AMOUNT, alice).expect("should deposit");
+
+        let err = vault
+            .sender(bob)
+            .withdraw(AMOUNT, bob, alice)
+            .expect_err("should not withdraw without allowance");
+        let expected: Vec<u8> =
+            erc20::Error::InsufficientAllowance(ERC20InsufficientAllowance {
+                spender: bob,
+                allowance: U256::ZERO,
+                needed: AMOUNT,
+            })
+            .into();
+        assert!(matches!(
+            err,
+            Error::InvalidShareWithReason(call::Error::Revert(ref reason))
+                if *reason == expected
+        ));
+
+        share
+            .sender(alice)
+            .erc20
+            .approve(bob, AMOUNT)
+            .expect("should approve bob");
+        let shares = vault
+            .sender(bob)
+            .withdraw(AMOUNT, bob, alice)
+            .expect("should withdraw with allowance");
+        assert_eq!(shares, AMOUNT);
+        assert_eq!(asset.sender(alice).balance_of(bob), AMOUNT);
+        assert_eq!(share.sender(alice).erc20.allowance(alice, bob), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn only_entry_points_mint_and_burn(
+        share: Contract<ShareExample>,
+        alice: Address,
+    ) {
+        let err = share
+            .sender(alice)
+            .vault_mint(alice, AMOUNT, AMOUNT)
+            .expect_err("should not mint from a non-vault");
+        assert!(matches!(
+            err,
+            Error::UnauthorizedVault(ERC7575UnauthorizedVault { caller })
+                if caller == alice
+        ));
+
+        let err = share
+            .sender(alice)
+            .vault_burn(alice, alice, AMOUNT, AMOUNT)
+            .expect_err("should not burn from a non-vault");
+        assert!(matches!(err, Error::UnauthorizedVault(_)));
+    }
+
+    #[motsu::test]
+    fn update_vault_replaces_entry_point(
+        share: Contract<ShareExample>,
+        asset: Address,
+        old_vault: Address,
+        new_vault: Address,
+    ) {
+        share.init(old_vault, |share| {
+            share.share._update_vault(asset, old_vault);
+            share.share._update_vault(asset, new_vault);
+        });
+
+        assert_eq!(share.sender(old_vault).vault(asset), new_vault);
+        let err = share
+            .sender(old_vault)
+            .vault_mint(old_vault, AMOUNT, AMOUNT)
+            .expect_err("should not mint from a replaced vault");
+        assert!(matches!(err, Error::UnauthorizedVault(_)));
+
+        share
+            .sender(new_vault)
+            .vault_mint(new_vault, AMOUNT, AMOUNT)
+            .expect("should mint from the new vault");
+        assert_eq!(share.sender(new_vault).balance_of(new_vault), AMOUNT);
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc7575Share::supports_interface(
+            0xf815_c03d_u32.to_be_bytes().into()
+        ));
+        assert!(Erc7575Vault::supports_interface(
+            0x2f0a_18c5_u32.to_be_bytes().into()
+        ));
+        assert!(!Erc7575Vault::supports_interface(
+            0xdead_beef_u32.to_be_bytes().into()
+        ));
+    }
+}
@@ -2,14 +2,14 @@
 //! [ERC-3156].
 //!
 //! Adds the [`IErc3156FlashLender::flash_loan`] method, which provides flash
-//! loan support at the token level. By default there is no fee, but this can be
-//! changed by overriding [`IErc3156FlashLender::flash_loan`].
+//! loan support at the token level. The fee and its receiver are provided by
+//! the [`IFlashMintHooks`] implementation the extension is composed with. By
+//! default there is no fee.
 //!
 //! NOTE: When this extension is used along with the
-//! [`crate::token::erc20::extensions::Capped`] extension,
-//! [`IErc3156FlashLender::max_flash_loan`] will not correctly reflect the
-//! maximum that can be flash minted. We recommend overriding
-//! [`IErc3156FlashLender::max_flash_loan`] so that it correctly reflects the
+//! [`crate::token::erc20::extensions::Capped`] extension, pass the contract's
+//! [`Capped`] to [`IErc3156FlashLender::max_flash_loan`] and
+//! [`IErc3156FlashLender::flash_loan`], so that flash loans never exceed the
 //! supply cap.
 //!
 //! [ERC-3156]: https://eips.ethereum.org/EIPS/eip-3156
@@ -19,14 +19,10 @@
 
 use alloy_primitives::{Address, U256};
 use stylus_sdk::{
-    abi::Bytes,
-    call::Call,
-    contract, msg,
-    prelude::*,
-    storage::{StorageAddress, StorageU256, TopLevelStorage},
+    abi::Bytes, call::Call, contract, msg, prelude::*, storage::TopLevelStorage,
 };
 
-use crate::token::erc20::{self, Erc20, IErc20};
+use crate::token::erc20::{self, extensions::Capped, Erc20, IErc20};
 
 /// The expected value returned from [`IERC3156FlashBorrower::on_flash_loan`].
 pub const BORROWER_CALLBACK_VALUE: [u8; 32] = keccak_const::Keccak256::new()
@@ -113,19 +109,50 @@ mod borrower {
     }
 }
 
+/// Overridable hooks of an [`Erc20FlashMint`] contract.
+///
+/// Every method has a default implementation, so an empty storage struct is
+/// enough for a flash mint without fees.
+pub trait IFlashMintHooks {
+    /// Returns the fee applied when doing flash loans. By default there is no
+    /// fee.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token` - The token to be flash loaned.
+    /// * `value` - The amount of tokens to be loaned.
+    fn _flash_fee(&self, token: Address, value: U256) -> U256 {
+        let _ = (token, value);
+        U256::ZERO
+    }
+
+    /// Returns the receiver address of the flash fee. By default the zero
+    /// address is returned, which means the fee is burned together with the
+    /// loan.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn _flash_fee_receiver(&self) -> Address {
+        Address::ZERO
+    }
+}
+
 /// State of an [`Erc20FlashMint`] Contract.
 #[storage]
-pub struct Erc20FlashMint {
-    /// Fee applied when doing flash loans.
-    pub(crate) flash_fee_value: StorageU256,
-    /// Receiver address of the flash fee.
-    pub(crate) flash_fee_receiver_address: StorageAddress,
+pub struct Erc20FlashMint<T: IFlashMintHooks + StorageType> {
+    /// Contract implementing [`IFlashMintHooks`] trait.
+    pub hooks: T,
 }
 
 /// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
 /// calling other contracts and not `&mut (impl TopLevelStorage +
 /// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
-unsafe impl TopLevelStorage for Erc20FlashMint {}
+unsafe impl<T: IFlashMintHooks + StorageType> TopLevelStorage
+    for Erc20FlashMint<T>
+{
+}
 
 /// Interface of the ERC-3156 Flash Lender, as defined in [ERC-3156].
 ///
@@ -136,27 +163,36 @@ pub trait IErc3156FlashLender {
 
     /// Returns the maximum amount of tokens available for loan.
     ///
-    /// NOTE: This function does not consider any form of supply cap, so in case
-    /// it's used in a token with a cap like
-    /// [`crate::token::erc20::extensions::Capped`], make sure to override this
-    /// function to integrate the cap instead of [`U256::MAX`].
+    /// When `capped` is provided, its cap bounds the total supply, so a
+    /// [`Capped`] token never flash mints past its cap.
     ///
     /// # Arguments
     ///
     /// * `&self` - Read access to the contract's state.
     /// * `token` - The address of the token that is requested.
     /// * `erc20` - Read access to an [`Erc20`] contract.
+    /// * `capped` - Read access to the contract's [`Capped`] extension, if any.
     ///
     /// # Examples
     ///
     /// ```rust,ignore
     /// fn max_flash_loan(&self, token: Address) -> U256 {
-    ///     self.erc20_flash_mint.max_flash_loan(token, &self.erc20)
+    ///     self.erc20_flash_mint.max_flash_loan(
+    ///         token,
+    ///         &self.erc20,
+    ///         Some(&self.capped),
+    ///     )
     /// }
     /// ```
-    fn max_flash_loan(&self, token: Address, erc20: &Erc20) -> U256;
+    fn max_flash_loan(
+        &self,
+        token: Address,
+        erc20: &Erc20,
+        capped: Option<&Capped>,
+    ) -> U256;
 
-    /// Returns the fee applied when doing flash loans.
+    /// Returns the fee applied when doing flash loans, as reported by
+    /// [`IFlashMintHooks::_flash_fee`].
     ///
     /// # Arguments
     ///
@@ -200,6 +236,7 @@ pub trait IErc3156FlashLender {
     /// * `value` - The amount of tokens to be loaned.
     /// * `data` - Arbitrary data that is passed to the receiver.
     /// * `erc20` - Write access to an [`Erc20`] contract.
+    /// * `capped` - Read access to the contract's [`Capped`] extension, if any.
     ///
     /// # Errors
     ///
@@ -237,6 +274,7 @@ pub trait IErc3156FlashLender {
     ///         value,
     ///         data,
     ///         &mut self.erc20,
+    ///         Some(&self.capped),
     ///     )?)
     /// }
     /// ```
@@ -247,15 +285,24 @@ pub trait IErc3156FlashLender {
         value: U256,
         data: Bytes,
         erc20: &mut Erc20,
+        capped: Option<&Capped>,
     ) -> Result<bool, Self::Error>;
 }
 
-impl IErc3156FlashLender for Erc20FlashMint {
+impl<T: IFlashMintHooks + StorageType> IErc3156FlashLender
+    for Erc20FlashMint<T>
+{
     type Error = Error;
 
-    fn max_flash_loan(&self, token: Address, erc20: &Erc20) -> U256 {
+    fn max_flash_loan(
+        &self,
+        token: Address,
+        erc20: &Erc20,
+        capped: Option<&Capped>,
+    ) -> U256 {
         if token == contract::address() {
-            U256::MAX - erc20.total_supply()
+            let max_supply = capped.map_or(U256::MAX, Capped::cap);
+            max_supply.saturating_sub(erc20.total_supply())
         } else {
             U256::MIN
         }
@@ -264,10 +311,10 @@ impl IErc3156FlashLender for Erc20FlashMint {
     fn flash_fee(
         &self,
         token: Address,
-        _value: U256,
+        value: U256,
     ) -> Result<U256, Self::Error> {
         if token == contract::address() {
-            Ok(self.flash_fee_value.get())
+            Ok(self.hooks._flash_fee(token, value))
         } else {
             Err(Error::UnsupportedToken(ERC3156UnsupportedToken { token }))
         }
@@ -283,8 +330,9 @@ impl IErc3156FlashLender for Erc20FlashMint {
         value: U256,
         data: Bytes,
         erc20: &mut Erc20,
+        capped: Option<&Capped>,
     ) -> Result<bool, Self::Error> {
-        let max_loan = self.max_flash_loan(token, erc20);
+        let max_loan = self.max_flash_loan(token, erc20, capped);
         if value > max_loan {
             return Err(Error::ExceededMaxLoan(ERC3156ExceededMaxLoan {
                 max_loan,
@@ -322,7 +370,7 @@ impl IErc3156FlashLender for Erc20FlashMint {
             .expect("allowance should not exceed `U256::MAX`");
         erc20._spend_allowance(receiver, contract::address(), allowance)?;
 
-        let flash_fee_receiver = self.flash_fee_receiver_address.get();
+        let flash_fee_receiver = self.hooks._flash_fee_receiver();
 
         if fee.is_zero() || flash_fee_receiver.is_zero() {
             erc20._burn(receiver, allowance)?;
@@ -339,24 +387,45 @@ impl IErc3156FlashLender for Erc20FlashMint {
 mod tests {
     use alloy_primitives::{uint, Address, U256};
     use motsu::prelude::Contract;
-    use stylus_sdk::{abi::Bytes, prelude::*};
+    use stylus_sdk::{
+        abi::Bytes,
+        prelude::*,
+        storage::{StorageAddress, StorageU256},
+    };
 
     use super::{
-        ERC3156ExceededMaxLoan, ERC3156InvalidReceiver,
+        Capped, ERC3156ExceededMaxLoan, ERC3156InvalidReceiver,
         ERC3156UnsupportedToken, Erc20, Erc20FlashMint, Error,
-        IErc3156FlashLender,
+        IErc3156FlashLender, IFlashMintHooks,
     };
 
+    #[storage]
+    struct TestHooks {
+        fee: StorageU256,
+        fee_receiver: StorageAddress,
+    }
+
+    impl IFlashMintHooks for TestHooks {
+        fn _flash_fee(&self, _token: Address, value: U256) -> U256 {
+            // One basis point of the loan plus a flat fee.
+            value / uint!(10000_U256) + self.fee.get()
+        }
+
+        fn _flash_fee_receiver(&self) -> Address {
+            self.fee_receiver.get()
+        }
+    }
+
     #[storage]
     struct Erc20FlashMintTestExample {
-        erc20_flash_mint: Erc20FlashMint,
+        erc20_flash_mint: Erc20FlashMint<TestHooks>,
         erc20: Erc20,
     }
 
     #[public]
     impl Erc20FlashMintTestExample {
         fn max_flash_loan(&self, token: Address) -> U256 {
-            self.erc20_flash_mint.max_flash_loan(token, &self.erc20)
+            self.erc20_flash_mint.max_flash_loan(token, &self.erc20, None)
         }
 
         fn flash_fee(
@@ -380,6 +449,7 @@ mod tests {
                 value,
                 data,
                 &mut self.erc20,
+                None,
             )
         }
     }
@@ -432,7 +502,7 @@ mod tests {
     ) {
         let flash_fee_value = uint!(69_U256);
         contract.init(alice, |contract| {
-            contract.erc20_flash_mint.flash_fee_value.set(flash_fee_value);
+            contract.erc20_flash_mint.hooks.fee.set(flash_fee_value);
         });
 
         let flash_fee = contract
@@ -441,6 +511,13 @@ mod tests {
             .expect("should return flash fee value");
 
         assert_eq!(flash_fee, flash_fee_value);
+
+        let flash_fee = contract
+            .sender(alice)
+            .flash_fee(contract.address(), uint!(20000_U256))
+            .expect("should return flash fee value");
+
+        assert_eq!(flash_fee, flash_fee_value + uint!(2_U256));
     }
 
     #[motsu::test]
@@ -533,4 +610,147 @@ mod tests {
                 if receiver == invalid_receiver
         ));
     }
+
+    #[storage]
+    struct CappedFlashMintTestExample {
+        erc20_flash_mint: Erc20FlashMint<TestHooks>,
+        erc20: Erc20,
+        capped: Capped,
+    }
+
+    #[public]
+    impl CappedFlashMintTestExample {
+        fn max_flash_loan(&self, token: Address) -> U256 {
+            self.erc20_flash_mint.max_flash_loan(
+                token,
+                &self.erc20,
+                Some(&self.capped),
+            )
+        }
+
+        fn flash_fee(
+            &self,
+            token: Address,
+            value: U256,
+        ) -> Result<U256, super::Error> {
+            self.erc20_flash_mint.flash_fee(token, value)
+        }
+
+        fn flash_loan(
+            &mut self,
+            receiver: Address,
+            token: Address,
+            value: U256,
+            data: Bytes,
+        ) -> Result<bool, super::Error> {
+            self.erc20_flash_mint.flash_loan(
+                receiver,
+                token,
+                value,
+                data,
+                &mut self.erc20,
+                Some(&self.capped),
+            )
+        }
+    }
+
+    unsafe impl TopLevelStorage for CappedFlashMintTestExample {}
+
+    #[motsu::test]
+    fn max_flash_loan_respects_cap(
+        contract: Contract<CappedFlashMintTestExample>,
+        alice: Address,
+    ) {
+        let cap = uint!(10000_U256);
+        let initial_supply = uint!(4000_U256);
+
+        contract.init(alice, |contract| {
+            contract.capped.cap.set(cap);
+            contract
+                .erc20
+                ._mint(alice, initial_supply)
+                .expect("should mint {{initial_supply}} tokens for {{alice}}");
+        });
+
+        let max_flash_loan =
+            contract.sender(alice).max_flash_loan(contract.address());
+        assert_eq!(max_flash_loan, cap - initial_supply);
+    }
+
+    #[motsu::test]
+    fn flash_fee_is_charged_alongside_cap(
+        contract: Contract<CappedFlashMintTestExample>,
+        alice: Address,
+    ) {
+        let cap = uint!(10000_U256);
+        let flash_fee_value = uint!(69_U256);
+
+        contract.init(alice, |contract| {
+            contract.capped.cap.set(cap);
+            contract.erc20_flash_mint.hooks.fee.set(flash_fee_value);
+        });
+
+        let max_flash_loan =
+            contract.sender(alice).max_flash_loan(contract.address());
+        assert_eq!(max_flash_loan, cap);
+
+        let flash_fee = contract
+            .sender(alice)
+            .flash_fee(contract.address(), uint!(1000_U256))
+            .expect("should return flash fee value");
+        assert_eq!(flash_fee, flash_fee_value);
+    }
+
+    #[motsu::test]
+    fn max_flash_loan_is_zero_when_supply_exceeds_cap(
+        contract: Contract<CappedFlashMintTestExample>,
+        alice: Address,
+    ) {
+        let cap = uint!(1000_U256);
+
+        contract.init(alice, |contract| {
+            contract.capped.cap.set(cap);
+            contract
+                .erc20
+                ._mint(alice, cap + uint!(1_U256))
+                .expect("should mint tokens for {{alice}}");
+        });
+
+        let max_flash_loan =
+            contract.sender(alice).max_flash_loan(contract.address());
+        assert_eq!(max_flash_loan, U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn flash_loan_reverts_when_exceeding_cap(
+        contract: Contract<CappedFlashMintTestExample>,
+        alice: Address,
+    ) {
+        let cap = uint!(10000_U256);
+        let initial_supply = uint!(4000_U256);
+
+        contract.init(alice, |contract| {
+            contract.capped.cap.set(cap);
+            contract
+                .erc20
+                ._mint(alice, initial_supply)
+                .expect("should mint {{initial_supply}} tokens for {{alice}}");
+        });
+
+        let err = contract
+            .sender(alice)
+            .flash_loan(
+                alice,
+                contract.address(),
+                cap - initial_supply + uint!(1_U256),
+                vec![0, 1].into(),
+            )
+            .expect_err("should return Error::ExceededMaxLoan");
+
+        assert!(matches!(
+            err,
+            Error::ExceededMaxLoan(ERC3156ExceededMaxLoan { max_loan })
+                if max_loan == cap - initial_supply
+        ));
+    }
 }
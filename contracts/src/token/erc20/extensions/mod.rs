@@ -1,14 +1,32 @@
 //! Common extensions to the ERC-20 standard.
+pub mod allowlist;
+pub mod blocklist;
+pub mod bridgeable;
 pub mod burnable;
 pub mod capped;
+pub mod custodian;
 pub mod erc4626;
+pub mod erc4626_fees;
+pub mod erc4626_protected;
+pub mod erc7540;
+pub mod erc7575;
 pub mod flash_mint;
 pub mod metadata;
 pub mod permit;
+pub mod temporary_approval;
 
+pub use allowlist::Erc20Allowlist;
+pub use blocklist::Erc20Blocklist;
+pub use bridgeable::{Erc20Bridgeable, IErc7802, ITokenBridge};
 pub use burnable::IErc20Burnable;
 pub use capped::Capped;
-pub use erc4626::{Erc4626, IErc4626};
-pub use flash_mint::{Erc20FlashMint, IErc3156FlashLender};
+pub use custodian::{Erc20Custodian, ICustodian};
+pub use erc4626::{Erc4626, IErc4626, IErc4626Shares};
+pub use erc4626_fees::Erc4626Fees;
+pub use erc4626_protected::{Erc4626Protected, InflationProtection};
+pub use erc7540::{Erc7540, IErc7540};
+pub use erc7575::{Erc7575Share, Erc7575Vault, IErc7575};
+pub use flash_mint::{Erc20FlashMint, IErc3156FlashLender, IFlashMintHooks};
 pub use metadata::{Erc20Metadata, IErc20Metadata};
 pub use permit::Erc20Permit;
+pub use temporary_approval::{Erc20TemporaryApproval, IErc20TemporaryApproval};
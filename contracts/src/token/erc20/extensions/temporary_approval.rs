@@ -0,0 +1,537 @@
+//! Optional Temporary Approval extension of the ERC-20 standard, as defined
+//! in the [ERC].
+//!
+//! Adds the `temporary_approve` method, which grants `spender` an allowance
+//! over the caller's tokens that only lives for the current transaction.
+//! Temporary allowances are kept in transient storage ([EIP-1153]), so they
+//! never touch persistent storage and are discarded when the transaction
+//! ends.
+//!
+//! [`Erc20TemporaryApproval::_spend_allowance`] consumes the temporary
+//! allowance before the persistent one set with [`IErc20::approve`], and
+//! [`IErc20::allowance`] reports the sum of both. Temporary approvals do not
+//! emit an [`erc20::Approval`] event.
+//!
+//! NOTE: Only the paths going through [`Erc20TemporaryApproval`] honor
+//! temporary approvals. Spending allowance through the inner [`Erc20`]
+//! directly only consumes the persistent allowance.
+//!
+//! [ERC]: https://eips.ethereum.org/EIPS/eip-7674
+//! [EIP-1153]: https://eips.ethereum.org/EIPS/eip-1153
+
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{b256, Address, FixedBytes, B256, U256};
+use stylus_sdk::{
+    msg,
+    prelude::{public, storage},
+};
+
+use crate::{
+    token::erc20::{
+        self, extensions::IErc20Burnable, ERC20InvalidApprover,
+        ERC20InvalidSpender, Erc20, IErc20,
+    },
+    utils::{introspection::erc165::IErc165, transient},
+};
+
+/// Root transient storage slot of the temporary allowances, derived from
+/// the [ERC-7201] namespace `openzeppelin.transient.ERC20TemporaryApproval`,
+/// the same as the Solidity implementation.
+///
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+const TEMPORARY_ALLOWANCES_SLOT: B256 =
+    b256!("30586ec2bdee597bc6ac1b726591eda9c17973fb3f97b0ffcb485cc06a0d8000");
+
+/// State of an [`Erc20TemporaryApproval`] contract.
+#[storage]
+pub struct Erc20TemporaryApproval {
+    /// [`Erc20`] contract.
+    pub erc20: Erc20,
+}
+
+impl Deref for Erc20TemporaryApproval {
+    type Target = Erc20;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc20
+    }
+}
+
+impl DerefMut for Erc20TemporaryApproval {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc20
+    }
+}
+
+/// Extension of [`Erc20`] that adds temporary allowances, valid only for
+/// the duration of the current transaction.
+pub trait IErc20TemporaryApproval {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Sets a `value` number of tokens as the temporary allowance of
+    /// `spender` over the caller's tokens. The allowance is dropped at the
+    /// end of the transaction.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `spender` - Account that will spend the tokens.
+    /// * `value` - The number of tokens being allowed to transfer by `spender`.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidSpender`] - If the `spender` address is
+    ///   `Address::ZERO`.
+    fn temporary_approve(
+        &mut self,
+        spender: Address,
+        value: U256,
+    ) -> Result<bool, Self::Error>;
+}
+
+impl IErc20TemporaryApproval for Erc20TemporaryApproval {
+    type Error = erc20::Error;
+
+    fn temporary_approve(
+        &mut self,
+        spender: Address,
+        value: U256,
+    ) -> Result<bool, Self::Error> {
+        self._temporary_approve(msg::sender(), spender, value)?;
+        Ok(true)
+    }
+}
+
+#[public]
+impl IErc20 for Erc20TemporaryApproval {
+    type Error = erc20::Error;
+
+    fn total_supply(&self) -> U256 {
+        self.erc20.total_supply()
+    }
+
+    fn balance_of(&self, account: Address) -> U256 {
+        self.erc20.balance_of(account)
+    }
+
+    fn transfer(
+        &mut self,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Self::Error> {
+        self.erc20.transfer(to, value)
+    }
+
+    /// Includes the temporary allowance granted for the current transaction,
+    /// saturating at `U256::MAX`.
+    fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self._temporary_allowance(owner, spender)
+            .saturating_add(self.erc20.allowance(owner, spender))
+    }
+
+    fn approve(
+        &mut self,
+        spender: Address,
+        value: U256,
+    ) -> Result<bool, Self::Error> {
+        self.erc20.approve(spender, value)
+    }
+
+    fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Self::Error> {
+        let spender = msg::sender();
+        self._spend_allowance(from, spender, value)?;
+        self.erc20._transfer(from, to, value)?;
+        Ok(true)
+    }
+}
+
+impl IErc20Burnable for Erc20TemporaryApproval {
+    type Error = erc20::Error;
+
+    fn burn(&mut self, value: U256) -> Result<(), Self::Error> {
+        self.erc20.burn(value)
+    }
+
+    fn burn_from(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Self::Error> {
+        self._spend_allowance(account, msg::sender(), value)?;
+        self.erc20._burn(account, value)
+    }
+}
+
+impl IErc165 for Erc20TemporaryApproval {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        Erc20::supports_interface(interface_id)
+    }
+}
+
+impl Erc20TemporaryApproval {
+    /// Returns the temporary allowance of `spender` over `owner`'s tokens
+    /// for the current transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account that owns the tokens.
+    /// * `spender` - Account that will spend the tokens.
+    #[must_use]
+    pub fn _temporary_allowance(
+        &self,
+        owner: Address,
+        spender: Address,
+    ) -> U256 {
+        transient::load_u256(temporary_allowance_slot(owner, spender))
+    }
+
+    /// Sets `value` as the temporary allowance of `spender` over `owner`'s
+    /// tokens for the current transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - Account that owns the tokens.
+    /// * `spender` - Account that will spend the tokens.
+    /// * `value` - The number of tokens being allowed to transfer by `spender`.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidApprover`] - If the `owner` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InvalidSpender`] - If the `spender` address is
+    ///   `Address::ZERO`.
+    pub fn _temporary_approve(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+    ) -> Result<(), erc20::Error> {
+        if owner.is_zero() {
+            return Err(erc20::Error::InvalidApprover(ERC20InvalidApprover {
+                approver: Address::ZERO,
+            }));
+        }
+
+        if spender.is_zero() {
+            return Err(erc20::Error::InvalidSpender(ERC20InvalidSpender {
+                spender: Address::ZERO,
+            }));
+        }
+
+        transient::store_u256(temporary_allowance_slot(owner, spender), value);
+        Ok(())
+    }
+
+    /// Updates `owner`'s allowance for `spender` based on spent `value`.
+    ///
+    /// Extended version of [`Erc20::_spend_allowance`] that spends the
+    /// temporary allowance of the current transaction first, and the
+    /// remainder from the persistent allowance. An infinite (`U256::MAX`)
+    /// temporary allowance is never decreased.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - Account that owns the tokens.
+    /// * `spender` - Account that will spend the tokens.
+    /// * `value` - The number of tokens being spent.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InsufficientAllowance`] - If not enough allowance is
+    ///   available.
+    pub fn _spend_allowance(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        value: U256,
+    ) -> Result<(), erc20::Error> {
+        let temporary_allowance = self._temporary_allowance(owner, spender);
+        if temporary_allowance == U256::MAX {
+            return Ok(());
+        }
+
+        let spent = temporary_allowance.min(value);
+        if !spent.is_zero() {
+            transient::store_u256(
+                temporary_allowance_slot(owner, spender),
+                temporary_allowance - spent,
+            );
+        }
+
+        let remaining = value - spent;
+        if remaining.is_zero() {
+            return Ok(());
+        }
+        self.erc20._spend_allowance(owner, spender, remaining)
+    }
+}
+
+/// Returns the transient storage slot of the temporary allowance of
+/// `spender` over `owner`'s tokens.
+fn temporary_allowance_slot(owner: Address, spender: Address) -> B256 {
+    transient::derive_mapping(
+        transient::derive_mapping(TEMPORARY_ALLOWANCES_SLOT, owner),
+        spender,
+    )
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{keccak256, uint, Address, B256, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::TopLevelStorage;
+
+    use super::{
+        Erc20TemporaryApproval, IErc20TemporaryApproval,
+        TEMPORARY_ALLOWANCES_SLOT,
+    };
+    use crate::{
+        token::erc20::{self, extensions::IErc20Burnable, IErc20},
+        utils::transient::hostio::end_transaction,
+    };
+
+    unsafe impl TopLevelStorage for Erc20TemporaryApproval {}
+
+    #[test]
+    fn temporary_allowances_slot_matches_erc7201_namespace() {
+        // keccak256(abi.encode(uint256(keccak256(
+        //     "openzeppelin.transient.ERC20TemporaryApproval"
+        // )) - 1)) & ~bytes32(uint256(0xff))
+        let namespace =
+            keccak256("openzeppelin.transient.ERC20TemporaryApproval");
+        let location = U256::from_be_bytes(namespace.0) - U256::from(1);
+        let mut slot = keccak256(location.to_be_bytes::<32>());
+        slot.0[31] = 0;
+
+        assert_eq!(TEMPORARY_ALLOWANCES_SLOT, B256::from(slot));
+    }
+
+    #[motsu::test]
+    fn inner_erc20_ignores_temporary_allowance(
+        contract: Contract<Erc20TemporaryApproval>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint tokens for Alice");
+        contract.sender(alice).temporary_approve(bob, uint!(4_U256)).unwrap();
+
+        assert_eq!(
+            U256::ZERO,
+            contract.sender(alice).erc20.allowance(alice, bob)
+        );
+        let err = contract
+            .sender(bob)
+            .erc20
+            .transfer_from(alice, bob, uint!(1_U256))
+            .expect_err("should only spend the persistent allowance");
+        assert!(matches!(err, erc20::Error::InsufficientAllowance(_)));
+    }
+
+    #[motsu::test]
+    fn temporary_approve_sets_allowance(
+        contract: Contract<Erc20TemporaryApproval>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let value = uint!(10_U256);
+        contract.sender(alice).temporary_approve(bob, value).unwrap();
+
+        assert_eq!(
+            value,
+            contract.sender(alice)._temporary_allowance(alice, bob)
+        );
+        assert_eq!(value, contract.sender(alice).allowance(alice, bob));
+        assert_eq!(
+            U256::ZERO,
+            contract.sender(alice).erc20.allowance(alice, bob)
+        );
+    }
+
+    #[motsu::test]
+    fn temporary_approve_errors_invalid_spender(
+        contract: Contract<Erc20TemporaryApproval>,
+        alice: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            .temporary_approve(Address::ZERO, uint!(1_U256))
+            .expect_err("should not approve the zero address");
+
+        assert!(matches!(
+            err,
+            erc20::Error::InvalidSpender(erc20::ERC20InvalidSpender {
+                spender
+            }) if spender.is_zero()
+        ));
+    }
+
+    #[motsu::test]
+    fn allowance_adds_persistent_and_temporary(
+        contract: Contract<Erc20TemporaryApproval>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice).approve(bob, uint!(3_U256)).unwrap();
+        contract.sender(alice).temporary_approve(bob, uint!(4_U256)).unwrap();
+
+        assert_eq!(uint!(7_U256), contract.sender(alice).allowance(alice, bob));
+
+        contract.sender(alice).temporary_approve(bob, U256::MAX).unwrap();
+        assert_eq!(U256::MAX, contract.sender(alice).allowance(alice, bob));
+    }
+
+    #[motsu::test]
+    fn transfer_from_spends_temporary_allowance_first(
+        contract: Contract<Erc20TemporaryApproval>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint tokens for Alice");
+        contract.sender(alice).approve(bob, uint!(5_U256)).unwrap();
+        contract.sender(alice).temporary_approve(bob, uint!(3_U256)).unwrap();
+
+        contract
+            .sender(bob)
+            .transfer_from(alice, bob, uint!(2_U256))
+            .expect("should spend the temporary allowance");
+        assert_eq!(
+            uint!(1_U256),
+            contract.sender(alice)._temporary_allowance(alice, bob)
+        );
+        assert_eq!(
+            uint!(5_U256),
+            contract.sender(alice).erc20.allowance(alice, bob)
+        );
+
+        contract
+            .sender(bob)
+            .transfer_from(alice, bob, uint!(4_U256))
+            .expect("should fall back to the persistent allowance");
+        assert_eq!(
+            U256::ZERO,
+            contract.sender(alice)._temporary_allowance(alice, bob)
+        );
+        assert_eq!(uint!(2_U256), contract.sender(alice).allowance(alice, bob));
+        assert_eq!(uint!(6_U256), contract.sender(alice).balance_of(bob));
+    }
+
+    #[motsu::test]
+    fn burn_from_spends_temporary_allowance(
+        contract: Contract<Erc20TemporaryApproval>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint tokens for Alice");
+        contract.sender(alice).temporary_approve(bob, uint!(4_U256)).unwrap();
+
+        contract
+            .sender(bob)
+            .burn_from(alice, uint!(4_U256))
+            .expect("should spend the temporary allowance");
+
+        assert_eq!(uint!(6_U256), contract.sender(alice).balance_of(alice));
+        assert_eq!(U256::ZERO, contract.sender(alice).allowance(alice, bob));
+    }
+
+    #[motsu::test]
+    fn infinite_temporary_allowance_is_not_spent(
+        contract: Contract<Erc20TemporaryApproval>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint tokens for Alice");
+        contract.sender(alice).temporary_approve(bob, U256::MAX).unwrap();
+
+        contract
+            .sender(bob)
+            .transfer_from(alice, bob, uint!(10_U256))
+            .expect("should spend the temporary allowance");
+
+        assert_eq!(
+            U256::MAX,
+            contract.sender(alice)._temporary_allowance(alice, bob)
+        );
+    }
+
+    #[motsu::test]
+    fn temporary_allowance_is_discarded_after_transaction(
+        contract: Contract<Erc20TemporaryApproval>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint tokens for Alice");
+        contract.sender(alice).temporary_approve(bob, U256::MAX).unwrap();
+
+        end_transaction();
+
+        assert_eq!(U256::ZERO, contract.sender(alice).allowance(alice, bob));
+        let err = contract
+            .sender(bob)
+            .transfer_from(alice, bob, uint!(1_U256))
+            .expect_err("should not spend an expired temporary allowance");
+        assert!(matches!(
+            err,
+            erc20::Error::InsufficientAllowance(
+                erc20::ERC20InsufficientAllowance { spender, allowance, needed }
+            ) if spender == bob
+                && allowance.is_zero()
+                && needed == uint!(1_U256)
+        ));
+    }
+
+    #[motsu::test]
+    fn transfer_from_errors_insufficient_allowance(
+        contract: Contract<Erc20TemporaryApproval>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect("should mint tokens for Alice");
+        contract.sender(alice).approve(bob, uint!(1_U256)).unwrap();
+        contract.sender(alice).temporary_approve(bob, uint!(2_U256)).unwrap();
+
+        let err = contract
+            .sender(bob)
+            .transfer_from(alice, bob, uint!(4_U256))
+            .expect_err("should not exceed the combined allowance");
+
+        assert!(matches!(
+            err,
+            erc20::Error::InsufficientAllowance(
+                erc20::ERC20InsufficientAllowance { spender, allowance, needed }
+            ) if spender == bob
+                && allowance == uint!(1_U256)
+                && needed == uint!(2_U256)
+        ));
+    }
+}
@@ -0,0 +1,890 @@
+//! Router adding slippage protection to any ERC-4626 vault.
+//!
+//! The standard [ERC-4626] `deposit`, `mint`, `withdraw` and `redeem`
+//! functions have no bound on the exchange rate, so a transaction can be
+//! sandwiched between price-moving transactions. [`Erc4626Router`] calls the
+//! vault on behalf of the caller and reverts when the amount received or
+//! spent is worse than the bound chosen by the caller.
+//!
+//! Assets are pulled from the caller through [`SafeErc20`], so the caller
+//! must approve them to the router, either directly or through
+//! [`Erc4626Router::self_permit`] for tokens implementing [ERC-2612]. To
+//! withdraw or redeem, the caller must approve its shares to the router.
+//!
+//! [`Erc4626Router::migrate`] moves a position from a vault into a chain of
+//! vaults, each one receiving the shares of the previous one, e.g. to move
+//! between two vaults of the same asset or into a vault of vault shares.
+//!
+//! NOTE: The router must not hold tokens between transactions, since anyone
+//! can spend them through a deposit.
+//!
+//! [ERC-4626]: https://eips.ethereum.org/EIPS/eip-4626
+//! [ERC-2612]: https://eips.ethereum.org/EIPS/eip-2612
+
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, B256, U256};
+pub use sol::*;
+use stylus_sdk::{
+    call::Call, contract, msg, prelude::*, storage::TopLevelStorage,
+};
+
+use self::vault::{IErc20Permit, IErc4626 as IErc4626Solidity};
+use crate::token::erc20::utils::{safe_erc20, ISafeErc20, SafeErc20};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Indicates that a deposit minted fewer shares than `min_shares`.
+        ///
+        /// * `shares` - Amount of shares minted.
+        /// * `min_shares` - Minimum amount of shares.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4626RouterInsufficientShares(uint256 shares, uint256 min_shares);
+
+        /// Indicates that a mint cost more assets than `max_assets`.
+        ///
+        /// * `assets` - Amount of assets spent.
+        /// * `max_assets` - Maximum amount of assets.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4626RouterExceededMaxAssets(uint256 assets, uint256 max_assets);
+
+        /// Indicates that a withdrawal burned more shares than `max_shares`.
+        ///
+        /// * `shares` - Amount of shares burned.
+        /// * `max_shares` - Maximum amount of shares.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4626RouterExceededMaxShares(uint256 shares, uint256 max_shares);
+
+        /// Indicates that a redemption returned fewer assets than
+        /// `min_assets`.
+        ///
+        /// * `assets` - Amount of assets returned.
+        /// * `min_assets` - Minimum amount of assets.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4626RouterInsufficientAssets(uint256 assets, uint256 min_assets);
+
+        /// Indicates that the asset of `vault` is not the token held at this
+        /// step of a migration.
+        ///
+        /// * `vault` - Address of the vault.
+        /// * `asset` - Asset of the vault.
+        /// * `expected` - Token held at this step of the migration.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4626RouterAssetMismatch(address vault, address asset, address expected);
+
+        /// Indicates a migration without a destination vault.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4626RouterEmptyPath();
+
+        /// Indicates that a call to `target` failed.
+        ///
+        /// * `target` - Address of the vault or token called.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4626RouterFailedCall(address target);
+    }
+}
+
+mod vault {
+    #![allow(missing_docs)]
+    #![allow(clippy::too_many_arguments)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::stylus_proc::sol_interface;
+
+    sol_interface! {
+        /// Solidity Interface of an ERC-4626 vault, as used by the router.
+        interface IErc4626 {
+            function asset() external view returns (address);
+            function previewMint(uint256 shares) external returns (uint256);
+            function deposit(uint256 assets, address receiver) external returns (uint256);
+            function mint(uint256 shares, address receiver) external returns (uint256);
+            function withdraw(uint256 assets, address receiver, address owner) external returns (uint256);
+            function redeem(uint256 shares, address receiver, address owner) external returns (uint256);
+        }
+
+        /// Solidity Interface of an ERC-2612 token, as used by the router.
+        interface IErc20Permit {
+            function permit(
+                address owner,
+                address spender,
+                uint256 value,
+                uint256 deadline,
+                uint8 v,
+                bytes32 r,
+                bytes32 s
+            ) external;
+        }
+    }
+}
+
+/// An [`Erc4626Router`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates that a deposit minted fewer shares than the minimum.
+    InsufficientShares(ERC4626RouterInsufficientShares),
+    /// Indicates that a mint cost more assets than the maximum.
+    ExceededMaxAssets(ERC4626RouterExceededMaxAssets),
+    /// Indicates that a withdrawal burned more shares than the maximum.
+    ExceededMaxShares(ERC4626RouterExceededMaxShares),
+    /// Indicates that a redemption returned fewer assets than the minimum.
+    InsufficientAssets(ERC4626RouterInsufficientAssets),
+    /// Indicates that the asset of a vault is not the token held at this step
+    /// of a migration.
+    AssetMismatch(ERC4626RouterAssetMismatch),
+    /// Indicates a migration without a destination vault.
+    EmptyPath(ERC4626RouterEmptyPath),
+    /// Indicates that a call to a vault or token failed.
+    FailedCall(ERC4626RouterFailedCall),
+    /// Error type from [`SafeErc20`] contract [`safe_erc20::Error`].
+    SafeErc20(safe_erc20::Error),
+}
+
+/// State of an [`Erc4626Router`] Contract.
+#[storage]
+pub struct Erc4626Router {
+    /// [`SafeErc20`] contract.
+    safe_erc20: SafeErc20,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc4626Router {}
+
+#[public]
+impl Erc4626Router {
+    /// Deposits `assets` of the caller into `vault` for `to`, and returns the
+    /// amount of shares minted.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `vault` - Address of the vault.
+    /// * `to` - Account receiving the shares.
+    /// * `assets` - Amount of assets to deposit.
+    /// * `min_shares` - Minimum amount of shares to receive.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::SafeErc20`] - If the assets cannot be pulled from the caller
+    ///   or approved to `vault`.
+    /// * [`Error::FailedCall`] - If the call to `vault` fails.
+    /// * [`Error::InsufficientShares`] - If fewer than `min_shares` shares are
+    ///   minted.
+    pub fn deposit_with_min(
+        &mut self,
+        vault: Address,
+        to: Address,
+        assets: U256,
+        min_shares: U256,
+    ) -> Result<U256, Error> {
+        let asset = self._asset(vault)?;
+        self.safe_erc20.safe_transfer_from(
+            asset,
+            msg::sender(),
+            contract::address(),
+            assets,
+        )?;
+
+        let shares = self._deposit(vault, asset, assets, to)?;
+        if shares < min_shares {
+            return Err(Error::InsufficientShares(
+                ERC4626RouterInsufficientShares { shares, min_shares },
+            ));
+        }
+
+        Ok(shares)
+    }
+
+    /// Mints `shares` of `vault` for `to` with assets of the caller, and
+    /// returns the amount of assets spent.
+    ///
+    /// Assets not spent by the vault are sent back to the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `vault` - Address of the vault.
+    /// * `to` - Account receiving the shares.
+    /// * `shares` - Amount of shares to mint.
+    /// * `max_assets` - Maximum amount of assets to spend.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If a call to `vault` fails.
+    /// * [`Error::ExceededMaxAssets`] - If minting costs more than `max_assets`
+    ///   assets.
+    /// * [`Error::SafeErc20`] - If the assets cannot be pulled from the caller,
+    ///   approved to `vault` or refunded.
+    pub fn mint_with_max(
+        &mut self,
+        vault: Address,
+        to: Address,
+        shares: U256,
+        max_assets: U256,
+    ) -> Result<U256, Error> {
+        let asset = self._asset(vault)?;
+        let previewed = IErc4626Solidity::new(vault)
+            .preview_mint(Call::new_in(self), shares)
+            .map_err(|_| Self::_failed_call(vault))?;
+        if previewed > max_assets {
+            return Err(Error::ExceededMaxAssets(
+                ERC4626RouterExceededMaxAssets {
+                    assets: previewed,
+                    max_assets,
+                },
+            ));
+        }
+
+        let caller = msg::sender();
+        self.safe_erc20.safe_transfer_from(
+            asset,
+            caller,
+            contract::address(),
+            previewed,
+        )?;
+        self.safe_erc20.force_approve(asset, vault, previewed)?;
+
+        let assets = IErc4626Solidity::new(vault)
+            .mint(Call::new_in(self), shares, to)
+            .map_err(|_| Self::_failed_call(vault))?;
+        if assets > max_assets {
+            return Err(Error::ExceededMaxAssets(
+                ERC4626RouterExceededMaxAssets { assets, max_assets },
+            ));
+        }
+
+        if assets < previewed {
+            self.safe_erc20.force_approve(asset, vault, U256::ZERO)?;
+            self.safe_erc20.safe_transfer(asset, caller, previewed - assets)?;
+        }
+
+        Ok(assets)
+    }
+
+    /// Withdraws `assets` from `vault` to `to`, burning shares of the caller,
+    /// and returns the amount of shares burned.
+    ///
+    /// The caller must approve its shares of `vault` to the router.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `vault` - Address of the vault.
+    /// * `to` - Account receiving the assets.
+    /// * `assets` - Amount of assets to withdraw.
+    /// * `max_shares` - Maximum amount of shares to burn.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call to `vault` fails.
+    /// * [`Error::ExceededMaxShares`] - If more than `max_shares` shares are
+    ///   burned.
+    pub fn withdraw_with_max(
+        &mut self,
+        vault: Address,
+        to: Address,
+        assets: U256,
+        max_shares: U256,
+    ) -> Result<U256, Error> {
+        let shares = IErc4626Solidity::new(vault)
+            .withdraw(Call::new_in(self), assets, to, msg::sender())
+            .map_err(|_| Self::_failed_call(vault))?;
+        if shares > max_shares {
+            return Err(Error::ExceededMaxShares(
+                ERC4626RouterExceededMaxShares { shares, max_shares },
+            ));
+        }
+
+        Ok(shares)
+    }
+
+    /// Redeems `shares` of the caller from `vault` to `to`, and returns the
+    /// amount of assets sent.
+    ///
+    /// The caller must approve its shares of `vault` to the router.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `vault` - Address of the vault.
+    /// * `to` - Account receiving the assets.
+    /// * `shares` - Amount of shares to redeem.
+    /// * `min_assets` - Minimum amount of assets to receive.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call to `vault` fails.
+    /// * [`Error::InsufficientAssets`] - If fewer than `min_assets` assets are
+    ///   sent.
+    pub fn redeem_with_min(
+        &mut self,
+        vault: Address,
+        to: Address,
+        shares: U256,
+        min_assets: U256,
+    ) -> Result<U256, Error> {
+        let assets = IErc4626Solidity::new(vault)
+            .redeem(Call::new_in(self), shares, to, msg::sender())
+            .map_err(|_| Self::_failed_call(vault))?;
+        if assets < min_assets {
+            return Err(Error::InsufficientAssets(
+                ERC4626RouterInsufficientAssets { assets, min_assets },
+            ));
+        }
+
+        Ok(assets)
+    }
+
+    /// Redeems `shares` of the caller from `from_vault` and deposits the
+    /// assets through `to_vaults`, and returns the amount of shares of the
+    /// last vault minted to `to`.
+    ///
+    /// The asset of each vault of `to_vaults` must be the token received from
+    /// the previous step: the asset of `from_vault` for the first one, and
+    /// the shares of the previous vault for the next ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from_vault` - Address of the vault to leave.
+    /// * `shares` - Amount of shares of `from_vault` to redeem.
+    /// * `to_vaults` - Addresses of the vaults to deposit into, in order.
+    /// * `to` - Account receiving the shares of the last vault.
+    /// * `min_shares` - Minimum amount of shares of the last vault to receive.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::EmptyPath`] - If `to_vaults` is empty.
+    /// * [`Error::FailedCall`] - If a call to a vault fails.
+    /// * [`Error::AssetMismatch`] - If the asset of a vault is not the token
+    ///   received from the previous step.
+    /// * [`Error::SafeErc20`] - If a token cannot be approved to a vault.
+    /// * [`Error::InsufficientShares`] - If fewer than `min_shares` shares are
+    ///   minted.
+    pub fn migrate(
+        &mut self,
+        from_vault: Address,
+        shares: U256,
+        to_vaults: Vec<Address>,
+        to: Address,
+        min_shares: U256,
+    ) -> Result<U256, Error> {
+        let mut path = to_vaults.into_iter().peekable();
+        if path.peek().is_none() {
+            return Err(Error::EmptyPath(ERC4626RouterEmptyPath {}));
+        }
+
+        let mut token = self._asset(from_vault)?;
+        let mut amount = IErc4626Solidity::new(from_vault)
+            .redeem(
+                Call::new_in(self),
+                shares,
+                contract::address(),
+                msg::sender(),
+            )
+            .map_err(|_| Self::_failed_call(from_vault))?;
+
+        while let Some(vault) = path.next() {
+            // Intermediate shares stay in the router for the next vault.
+            let receiver =
+                if path.peek().is_some() { contract::address() } else { to };
+            amount = self._deposit_checked(vault, token, amount, receiver)?;
+            token = vault;
+        }
+
+        let shares = amount;
+        if shares < min_shares {
+            return Err(Error::InsufficientShares(
+                ERC4626RouterInsufficientShares { shares, min_shares },
+            ));
+        }
+
+        Ok(shares)
+    }
+
+    /// Approves `value` of `token` of the caller to the router with an
+    /// [ERC-2612] signature, so that it can be used in the same transaction.
+    ///
+    /// [ERC-2612]: https://eips.ethereum.org/EIPS/eip-2612
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token` - Address of the token implementing [ERC-2612].
+    /// * `value` - Amount of tokens to approve.
+    /// * `deadline` - Deadline of the signature.
+    /// * `v` - v value from the signature.
+    /// * `r` - r value from the signature.
+    /// * `s` - s value from the signature.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the permit is rejected by `token`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn self_permit(
+        &mut self,
+        token: Address,
+        value: U256,
+        deadline: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), Error> {
+        IErc20Permit::new(token)
+            .permit(
+                Call::new_in(self),
+                msg::sender(),
+                contract::address(),
+                value,
+                deadline,
+                v,
+                r,
+                s,
+            )
+            .map_err(|_| Self::_failed_call(token))
+    }
+}
+
+impl Erc4626Router {
+    /// Returns the asset of `vault`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call to `vault` fails.
+    fn _asset(&mut self, vault: Address) -> Result<Address, Error> {
+        IErc4626Solidity::new(vault)
+            .asset(Call::new_in(self))
+            .map_err(|_| Self::_failed_call(vault))
+    }
+
+    /// Deposits `assets` of `asset` held by the router into `vault` for
+    /// `receiver`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::SafeErc20`] - If the assets cannot be approved to `vault`.
+    /// * [`Error::FailedCall`] - If the call to `vault` fails.
+    fn _deposit(
+        &mut self,
+        vault: Address,
+        asset: Address,
+        assets: U256,
+        receiver: Address,
+    ) -> Result<U256, Error> {
+        self.safe_erc20.force_approve(asset, vault, assets)?;
+        IErc4626Solidity::new(vault)
+            .deposit(Call::new_in(self), assets, receiver)
+            .map_err(|_| Self::_failed_call(vault))
+    }
+
+    /// Deposits `assets` of `asset` held by the router into `vault` for
+    /// `receiver`, after checking that `asset` is the asset of `vault`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AssetMismatch`] - If `asset` is not the asset of `vault`.
+    /// * [`Error::SafeErc20`] - If the assets cannot be approved to `vault`.
+    /// * [`Error::FailedCall`] - If a call to `vault` fails.
+    fn _deposit_checked(
+        &mut self,
+        vault: Address,
+        asset: Address,
+        assets: U256,
+        receiver: Address,
+    ) -> Result<U256, Error> {
+        let vault_asset = self._asset(vault)?;
+        if vault_asset != asset {
+            return Err(Error::AssetMismatch(ERC4626RouterAssetMismatch {
+                vault,
+                asset: vault_asset,
+                expected: asset,
+            }));
+        }
+        self._deposit(vault, asset, assets, receiver)
+    }
+
+    /// Returns the error of a failed call to `target`.
+    fn _failed_call(target: Address) -> Error {
+        Error::FailedCall(ERC4626RouterFailedCall { target })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::*;
+
+    use super::{
+        ERC4626RouterAssetMismatch, ERC4626RouterExceededMaxAssets,
+        ERC4626RouterExceededMaxShares, ERC4626RouterInsufficientAssets,
+        ERC4626RouterInsufficientShares, Erc4626Router, Error,
+    };
+    use crate::token::erc20::{
+        extensions::{erc4626, Erc4626, IErc4626},
+        Erc20, IErc20,
+    };
+
+    #[storage]
+    struct VaultExample {
+        erc4626: Erc4626,
+        erc20: Erc20,
+    }
+
+    #[public]
+    impl VaultExample {
+        fn asset(&self) -> Address {
+            self.erc4626.asset()
+        }
+
+        fn preview_mint(
+            &mut self,
+            shares: U256,
+        ) -> Result<U256, erc4626::Error> {
+            self.erc4626.preview_mint(shares, &self.erc20)
+        }
+
+        fn deposit(
+            &mut self,
+            assets: U256,
+            receiver: Address,
+        ) -> Result<U256, erc4626::Error> {
+            self.erc4626.deposit(assets, receiver, &mut self.erc20)
+        }
+
+        fn mint(
+            &mut self,
+            shares: U256,
+            receiver: Address,
+        ) -> Result<U256, erc4626::Error> {
+            self.erc4626.mint(shares, receiver, &mut self.erc20)
+        }
+
+        fn withdraw(
+            &mut self,
+            assets: U256,
+            receiver: Address,
+            owner: Address,
+        ) -> Result<U256, erc4626::Error> {
+            self.erc4626.withdraw(assets, receiver, owner, &mut self.erc20)
+        }
+
+        fn redeem(
+            &mut self,
+            shares: U256,
+            receiver: Address,
+            owner: Address,
+        ) -> Result<U256, erc4626::Error> {
+            self.erc4626.redeem(shares, receiver, owner, &mut self.erc20)
+        }
+
+        fn balance_of(&self, account: Address) -> U256 {
+            self.erc20.balance_of(account)
+        }
+
+        fn transfer(
+            &mut self,
+            to: Address,
+            value: U256,
+        ) -> Result<bool, crate::token::erc20::Error> {
+            self.erc20.transfer(to, value)
+        }
+
+        fn transfer_from(
+            &mut self,
+            from: Address,
+            to: Address,
+            value: U256,
+        ) -> Result<bool, crate::token::erc20::Error> {
+            self.erc20.transfer_from(from, to, value)
+        }
+
+        fn approve(
+            &mut self,
+            spender: Address,
+            value: U256,
+        ) -> Result<bool, crate::token::erc20::Error> {
+            self.erc20.approve(spender, value)
+        }
+    }
+
+    unsafe impl TopLevelStorage for VaultExample {}
+
+    const ALICE_ASSETS: U256 = uint!(10_000_U256);
+    const AMOUNT: U256 = uint!(1000_U256);
+
+    fn setup_vault(
+        vault: &Contract<VaultExample>,
+        asset: Address,
+        alice: Address,
+    ) {
+        vault.init(alice, |vault| vault.erc4626.asset.set(asset));
+    }
+
+    fn setup(
+        router: &Contract<Erc4626Router>,
+        vault: &Contract<VaultExample>,
+        asset: &Contract<Erc20>,
+        alice: Address,
+    ) {
+        setup_vault(vault, asset.address(), alice);
+        asset.init(alice, |asset| {
+            asset._mint(alice, ALICE_ASSETS).expect("should mint assets");
+        });
+        asset
+            .sender(alice)
+            .approve(router.address(), U256::MAX)
+            .expect("should approve the router");
+    }
+
+    #[motsu::test]
+    fn deposit_with_min_checks_shares(
+        router: Contract<Erc4626Router>,
+        vault: Contract<VaultExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup(&router, &vault, &asset, alice);
+
+        let shares = router
+            .sender(alice)
+            .deposit_with_min(vault.address(), bob, AMOUNT, AMOUNT)
+            .expect("should deposit");
+        assert_eq!(shares, AMOUNT);
+        assert_eq!(vault.sender(alice).erc20.balance_of(bob), AMOUNT);
+        assert_eq!(
+            asset.sender(alice).balance_of(router.address()),
+            U256::ZERO
+        );
+
+        let err = router
+            .sender(alice)
+            .deposit_with_min(
+                vault.address(),
+                bob,
+                AMOUNT,
+                AMOUNT + uint!(1_U256),
+            )
+            .expect_err("should not mint enough shares");
+        assert!(matches!(
+            err,
+            Error::InsufficientShares(ERC4626RouterInsufficientShares {
+                shares,
+                min_shares,
+            }) if shares == AMOUNT && min_shares == AMOUNT + uint!(1_U256)
+        ));
+    }
+
+    #[motsu::test]
+    fn mint_with_max_checks_assets(
+        router: Contract<Erc4626Router>,
+        vault: Contract<VaultExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+    ) {
+        setup(&router, &vault, &asset, alice);
+
+        let err = router
+            .sender(alice)
+            .mint_with_max(
+                vault.address(),
+                alice,
+                AMOUNT,
+                AMOUNT - uint!(1_U256),
+            )
+            .expect_err("should cost too many assets");
+        assert!(matches!(
+            err,
+            Error::ExceededMaxAssets(ERC4626RouterExceededMaxAssets {
+                assets,
+                max_assets,
+            }) if assets == AMOUNT && max_assets == AMOUNT - uint!(1_U256)
+        ));
+
+        let assets = router
+            .sender(alice)
+            .mint_with_max(vault.address(), alice, AMOUNT, AMOUNT)
+            .expect("should mint");
+        assert_eq!(assets, AMOUNT);
+        assert_eq!(vault.sender(alice).erc20.balance_of(alice), AMOUNT);
+        assert_eq!(
+            asset.sender(alice).balance_of(alice),
+            ALICE_ASSETS - AMOUNT
+        );
+    }
+
+    #[motsu::test]
+    fn withdraw_and_redeem_check_bounds(
+        router: Contract<Erc4626Router>,
+        vault: Contract<VaultExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        setup(&router, &vault, &asset, alice);
+        router
+            .sender(alice)
+            .deposit_with_min(vault.address(), alice, AMOUNT, AMOUNT)
+            .expect("should deposit");
+        vault
+            .sender(alice)
+            .approve(router.address(), U256::MAX)
+            .expect("should approve shares");
+
+        let quarter = AMOUNT / uint!(4_U256);
+        let shares = router
+            .sender(alice)
+            .withdraw_with_max(vault.address(), bob, quarter, quarter)
+            .expect("should withdraw");
+        assert_eq!(shares, quarter);
+        let assets = router
+            .sender(alice)
+            .redeem_with_min(vault.address(), bob, quarter, quarter)
+            .expect("should redeem");
+        assert_eq!(assets, quarter);
+        assert_eq!(
+            asset.sender(alice).balance_of(bob),
+            quarter * uint!(2_U256)
+        );
+        assert_eq!(
+            vault.sender(alice).erc20.balance_of(alice),
+            AMOUNT - quarter * uint!(2_U256)
+        );
+
+        let err = router
+            .sender(alice)
+            .withdraw_with_max(
+                vault.address(),
+                bob,
+                quarter,
+                quarter - uint!(1_U256),
+            )
+            .expect_err("should burn too many shares");
+        assert!(matches!(
+            err,
+            Error::ExceededMaxShares(ERC4626RouterExceededMaxShares {
+                shares,
+                max_shares,
+            }) if shares == quarter && max_shares == quarter - uint!(1_U256)
+        ));
+
+        let err = router
+            .sender(alice)
+            .redeem_with_min(
+                vault.address(),
+                bob,
+                quarter,
+                quarter + uint!(1_U256),
+            )
+            .expect_err("should not return enough assets");
+        assert!(matches!(
+            err,
+            Error::InsufficientAssets(ERC4626RouterInsufficientAssets {
+                assets,
+                min_assets,
+            }) if assets == quarter && min_assets == quarter + uint!(1_U256)
+        ));
+    }
+
+    #[motsu::test]
+    fn migrate_through_vaults(
+        router: Contract<Erc4626Router>,
+        vault: Contract<VaultExample>,
+        other_vault: Contract<VaultExample>,
+        wrapper_vault: Contract<VaultExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+    ) {
+        setup(&router, &vault, &asset, alice);
+        setup_vault(&other_vault, asset.address(), alice);
+        setup_vault(&wrapper_vault, other_vault.address(), alice);
+        router
+            .sender(alice)
+            .deposit_with_min(vault.address(), alice, AMOUNT, AMOUNT)
+            .expect("should deposit");
+        vault
+            .sender(alice)
+            .approve(router.address(), U256::MAX)
+            .expect("should approve shares");
+
+        let shares = router
+            .sender(alice)
+            .migrate(
+                vault.address(),
+                AMOUNT,
+                vec![other_vault.address(), wrapper_vault.address()],
+                alice,
+                AMOUNT,
+            )
+            .expect("should migrate");
+
+        assert_eq!(shares, AMOUNT);
+        assert_eq!(vault.sender(alice).erc20.balance_of(alice), U256::ZERO);
+        assert_eq!(
+            other_vault.sender(alice).erc20.balance_of(wrapper_vault.address()),
+            AMOUNT
+        );
+        assert_eq!(wrapper_vault.sender(alice).erc20.balance_of(alice), AMOUNT);
+    }
+
+    #[motsu::test]
+    fn migrate_reverts_on_asset_mismatch(
+        router: Contract<Erc4626Router>,
+        vault: Contract<VaultExample>,
+        wrapper_vault: Contract<VaultExample>,
+        asset: Contract<Erc20>,
+        alice: Address,
+    ) {
+        setup(&router, &vault, &asset, alice);
+        setup_vault(&wrapper_vault, vault.address(), alice);
+        router
+            .sender(alice)
+            .deposit_with_min(vault.address(), alice, AMOUNT, AMOUNT)
+            .expect("should deposit");
+        vault
+            .sender(alice)
+            .approve(router.address(), U256::MAX)
+            .expect("should approve shares");
+
+        let err = router
+            .sender(alice)
+            .migrate(
+                vault.address(),
+                AMOUNT,
+                vec![wrapper_vault.address()],
+                alice,
+                U256::ZERO,
+            )
+            .expect_err("should not deposit assets into a vault of shares");
+        assert!(matches!(
+            err,
+            Error::AssetMismatch(ERC4626RouterAssetMismatch {
+                vault: mismatched,
+                asset: vault_asset,
+                expected,
+            }) if mismatched == wrapper_vault.address()
+                && vault_asset == vault.address()
+                && expected == asset.address()
+        ));
+
+        let err = router
+            .sender(alice)
+            .migrate(vault.address(), AMOUNT, vec![], alice, U256::ZERO)
+            .expect_err("should require a destination vault");
+        assert!(matches!(err, Error::EmptyPath(_)));
+    }
+}
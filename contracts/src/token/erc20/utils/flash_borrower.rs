@@ -0,0 +1,322 @@
+//! Helper for implementing the borrower side of ERC-3156 flash loans, as
+//! defined in [ERC-3156].
+//!
+//! A lender transfers (or mints) the loan to the borrower and then calls its
+//! `onFlashLoan` function. Since anyone can call that function, a borrower
+//! must check both that the caller is a lender it trusts and that the loan was
+//! initiated by someone it trusts, otherwise an attacker could make it act on
+//! a loan it never asked for.
+//!
+//! [`IErc3156FlashBorrower::on_flash_loan`] performs these checks and returns
+//! the value expected by the lender, so that implementors only need to provide
+//! the trust checks and the logic executed with the borrowed tokens.
+//!
+//! [ERC-3156]: https://eips.ethereum.org/EIPS/eip-3156
+
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, B256, U256};
+pub use sol::*;
+use stylus_sdk::{abi::Bytes, contract, msg, stylus_proc::SolidityError};
+
+use crate::token::erc20::extensions::flash_mint::BORROWER_CALLBACK_VALUE;
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Indicates that the flash loan callback was not called by a trusted
+        /// lender.
+        ///
+        /// * `lender` - Address of the caller.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC3156UntrustedLender(address lender);
+
+        /// Indicates that the flash loan was not started by a trusted
+        /// initiator.
+        ///
+        /// * `initiator` - Address of the initiator of the flash loan.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC3156UntrustedInitiator(address initiator);
+    }
+}
+
+/// An [`IErc3156FlashBorrower`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates that the flash loan callback was not called by a trusted
+    /// lender.
+    UntrustedLender(ERC3156UntrustedLender),
+    /// Indicates that the flash loan was not started by a trusted initiator.
+    UntrustedInitiator(ERC3156UntrustedInitiator),
+}
+
+/// Borrower side of an ERC-3156 flash loan.
+///
+/// Implementors expose [`IErc3156FlashBorrower::on_flash_loan`] as their
+/// `onFlashLoan` function, and must leave `amount + fee` tokens approved to
+/// the lender (see [`IErc3156FlashBorrower::_on_flash_loan`]).
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[public]
+/// impl Borrower {
+///     fn on_flash_loan(
+///         &mut self,
+///         initiator: Address,
+///         token: Address,
+///         amount: U256,
+///         fee: U256,
+///         data: Bytes,
+///     ) -> Result<B256, Vec<u8>> {
+///         Ok(IErc3156FlashBorrower::on_flash_loan(
+///             self, initiator, token, amount, fee, data,
+///         )?)
+///     }
+/// }
+/// ```
+pub trait IErc3156FlashBorrower {
+    /// The error type associated to this trait implementation.
+    type Error: Into<Vec<u8>> + From<Error>;
+
+    /// Returns whether `lender` is allowed to call
+    /// [`IErc3156FlashBorrower::on_flash_loan`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `lender` - Address of the caller.
+    fn _is_trusted_lender(&self, lender: Address) -> bool;
+
+    /// Returns whether flash loans started by `initiator` are accepted.
+    ///
+    /// By default, only loans started by the borrower contract itself are
+    /// accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `initiator` - Address that requested the flash loan from the lender.
+    fn _is_trusted_initiator(&self, initiator: Address) -> bool {
+        initiator == contract::address()
+    }
+
+    /// Executes the borrower logic with the `amount` of `token` lent.
+    ///
+    /// Before returning, the borrower must approve `amount + fee` tokens to
+    /// the lender, which is [`msg::sender()`], so that the loan can be repaid.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `initiator` - The initiator of the flash loan.
+    /// * `token` - The token lent.
+    /// * `amount` - The amount of tokens lent.
+    /// * `fee` - The additional amount of tokens to repay.
+    /// * `data` - Arbitrary data passed by the initiator.
+    ///
+    /// # Errors
+    ///
+    /// Any error of the borrower logic, which reverts the flash loan.
+    fn _on_flash_loan(
+        &mut self,
+        initiator: Address,
+        token: Address,
+        amount: U256,
+        fee: U256,
+        data: &[u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Receives a flash loan.
+    ///
+    /// Checks that the caller is a trusted lender and that the loan was
+    /// started by a trusted initiator, runs
+    /// [`IErc3156FlashBorrower::_on_flash_loan`] and returns
+    /// [`BORROWER_CALLBACK_VALUE`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `initiator` - The initiator of the flash loan.
+    /// * `token` - The token lent.
+    /// * `amount` - The amount of tokens lent.
+    /// * `fee` - The additional amount of tokens to repay.
+    /// * `data` - Arbitrary data passed by the initiator.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UntrustedLender`] - If the caller is not a trusted lender.
+    /// * [`Error::UntrustedInitiator`] - If `initiator` is not trusted.
+    /// * Any error returned by [`IErc3156FlashBorrower::_on_flash_loan`].
+    fn on_flash_loan(
+        &mut self,
+        initiator: Address,
+        token: Address,
+        amount: U256,
+        fee: U256,
+        data: Bytes,
+    ) -> Result<B256, Self::Error> {
+        let lender = msg::sender();
+        if !self._is_trusted_lender(lender) {
+            return Err(Error::UntrustedLender(ERC3156UntrustedLender {
+                lender,
+            })
+            .into());
+        }
+
+        if !self._is_trusted_initiator(initiator) {
+            return Err(Error::UntrustedInitiator(ERC3156UntrustedInitiator {
+                initiator,
+            })
+            .into());
+        }
+
+        self._on_flash_loan(initiator, token, amount, fee, &data)?;
+
+        Ok(BORROWER_CALLBACK_VALUE.into())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, B256, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::{
+        abi::Bytes,
+        prelude::*,
+        storage::{StorageAddress, StorageU256},
+    };
+
+    use super::{
+        ERC3156UntrustedInitiator, ERC3156UntrustedLender, Error,
+        IErc3156FlashBorrower, BORROWER_CALLBACK_VALUE,
+    };
+
+    #[storage]
+    struct TestBorrower {
+        lender: StorageAddress,
+        borrowed: StorageU256,
+    }
+
+    impl IErc3156FlashBorrower for TestBorrower {
+        type Error = Error;
+
+        fn _is_trusted_lender(&self, lender: Address) -> bool {
+            lender == self.lender.get()
+        }
+
+        fn _on_flash_loan(
+            &mut self,
+            _initiator: Address,
+            _token: Address,
+            amount: U256,
+            _fee: U256,
+            _data: &[u8],
+        ) -> Result<(), Self::Error> {
+            self.borrowed.set(amount);
+            Ok(())
+        }
+    }
+
+    #[public]
+    impl TestBorrower {
+        fn on_flash_loan(
+            &mut self,
+            initiator: Address,
+            token: Address,
+            amount: U256,
+            fee: U256,
+            data: Bytes,
+        ) -> Result<B256, Error> {
+            IErc3156FlashBorrower::on_flash_loan(
+                self, initiator, token, amount, fee, data,
+            )
+        }
+    }
+
+    unsafe impl TopLevelStorage for TestBorrower {}
+
+    #[motsu::test]
+    fn on_flash_loan_returns_callback_value(
+        contract: Contract<TestBorrower>,
+        lender: Address,
+        token: Address,
+    ) {
+        let amount = uint!(1000_U256);
+        contract.init(lender, |contract| contract.lender.set(lender));
+
+        let value = contract
+            .sender(lender)
+            .on_flash_loan(
+                contract.address(),
+                token,
+                amount,
+                U256::ZERO,
+                vec![].into(),
+            )
+            .expect("should accept the flash loan");
+
+        assert_eq!(value, B256::from(BORROWER_CALLBACK_VALUE));
+        assert_eq!(contract.sender(lender).borrowed.get(), amount);
+    }
+
+    #[motsu::test]
+    fn on_flash_loan_reverts_when_untrusted_lender(
+        contract: Contract<TestBorrower>,
+        lender: Address,
+        alice: Address,
+        token: Address,
+    ) {
+        contract.init(lender, |contract| contract.lender.set(lender));
+
+        let err = contract
+            .sender(alice)
+            .on_flash_loan(
+                contract.address(),
+                token,
+                uint!(1000_U256),
+                U256::ZERO,
+                vec![].into(),
+            )
+            .expect_err("should return Error::UntrustedLender");
+
+        assert!(matches!(
+            err,
+            Error::UntrustedLender(ERC3156UntrustedLender { lender })
+                if lender == alice
+        ));
+        assert_eq!(contract.sender(alice).borrowed.get(), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn on_flash_loan_reverts_when_untrusted_initiator(
+        contract: Contract<TestBorrower>,
+        lender: Address,
+        alice: Address,
+        token: Address,
+    ) {
+        contract.init(lender, |contract| contract.lender.set(lender));
+
+        let err = contract
+            .sender(lender)
+            .on_flash_loan(
+                alice,
+                token,
+                uint!(1000_U256),
+                U256::ZERO,
+                vec![].into(),
+            )
+            .expect_err("should return Error::UntrustedInitiator");
+
+        assert!(matches!(
+            err,
+            Error::UntrustedInitiator(ERC3156UntrustedInitiator { initiator })
+                if initiator == alice
+        ));
+        assert_eq!(contract.sender(lender).borrowed.get(), U256::ZERO);
+    }
+}
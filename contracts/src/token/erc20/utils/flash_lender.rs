@@ -0,0 +1,492 @@
+//! Reference implementation of an ERC-3156 flash lender for arbitrary ERC-20
+//! tokens, as defined in [ERC-3156].
+//!
+//! Unlike [`crate::token::erc20::extensions::Erc20FlashMint`], which mints
+//! the token's own supply, [`FlashLender`] lends from its balances of any
+//! supported ERC-20 token. Token transfers go through [`SafeErc20`], so tokens
+//! that do not return a value are supported.
+//!
+//! The fee is a fraction of the loan, expressed in basis points and rounded
+//! down. The loan and the fee are pulled back from the receiver at the end of
+//! the loan, so the receiver must approve `amount + fee` tokens to the lender.
+//!
+//! [ERC-3156]: https://eips.ethereum.org/EIPS/eip-3156
+
+use alloc::vec::Vec;
+
+use alloy_primitives::{uint, Address, U256};
+use stylus_sdk::{
+    abi::Bytes,
+    call::Call,
+    contract, msg,
+    prelude::*,
+    storage::{StorageBool, StorageMap, StorageU256, TopLevelStorage},
+};
+
+use crate::{
+    token::erc20::{
+        extensions::flash_mint::{
+            ERC3156ExceededMaxLoan, ERC3156InvalidReceiver,
+            ERC3156UnsupportedToken, IERC3156FlashBorrower,
+            BORROWER_CALLBACK_VALUE,
+        },
+        utils::{safe_erc20, IErc20 as IErc20Solidity, ISafeErc20, SafeErc20},
+    },
+    utils::math::alloy::{Math, Rounding},
+};
+
+/// Denominator of the flash fee, which is expressed in basis points.
+pub const FEE_DENOMINATOR: U256 = uint!(10_000_U256);
+
+/// A [`FlashLender`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicate that the loan token is not supported or valid.
+    UnsupportedToken(ERC3156UnsupportedToken),
+    /// Indicate an error related to the loan value exceeding the maximum.
+    ExceededMaxLoan(ERC3156ExceededMaxLoan),
+    /// Indicate that the receiver of a flashloan is not a valid
+    /// [`IERC3156FlashBorrower::on_flash_loan`] implementer.
+    InvalidReceiver(ERC3156InvalidReceiver),
+    /// Error type from [`SafeErc20`] contract [`safe_erc20::Error`].
+    SafeErc20(safe_erc20::Error),
+}
+
+/// State of a [`FlashLender`] Contract.
+#[storage]
+pub struct FlashLender {
+    /// Tokens that can be flash loaned.
+    pub(crate) supported_tokens: StorageMap<Address, StorageBool>,
+    /// Fee applied to flash loans, in basis points.
+    pub(crate) fee: StorageU256,
+    /// [`SafeErc20`] contract.
+    safe_erc20: SafeErc20,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for FlashLender {}
+
+#[public]
+impl FlashLender {
+    /// Returns the maximum amount of `token` available for loan, which is the
+    /// lender's balance of a supported token and zero otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token` - The address of the token that is requested.
+    pub fn max_flash_loan(&self, token: Address) -> U256 {
+        if !self.supported_tokens.get(token) {
+            return U256::ZERO;
+        }
+
+        IErc20Solidity::new(token)
+            .balance_of(self, contract::address())
+            .unwrap_or_default()
+    }
+
+    /// Returns the fee applied when flash loaning `value` of `token`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token` - The token to be flash loaned.
+    /// * `value` - The amount of tokens to be loaned.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnsupportedToken`] - If the token is not supported.
+    pub fn flash_fee(
+        &self,
+        token: Address,
+        value: U256,
+    ) -> Result<U256, Error> {
+        if !self.supported_tokens.get(token) {
+            return Err(Error::UnsupportedToken(ERC3156UnsupportedToken {
+                token,
+            }));
+        }
+
+        Ok(self._flash_fee(value))
+    }
+
+    /// Performs a flash loan.
+    ///
+    /// `value` tokens are transferred to the `receiver`, who is required to
+    /// implement the [`IERC3156FlashBorrower`] interface. By the end of the
+    /// flash loan, the receiver is expected to own `value + fee` tokens and
+    /// have them approved to the lender, which pulls them back.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `receiver` - The receiver of the flash loan. Should implement the
+    ///   [`IERC3156FlashBorrower::on_flash_loan`] interface.
+    /// * `token` - The token to be flash loaned.
+    /// * `value` - The amount of tokens to be loaned.
+    /// * `data` - Arbitrary data that is passed to the receiver.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnsupportedToken`] - If `token` is not supported.
+    /// * [`Error::ExceededMaxLoan`] - If the `value` is greater than the value
+    ///   returned by [`FlashLender::max_flash_loan`].
+    /// * [`Error::InvalidReceiver`] - If the `receiver` address is not a
+    ///   contract, the contract fails to execute the call, or the receiver does
+    ///   not return [`BORROWER_CALLBACK_VALUE`].
+    /// * [`Error::SafeErc20`] - If transferring the loan or pulling back the
+    ///   loan and the fee fails.
+    ///
+    /// # Panics
+    ///
+    /// * If the sum of the loan value and fee exceeds `U256::MAX`.
+    pub fn flash_loan(
+        &mut self,
+        receiver: Address,
+        token: Address,
+        value: U256,
+        data: Bytes,
+    ) -> Result<bool, Error> {
+        let fee = self.flash_fee(token, value)?;
+
+        let max_loan = self.max_flash_loan(token);
+        if value > max_loan {
+            return Err(Error::ExceededMaxLoan(ERC3156ExceededMaxLoan {
+                max_loan,
+            }));
+        }
+
+        if !Address::has_code(&receiver) {
+            return Err(Error::InvalidReceiver(ERC3156InvalidReceiver {
+                receiver,
+            }));
+        }
+
+        self.safe_erc20.safe_transfer(token, receiver, value)?;
+
+        let loan_receiver = IERC3156FlashBorrower::new(receiver);
+        let loan_return = loan_receiver
+            .on_flash_loan(
+                Call::new_in(self),
+                msg::sender(),
+                token,
+                value,
+                fee,
+                data.0.into(),
+            )
+            .map_err(|_| {
+                Error::InvalidReceiver(ERC3156InvalidReceiver { receiver })
+            })?;
+        if loan_return != BORROWER_CALLBACK_VALUE {
+            return Err(Error::InvalidReceiver(ERC3156InvalidReceiver {
+                receiver,
+            }));
+        }
+
+        let repayment = value
+            .checked_add(fee)
+            .expect("repayment should not exceed `U256::MAX`");
+        self.safe_erc20.safe_transfer_from(
+            token,
+            receiver,
+            contract::address(),
+            repayment,
+        )?;
+
+        Ok(true)
+    }
+}
+
+impl FlashLender {
+    /// Sets whether `token` can be flash loaned.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token` - Address of the ERC-20 token.
+    /// * `supported` - Whether the token can be flash loaned.
+    pub fn _set_supported_token(&mut self, token: Address, supported: bool) {
+        self.supported_tokens.setter(token).set(supported);
+    }
+
+    /// Sets the fee applied to flash loans, in basis points.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `fee` - Fee in basis points of the loan.
+    pub fn _set_flash_fee(&mut self, fee: U256) {
+        self.fee.set(fee);
+    }
+
+    /// Returns the fee for a loan of `value` tokens, rounded down.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `value` - The amount of tokens to be loaned.
+    fn _flash_fee(&self, value: U256) -> U256 {
+        value.mul_div(self.fee.get(), FEE_DENOMINATOR, Rounding::Floor)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, B256, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::{
+        abi::Bytes,
+        msg,
+        prelude::*,
+        storage::{StorageAddress, StorageU256},
+    };
+
+    use super::{
+        ERC3156ExceededMaxLoan, ERC3156InvalidReceiver,
+        ERC3156UnsupportedToken, Error, FlashLender,
+    };
+    use crate::token::erc20::{
+        utils::{
+            flash_borrower::{self, IErc3156FlashBorrower},
+            ISafeErc20, SafeErc20,
+        },
+        Erc20, IErc20,
+    };
+
+    #[storage]
+    struct TestBorrower {
+        lender: StorageAddress,
+        balance_during_loan: StorageU256,
+        safe_erc20: SafeErc20,
+    }
+
+    impl IErc3156FlashBorrower for TestBorrower {
+        type Error = flash_borrower::Error;
+
+        fn _is_trusted_lender(&self, lender: Address) -> bool {
+            lender == self.lender.get()
+        }
+
+        fn _is_trusted_initiator(&self, _initiator: Address) -> bool {
+            true
+        }
+
+        fn _on_flash_loan(
+            &mut self,
+            _initiator: Address,
+            token: Address,
+            amount: U256,
+            fee: U256,
+            _data: &[u8],
+        ) -> Result<(), Self::Error> {
+            let balance = crate::token::erc20::utils::IErc20::new(token)
+                .balance_of(&*self, stylus_sdk::contract::address())
+                .expect("should read balance");
+            self.balance_during_loan.set(balance);
+            self.safe_erc20
+                .force_approve(token, msg::sender(), amount + fee)
+                .expect("should approve repayment");
+            Ok(())
+        }
+    }
+
+    #[public]
+    impl TestBorrower {
+        fn on_flash_loan(
+            &mut self,
+            initiator: Address,
+            token: Address,
+            amount: U256,
+            fee: U256,
+            data: Bytes,
+        ) -> Result<B256, flash_borrower::Error> {
+            IErc3156FlashBorrower::on_flash_loan(
+                self, initiator, token, amount, fee, data,
+            )
+        }
+    }
+
+    unsafe impl TopLevelStorage for TestBorrower {}
+
+    const LENDER_BALANCE: U256 = uint!(100_000_U256);
+
+    fn setup(
+        lender: &Contract<FlashLender>,
+        token: &Contract<Erc20>,
+        borrower: &Contract<TestBorrower>,
+        alice: Address,
+        fee: U256,
+    ) {
+        let token_addr = token.address();
+        lender.init(alice, |lender| {
+            lender._set_supported_token(token_addr, true);
+            lender._set_flash_fee(fee);
+        });
+        let lender_addr = lender.address();
+        token.init(alice, |token| {
+            token
+                ._mint(lender_addr, LENDER_BALANCE)
+                .expect("should mint tokens for the lender");
+        });
+        borrower.init(alice, |borrower| borrower.lender.set(lender_addr));
+    }
+
+    #[motsu::test]
+    fn max_flash_loan_is_lender_balance(
+        lender: Contract<FlashLender>,
+        token: Contract<Erc20>,
+        borrower: Contract<TestBorrower>,
+        alice: Address,
+    ) {
+        setup(&lender, &token, &borrower, alice, U256::ZERO);
+
+        assert_eq!(
+            lender.sender(alice).max_flash_loan(token.address()),
+            LENDER_BALANCE
+        );
+        assert_eq!(lender.sender(alice).max_flash_loan(alice), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn flash_fee_is_rounded_down(
+        lender: Contract<FlashLender>,
+        token: Contract<Erc20>,
+        borrower: Contract<TestBorrower>,
+        alice: Address,
+    ) {
+        // 0.5%
+        setup(&lender, &token, &borrower, alice, uint!(50_U256));
+
+        let fee = lender
+            .sender(alice)
+            .flash_fee(token.address(), uint!(1999_U256))
+            .expect("should return the flash fee");
+        assert_eq!(fee, uint!(9_U256));
+
+        let err = lender
+            .sender(alice)
+            .flash_fee(alice, uint!(1999_U256))
+            .expect_err("should return Error::UnsupportedToken");
+        assert!(matches!(
+            err,
+            Error::UnsupportedToken(ERC3156UnsupportedToken { token })
+                if token == alice
+        ));
+    }
+
+    #[motsu::test]
+    fn flash_loan_lends_and_collects_fee(
+        lender: Contract<FlashLender>,
+        token: Contract<Erc20>,
+        borrower: Contract<TestBorrower>,
+        alice: Address,
+    ) {
+        // 1%
+        setup(&lender, &token, &borrower, alice, uint!(100_U256));
+        let fee = uint!(10_U256);
+        let borrower_addr = borrower.address();
+        token.init(alice, |token| {
+            token._mint(borrower_addr, fee).expect("should mint the fee");
+        });
+
+        let value = uint!(1000_U256);
+        let result = lender
+            .sender(alice)
+            .flash_loan(
+                borrower.address(),
+                token.address(),
+                value,
+                vec![].into(),
+            )
+            .expect("should perform the flash loan");
+        assert!(result);
+
+        assert_eq!(
+            borrower.sender(alice).balance_during_loan.get(),
+            value + fee
+        );
+        assert_eq!(
+            token.sender(alice).balance_of(lender.address()),
+            LENDER_BALANCE + fee
+        );
+        assert_eq!(
+            token.sender(alice).balance_of(borrower.address()),
+            U256::ZERO
+        );
+    }
+
+    #[motsu::test]
+    fn flash_loan_reverts_when_exceeded_max_loan(
+        lender: Contract<FlashLender>,
+        token: Contract<Erc20>,
+        borrower: Contract<TestBorrower>,
+        alice: Address,
+    ) {
+        setup(&lender, &token, &borrower, alice, U256::ZERO);
+
+        let err = lender
+            .sender(alice)
+            .flash_loan(
+                borrower.address(),
+                token.address(),
+                LENDER_BALANCE + uint!(1_U256),
+                vec![].into(),
+            )
+            .expect_err("should return Error::ExceededMaxLoan");
+        assert!(matches!(
+            err,
+            Error::ExceededMaxLoan(ERC3156ExceededMaxLoan { max_loan })
+                if max_loan == LENDER_BALANCE
+        ));
+    }
+
+    #[motsu::test]
+    fn flash_loan_reverts_when_untrusted_lender(
+        lender: Contract<FlashLender>,
+        token: Contract<Erc20>,
+        borrower: Contract<TestBorrower>,
+        alice: Address,
+    ) {
+        setup(&lender, &token, &borrower, alice, U256::ZERO);
+        borrower.init(alice, |borrower| borrower.lender.set(alice));
+
+        let err = lender
+            .sender(alice)
+            .flash_loan(
+                borrower.address(),
+                token.address(),
+                uint!(1000_U256),
+                vec![].into(),
+            )
+            .expect_err("should return Error::InvalidReceiver");
+        assert!(matches!(
+            err,
+            Error::InvalidReceiver(ERC3156InvalidReceiver { receiver })
+                if receiver == borrower.address()
+        ));
+    }
+
+    #[motsu::test]
+    fn flash_loan_reverts_when_receiver_is_not_a_contract(
+        lender: Contract<FlashLender>,
+        token: Contract<Erc20>,
+        borrower: Contract<TestBorrower>,
+        alice: Address,
+    ) {
+        setup(&lender, &token, &borrower, alice, U256::ZERO);
+
+        let err = lender
+            .sender(alice)
+            .flash_loan(alice, token.address(), uint!(1000_U256), vec![].into())
+            .expect_err("should return Error::InvalidReceiver");
+        assert!(matches!(
+            err,
+            Error::InvalidReceiver(ERC3156InvalidReceiver { receiver })
+                if receiver == alice
+        ));
+    }
+}
@@ -1,6 +1,12 @@
 //! Utilities for the ERC-20 standard.
+pub mod erc4626_router;
+pub mod flash_borrower;
+pub mod flash_lender;
 pub mod safe_erc20;
 
+pub use erc4626_router::Erc4626Router;
+pub use flash_borrower::IErc3156FlashBorrower;
+pub use flash_lender::FlashLender;
 pub use safe_erc20::{ISafeErc20, SafeErc20};
 pub use token::*;
 mod token {
@@ -0,0 +1,170 @@
+//! Optional Content URI extension of the ERC-6909 standard, as defined in the
+//! [ERC].
+//!
+//! [ERC]: https://eips.ethereum.org/EIPS/eip-6909#content-uri-extension
+
+use alloc::{string::String, vec::Vec};
+
+use alloy_primitives::{FixedBytes, U256};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    evm,
+    prelude::{public, storage},
+    storage::{StorageMap, StorageString},
+};
+
+use crate::utils::introspection::erc165::{Erc165, IErc165};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the contract URI is changed.
+        #[allow(missing_docs)]
+        event ContractURIUpdated();
+
+        /// Emitted when the URI for token type `id` changes to `value`.
+        #[allow(missing_docs)]
+        event URI(string value, uint256 indexed id);
+    }
+}
+
+/// State of an [`Erc6909ContentUri`] contract.
+#[storage]
+pub struct Erc6909ContentUri {
+    /// URI of the contract's metadata.
+    pub(crate) contract_uri: StorageString,
+    /// Maps token ids to their URI.
+    pub(crate) token_uris: StorageMap<U256, StorageString>,
+}
+
+/// Interface for the optional content URI functions from the ERC-6909
+/// standard.
+#[interface_id]
+pub trait IErc6909ContentUri {
+    /// Returns the URI of the contract's metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[selector(name = "contractURI")]
+    fn contract_uri(&self) -> String;
+
+    /// Returns the URI of the metadata of the token of type `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Token id as a number.
+    #[selector(name = "tokenURI")]
+    fn token_uri(&self, id: U256) -> String;
+}
+
+#[public]
+impl IErc6909ContentUri for Erc6909ContentUri {
+    #[selector(name = "contractURI")]
+    fn contract_uri(&self) -> String {
+        self.contract_uri.get_string()
+    }
+
+    #[selector(name = "tokenURI")]
+    fn token_uri(&self, id: U256) -> String {
+        self.token_uris.getter(id).get_string()
+    }
+}
+
+impl Erc6909ContentUri {
+    /// Sets the URI of the contract's metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `contract_uri` - New URI of the contract's metadata.
+    ///
+    /// # Events
+    ///
+    /// * [`ContractURIUpdated`].
+    pub fn _set_contract_uri(&mut self, contract_uri: String) {
+        self.contract_uri.set_str(contract_uri);
+        evm::log(ContractURIUpdated {});
+    }
+
+    /// Sets the URI of the metadata of the token of type `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `id` - Token id as a number.
+    /// * `token_uri` - New URI of the token's metadata.
+    ///
+    /// # Events
+    ///
+    /// * [`URI`].
+    pub fn _set_token_uri(&mut self, id: U256, token_uri: String) {
+        self.token_uris.setter(id).set_str(&token_uri);
+        evm::log(URI { value: token_uri, id });
+    }
+}
+
+impl IErc165 for Erc6909ContentUri {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IErc6909ContentUri>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::TopLevelStorage;
+
+    use super::{Erc6909ContentUri, IErc165, IErc6909ContentUri};
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    unsafe impl TopLevelStorage for Erc6909ContentUri {}
+
+    #[motsu::test]
+    fn sets_contract_uri(
+        contract: Contract<Erc6909ContentUri>,
+        alice: Address,
+    ) {
+        assert_eq!("", contract.sender(alice).contract_uri());
+
+        let uri = "https://example.com/contract.json";
+        contract.init(alice, |contract| {
+            contract._set_contract_uri(uri.into());
+        });
+
+        assert_eq!(uri, contract.sender(alice).contract_uri());
+    }
+
+    #[motsu::test]
+    fn sets_token_uri_per_token_id(
+        contract: Contract<Erc6909ContentUri>,
+        alice: Address,
+    ) {
+        let uri = "https://example.com/1.json";
+        contract.init(alice, |contract| {
+            contract._set_token_uri(TOKEN_ID, uri.into());
+        });
+
+        assert_eq!(uri, contract.sender(alice).token_uri(TOKEN_ID));
+        assert_eq!("", contract.sender(alice).token_uri(uint!(2_U256)));
+    }
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <Erc6909ContentUri as IErc6909ContentUri>::INTERFACE_ID;
+        let expected = 0x20d8_8258;
+        assert_eq!(actual, expected);
+
+        let actual = <Erc6909ContentUri as IErc165>::INTERFACE_ID;
+        let expected = 0x01ff_c9a7;
+        assert_eq!(actual, expected);
+    }
+}
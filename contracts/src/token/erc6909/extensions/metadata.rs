@@ -0,0 +1,209 @@
+//! Optional Metadata of the ERC-6909 standard, as defined in the [ERC].
+//!
+//! Unlike ERC-20, every token id has its own name, symbol and decimals, all
+//! of which default to empty values until set.
+//!
+//! [ERC]: https://eips.ethereum.org/EIPS/eip-6909#metadata-extension
+
+use alloc::{string::String, vec::Vec};
+
+use alloy_primitives::{FixedBytes, U256, U8};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    evm,
+    prelude::{public, storage},
+    storage::{StorageMap, StorageString, StorageU8},
+};
+
+use crate::utils::introspection::erc165::{Erc165, IErc165};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the name of token type `id` is updated to
+        /// `new_name`.
+        #[allow(missing_docs)]
+        event ERC6909NameUpdated(uint256 indexed id, string new_name);
+
+        /// Emitted when the symbol of token type `id` is updated to
+        /// `new_symbol`.
+        #[allow(missing_docs)]
+        event ERC6909SymbolUpdated(uint256 indexed id, string new_symbol);
+
+        /// Emitted when the decimals of token type `id` are updated to
+        /// `new_decimals`.
+        #[allow(missing_docs)]
+        event ERC6909DecimalsUpdated(uint256 indexed id, uint8 new_decimals);
+    }
+}
+
+/// State of an [`Erc6909Metadata`] contract.
+#[storage]
+pub struct Erc6909Metadata {
+    /// Maps token ids to their name.
+    pub(crate) names: StorageMap<U256, StorageString>,
+    /// Maps token ids to their symbol.
+    pub(crate) symbols: StorageMap<U256, StorageString>,
+    /// Maps token ids to their decimals.
+    pub(crate) decimals: StorageMap<U256, StorageU8>,
+}
+
+/// Interface for the optional metadata functions from the ERC-6909 standard.
+#[interface_id]
+pub trait IErc6909Metadata {
+    /// Returns the name of the token of type `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Token id as a number.
+    fn name(&self, id: U256) -> String;
+
+    /// Returns the symbol of the token of type `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Token id as a number.
+    fn symbol(&self, id: U256) -> String;
+
+    /// Returns the number of decimals used to get the user representation of
+    /// the token of type `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Token id as a number.
+    fn decimals(&self, id: U256) -> u8;
+}
+
+#[public]
+impl IErc6909Metadata for Erc6909Metadata {
+    fn name(&self, id: U256) -> String {
+        self.names.getter(id).get_string()
+    }
+
+    fn symbol(&self, id: U256) -> String {
+        self.symbols.getter(id).get_string()
+    }
+
+    fn decimals(&self, id: U256) -> u8 {
+        self.decimals.get(id).to::<u8>()
+    }
+}
+
+impl Erc6909Metadata {
+    /// Sets the `name` of the token of type `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `id` - Token id as a number.
+    /// * `name` - New name of the token.
+    ///
+    /// # Events
+    ///
+    /// * [`ERC6909NameUpdated`].
+    pub fn _set_name(&mut self, id: U256, name: String) {
+        self.names.setter(id).set_str(&name);
+        evm::log(ERC6909NameUpdated { id, new_name: name });
+    }
+
+    /// Sets the `symbol` of the token of type `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `id` - Token id as a number.
+    /// * `symbol` - New symbol of the token.
+    ///
+    /// # Events
+    ///
+    /// * [`ERC6909SymbolUpdated`].
+    pub fn _set_symbol(&mut self, id: U256, symbol: String) {
+        self.symbols.setter(id).set_str(&symbol);
+        evm::log(ERC6909SymbolUpdated { id, new_symbol: symbol });
+    }
+
+    /// Sets the `decimals` of the token of type `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `id` - Token id as a number.
+    /// * `decimals` - New number of decimals of the token.
+    ///
+    /// # Events
+    ///
+    /// * [`ERC6909DecimalsUpdated`].
+    pub fn _set_decimals(&mut self, id: U256, decimals: u8) {
+        self.decimals.setter(id).set(U8::from(decimals));
+        evm::log(ERC6909DecimalsUpdated { id, new_decimals: decimals });
+    }
+}
+
+impl IErc165 for Erc6909Metadata {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IErc6909Metadata>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::TopLevelStorage;
+
+    use super::{Erc6909Metadata, IErc165, IErc6909Metadata};
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    unsafe impl TopLevelStorage for Erc6909Metadata {}
+
+    #[motsu::test]
+    fn defaults_to_empty_metadata(
+        contract: Contract<Erc6909Metadata>,
+        alice: Address,
+    ) {
+        assert_eq!("", contract.sender(alice).name(TOKEN_ID));
+        assert_eq!("", contract.sender(alice).symbol(TOKEN_ID));
+        assert_eq!(0, contract.sender(alice).decimals(TOKEN_ID));
+    }
+
+    #[motsu::test]
+    fn sets_metadata_per_token_id(
+        contract: Contract<Erc6909Metadata>,
+        alice: Address,
+    ) {
+        contract.init(alice, |contract| {
+            contract._set_name(TOKEN_ID, "LP Position".into());
+            contract._set_symbol(TOKEN_ID, "LP".into());
+            contract._set_decimals(TOKEN_ID, 18);
+        });
+
+        assert_eq!("LP Position", contract.sender(alice).name(TOKEN_ID));
+        assert_eq!("LP", contract.sender(alice).symbol(TOKEN_ID));
+        assert_eq!(18, contract.sender(alice).decimals(TOKEN_ID));
+
+        let other_id = uint!(2_U256);
+        assert_eq!("", contract.sender(alice).name(other_id));
+        assert_eq!("", contract.sender(alice).symbol(other_id));
+        assert_eq!(0, contract.sender(alice).decimals(other_id));
+    }
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <Erc6909Metadata as IErc6909Metadata>::INTERFACE_ID;
+        let expected = 0x71ab_c795;
+        assert_eq!(actual, expected);
+
+        let actual = <Erc6909Metadata as IErc165>::INTERFACE_ID;
+        let expected = 0x01ff_c9a7;
+        assert_eq!(actual, expected);
+    }
+}
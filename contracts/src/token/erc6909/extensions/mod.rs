@@ -0,0 +1,8 @@
+//! Common extensions to the ERC-6909 standard.
+pub mod content_uri;
+pub mod metadata;
+pub mod token_supply;
+
+pub use content_uri::{Erc6909ContentUri, IErc6909ContentUri};
+pub use metadata::{Erc6909Metadata, IErc6909Metadata};
+pub use token_supply::{Erc6909TokenSupply, IErc6909TokenSupply};
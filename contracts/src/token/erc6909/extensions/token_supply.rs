@@ -0,0 +1,345 @@
+//! Optional Token Supply extension of the ERC-6909 standard, as defined in
+//! the [ERC], that tracks the total supply of each token id.
+//!
+//! NOTE: This contract implies a global limit of 2**256 - 1 to the number of
+//! tokens of each type that can be minted.
+//!
+//! CAUTION: This extension should not be added in an upgrade to an already
+//! deployed contract.
+//!
+//! [ERC]: https://eips.ethereum.org/EIPS/eip-6909#token-supply-extension
+
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use openzeppelin_stylus_proc::interface_id;
+use stylus_sdk::{
+    msg,
+    prelude::{public, storage},
+    storage::{StorageMap, StorageU256},
+};
+
+use crate::{
+    token::erc6909::{self, Erc6909, IErc6909},
+    utils::{
+        introspection::erc165::IErc165,
+        math::storage::{AddAssignChecked, SubAssignUnchecked},
+    },
+};
+
+/// State of an [`Erc6909TokenSupply`] contract.
+#[storage]
+pub struct Erc6909TokenSupply {
+    /// [`Erc6909`] contract.
+    pub erc6909: Erc6909,
+    /// Mapping from token id to total supply.
+    pub(crate) total_supplies: StorageMap<U256, StorageU256>,
+}
+
+impl Deref for Erc6909TokenSupply {
+    type Target = Erc6909;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc6909
+    }
+}
+
+impl DerefMut for Erc6909TokenSupply {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc6909
+    }
+}
+
+/// Required interface of an [`Erc6909TokenSupply`] contract.
+#[interface_id]
+pub trait IErc6909TokenSupply {
+    /// Returns the total supply of the token of type `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Token id as a number.
+    fn total_supply(&self, id: U256) -> U256;
+}
+
+impl IErc6909TokenSupply for Erc6909TokenSupply {
+    fn total_supply(&self, id: U256) -> U256 {
+        self.total_supplies.get(id)
+    }
+}
+
+#[public]
+impl IErc6909 for Erc6909TokenSupply {
+    type Error = erc6909::Error;
+
+    fn balance_of(&self, owner: Address, id: U256) -> U256 {
+        self.erc6909.balance_of(owner, id)
+    }
+
+    fn allowance(&self, owner: Address, spender: Address, id: U256) -> U256 {
+        self.erc6909.allowance(owner, spender, id)
+    }
+
+    fn is_operator(&self, owner: Address, spender: Address) -> bool {
+        self.erc6909.is_operator(owner, spender)
+    }
+
+    fn approve(
+        &mut self,
+        spender: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<bool, erc6909::Error> {
+        self.erc6909.approve(spender, id, amount)
+    }
+
+    fn set_operator(
+        &mut self,
+        spender: Address,
+        approved: bool,
+    ) -> Result<bool, erc6909::Error> {
+        self.erc6909.set_operator(spender, approved)
+    }
+
+    fn transfer(
+        &mut self,
+        receiver: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<bool, erc6909::Error> {
+        self._transfer(msg::sender(), receiver, id, amount)?;
+        Ok(true)
+    }
+
+    fn transfer_from(
+        &mut self,
+        sender: Address,
+        receiver: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<bool, erc6909::Error> {
+        self.erc6909.authorize_transfer(sender, id, amount)?;
+        self._transfer(sender, receiver, id, amount)?;
+        Ok(true)
+    }
+}
+
+impl IErc165 for Erc6909TokenSupply {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IErc6909TokenSupply>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || Erc6909::supports_interface(interface_id)
+    }
+}
+
+impl Erc6909TokenSupply {
+    /// Creates `amount` of tokens of type `id` and assigns them to `to`.
+    ///
+    /// Re-export of [`Erc6909::_mint`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint(
+        &mut self,
+        to: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), erc6909::Error> {
+        Erc6909::require_receiver(to)?;
+        self._update(Address::ZERO, to, id, amount)
+    }
+
+    /// Moves `amount` of tokens of type `id` from `from` to `to` without
+    /// checking for approvals.
+    ///
+    /// Re-export of [`Erc6909::_transfer`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), erc6909::Error> {
+        Erc6909::require_sender(from)?;
+        Erc6909::require_receiver(to)?;
+        self._update(from, to, id, amount)
+    }
+
+    /// Destroys `amount` of tokens of type `id` from `from`.
+    ///
+    /// Re-export of [`Erc6909::_burn`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(
+        &mut self,
+        from: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), erc6909::Error> {
+        Erc6909::require_sender(from)?;
+        self._update(from, Address::ZERO, id, amount)
+    }
+}
+
+impl Erc6909TokenSupply {
+    /// Extended version of [`Erc6909::_update`] that updates the supply of
+    /// tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account of the sender.
+    /// * `to` - Account of the recipient.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens to be transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc6909::Error::InsufficientBalance`] - If `amount` is greater than
+    ///   the balance of the `from` account.
+    ///
+    /// # Events
+    ///
+    /// * [`erc6909::Transfer`].
+    ///
+    /// # Panics
+    ///
+    /// * If updated balance and/or supply exceeds `U256::MAX`, may happen
+    ///   during the `mint` operation.
+    fn _update(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), erc6909::Error> {
+        self.erc6909._update(from, to, id, amount)?;
+
+        if from.is_zero() {
+            self.total_supplies.setter(id).add_assign_checked(
+                amount,
+                "should not exceed `U256::MAX` for `total_supplies`",
+            );
+        }
+
+        if to.is_zero() {
+            // SAFETY: Overflow not possible:
+            // amount <= balance_of(from, id) <= total_supply(id).
+            self.total_supplies.setter(id).sub_assign_unchecked(amount);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::TopLevelStorage;
+
+    use super::{Erc6909TokenSupply, IErc6909TokenSupply};
+    use crate::{
+        token::erc6909::{self, IErc6909},
+        utils::introspection::erc165::IErc165,
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+    const AMOUNT: U256 = uint!(100_U256);
+
+    unsafe impl TopLevelStorage for Erc6909TokenSupply {}
+
+    #[motsu::test]
+    fn mint_increases_supply(
+        contract: Contract<Erc6909TokenSupply>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+        contract.sender(alice)._mint(bob, TOKEN_ID, AMOUNT).unwrap();
+
+        assert_eq!(
+            AMOUNT + AMOUNT,
+            contract.sender(alice).total_supply(TOKEN_ID)
+        );
+        assert_eq!(U256::ZERO, contract.sender(alice).total_supply(U256::ZERO));
+    }
+
+    #[motsu::test]
+    fn burn_decreases_supply(
+        contract: Contract<Erc6909TokenSupply>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+        contract.sender(alice)._burn(alice, TOKEN_ID, uint!(40_U256)).unwrap();
+
+        assert_eq!(
+            uint!(60_U256),
+            contract.sender(alice).total_supply(TOKEN_ID)
+        );
+    }
+
+    #[motsu::test]
+    fn transfers_keep_supply(
+        contract: Contract<Erc6909TokenSupply>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+        contract.sender(alice).approve(bob, TOKEN_ID, AMOUNT).unwrap();
+
+        contract.sender(alice).transfer(bob, TOKEN_ID, uint!(30_U256)).unwrap();
+        contract
+            .sender(bob)
+            .transfer_from(alice, bob, TOKEN_ID, uint!(20_U256))
+            .unwrap();
+
+        assert_eq!(AMOUNT, contract.sender(alice).total_supply(TOKEN_ID));
+        assert_eq!(
+            uint!(50_U256),
+            contract.sender(alice).balance_of(bob, TOKEN_ID)
+        );
+    }
+
+    #[motsu::test]
+    fn failed_burn_keeps_supply(
+        contract: Contract<Erc6909TokenSupply>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+
+        let err = contract
+            .sender(alice)
+            ._burn(alice, TOKEN_ID, AMOUNT + uint!(1_U256))
+            .expect_err("should not burn more than the balance");
+
+        assert!(matches!(err, erc6909::Error::InsufficientBalance(_)));
+        assert_eq!(AMOUNT, contract.sender(alice).total_supply(TOKEN_ID));
+    }
+
+    #[motsu::test]
+    #[should_panic = "should not exceed `U256::MAX` for `balances`"]
+    fn mint_panics_on_overflow(
+        contract: Contract<Erc6909TokenSupply>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, U256::MAX).unwrap();
+        _ = contract.sender(alice)._mint(alice, TOKEN_ID, uint!(1_U256));
+    }
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <Erc6909TokenSupply as IErc6909TokenSupply>::INTERFACE_ID;
+        let expected = 0xbd85_b039;
+        assert_eq!(actual, expected);
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc6909TokenSupply::supports_interface(
+            <Erc6909TokenSupply as IErc6909TokenSupply>::INTERFACE_ID.into()
+        ));
+        assert!(Erc6909TokenSupply::supports_interface(
+            <Erc6909TokenSupply as IErc6909>::INTERFACE_ID.into()
+        ));
+    }
+}
@@ -0,0 +1,1083 @@
+//! Implementation of the ERC-6909 minimal multi-token standard, as defined in
+//! the [ERC].
+//!
+//! Unlike ERC-1155, transfers do not call back into the recipient, and
+//! approvals can be granted either per token id with an allowance or for all
+//! token ids with an operator.
+//!
+//! [ERC]: https://eips.ethereum.org/EIPS/eip-6909
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    call::MethodError,
+    evm, msg,
+    prelude::{public, storage, SolidityError},
+    storage::{StorageBool, StorageMap, StorageU256},
+};
+
+use crate::utils::{
+    introspection::erc165::{Erc165, IErc165},
+    math::storage::{AddAssignChecked, SubAssignUnchecked},
+};
+
+pub mod extensions;
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when `amount` tokens of type `id` are moved from `sender`
+        /// to `receiver` by `caller`.
+        #[allow(missing_docs)]
+        event Transfer(
+            address caller,
+            address indexed sender,
+            address indexed receiver,
+            uint256 indexed id,
+            uint256 amount
+        );
+
+        /// Emitted when `owner` grants or revokes permission to `spender` to
+        /// transfer all of their tokens, according to `approved`.
+        #[allow(missing_docs)]
+        event OperatorSet(
+            address indexed owner,
+            address indexed spender,
+            bool approved
+        );
+
+        /// Emitted when the allowance of `spender` for tokens of type `id`
+        /// owned by `owner` is set to `amount`.
+        #[allow(missing_docs)]
+        event Approval(
+            address indexed owner,
+            address indexed spender,
+            uint256 indexed id,
+            uint256 amount
+        );
+    }
+
+    sol! {
+        /// Indicates an error related to the current `balance` of a `sender`.
+        /// Used in transfers.
+        ///
+        /// * `sender` - Address whose tokens are being transferred.
+        /// * `balance` - Current balance for the interacting account.
+        /// * `needed` - Minimum amount required to perform a transfer.
+        /// * `id` - Identifier number of a token.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC6909InsufficientBalance(
+            address sender,
+            uint256 balance,
+            uint256 needed,
+            uint256 id
+        );
+
+        /// Indicates a failure with the `spender`’s `allowance`.
+        /// Used in transfers.
+        ///
+        /// * `spender` - Address that may be allowed to operate on tokens
+        ///   without being their owner.
+        /// * `allowance` - Amount of tokens a `spender` is allowed to operate
+        ///   with.
+        /// * `needed` - Minimum amount required to perform a transfer.
+        /// * `id` - Identifier number of a token.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC6909InsufficientAllowance(
+            address spender,
+            uint256 allowance,
+            uint256 needed,
+            uint256 id
+        );
+
+        /// Indicates a failure with the `approver` of a token to be approved.
+        /// Used in approvals.
+        ///
+        /// * `approver` - Address initiating an approval operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC6909InvalidApprover(address approver);
+
+        /// Indicates a failure with the token `receiver`.
+        /// Used in transfers.
+        ///
+        /// * `receiver` - Address to which tokens are being transferred.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC6909InvalidReceiver(address receiver);
+
+        /// Indicates a failure with the token `sender`.
+        /// Used in transfers.
+        ///
+        /// * `sender` - Address whose tokens are being transferred.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC6909InvalidSender(address sender);
+
+        /// Indicates a failure with the `spender` to be approved.
+        /// Used in approvals.
+        ///
+        /// * `spender` - Address that may be allowed to operate on tokens
+        ///   without being their owner.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC6909InvalidSpender(address spender);
+    }
+}
+
+/// An [`Erc6909`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates an error related to the current `balance` of `sender`.
+    /// Used in transfers.
+    InsufficientBalance(ERC6909InsufficientBalance),
+    /// Indicates a failure with the `spender`’s `allowance`. Used in
+    /// transfers.
+    InsufficientAllowance(ERC6909InsufficientAllowance),
+    /// Indicates a failure with the `approver` of a token to be approved.
+    /// Used in approvals.
+    InvalidApprover(ERC6909InvalidApprover),
+    /// Indicates a failure with the token `receiver`. Used in transfers.
+    InvalidReceiver(ERC6909InvalidReceiver),
+    /// Indicates a failure with the token `sender`. Used in transfers.
+    InvalidSender(ERC6909InvalidSender),
+    /// Indicates a failure with the `spender` to be approved. Used in
+    /// approvals.
+    InvalidSpender(ERC6909InvalidSpender),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`Erc6909`] token.
+#[storage]
+pub struct Erc6909 {
+    /// Maps owners to their balance of each token id.
+    pub(crate) balances: StorageMap<Address, StorageMap<U256, StorageU256>>,
+    /// Maps owners to a mapping of operator approvals.
+    pub(crate) operator_approvals:
+        StorageMap<Address, StorageMap<Address, StorageBool>>,
+    /// Maps owners to a mapping of spender allowances per token id.
+    pub(crate) allowances:
+        StorageMap<Address, StorageMap<Address, StorageMap<U256, StorageU256>>>,
+}
+
+/// Required interface of an [`Erc6909`] compliant contract.
+#[interface_id]
+pub trait IErc6909 {
+    /// The error type associated to this ERC-6909 trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Returns the amount of tokens of type `id` owned by `owner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account of the token's owner.
+    /// * `id` - Token id as a number.
+    fn balance_of(&self, owner: Address, id: U256) -> U256;
+
+    /// Returns the amount of tokens of type `id` that `spender` is allowed to
+    /// spend on behalf of `owner`.
+    ///
+    /// NOTE: Does not include operator allowances.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account of the token's owner.
+    /// * `spender` - Account that may spend the tokens.
+    /// * `id` - Token id as a number.
+    fn allowance(&self, owner: Address, spender: Address, id: U256) -> U256;
+
+    /// Returns true if `spender` is set as an operator for `owner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account of the token's owner.
+    /// * `spender` - Account to be checked.
+    fn is_operator(&self, owner: Address, spender: Address) -> bool;
+
+    /// Sets an `amount` of tokens of type `id` that `spender` is allowed to
+    /// spend on behalf of the caller. Overwrites any existing allowance.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `spender` - Account that will spend the tokens.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens `spender` is allowed to spend.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidSpender`] - If `spender` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`Approval`].
+    fn approve(
+        &mut self,
+        spender: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<bool, Self::Error>;
+
+    /// Grants or revokes unlimited transfer permission of any token id to
+    /// `spender` for the caller's tokens.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `spender` - Account to add to or remove from the set of operators.
+    /// * `approved` - Flag that determines whether or not permission will be
+    ///   granted to `spender`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidSpender`] - If `spender` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`OperatorSet`].
+    fn set_operator(
+        &mut self,
+        spender: Address,
+        approved: bool,
+    ) -> Result<bool, Self::Error>;
+
+    /// Transfers `amount` of tokens of type `id` from the caller's account to
+    /// `receiver`.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `receiver` - Account of the recipient.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens to be transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidReceiver`] - If `receiver` is `Address::ZERO`.
+    /// * [`Error::InsufficientBalance`] - If the caller doesn't have a balance
+    ///   of at least `amount` tokens of type `id`.
+    ///
+    /// # Events
+    ///
+    /// * [`Transfer`].
+    fn transfer(
+        &mut self,
+        receiver: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<bool, Self::Error>;
+
+    /// Transfers `amount` of tokens of type `id` from `sender` to `receiver`.
+    ///
+    /// The caller must be `sender`, an operator of `sender`, or have an
+    /// allowance of at least `amount` tokens of type `id`, which is then
+    /// consumed unless it is `U256::MAX`.
+    ///
+    /// Returns a boolean value indicating whether the operation succeeded.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `sender` - Account to transfer tokens from.
+    /// * `receiver` - Account of the recipient.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens to be transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientAllowance`] - If the caller is neither `sender`
+    ///   nor one of its operators, and its allowance is lower than `amount`.
+    /// * [`Error::InvalidSender`] - If `sender` is `Address::ZERO`.
+    /// * [`Error::InvalidReceiver`] - If `receiver` is `Address::ZERO`.
+    /// * [`Error::InsufficientBalance`] - If `sender` doesn't have a balance of
+    ///   at least `amount` tokens of type `id`.
+    ///
+    /// # Events
+    ///
+    /// * [`Transfer`].
+    fn transfer_from(
+        &mut self,
+        sender: Address,
+        receiver: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<bool, Self::Error>;
+}
+
+#[public]
+impl IErc6909 for Erc6909 {
+    type Error = Error;
+
+    fn balance_of(&self, owner: Address, id: U256) -> U256 {
+        self.balances.get(owner).get(id)
+    }
+
+    fn allowance(&self, owner: Address, spender: Address, id: U256) -> U256 {
+        self.allowances.get(owner).get(spender).get(id)
+    }
+
+    fn is_operator(&self, owner: Address, spender: Address) -> bool {
+        self.operator_approvals.get(owner).get(spender)
+    }
+
+    fn approve(
+        &mut self,
+        spender: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<bool, Self::Error> {
+        self._approve(msg::sender(), spender, id, amount)?;
+        Ok(true)
+    }
+
+    fn set_operator(
+        &mut self,
+        spender: Address,
+        approved: bool,
+    ) -> Result<bool, Self::Error> {
+        self._set_operator(msg::sender(), spender, approved)?;
+        Ok(true)
+    }
+
+    fn transfer(
+        &mut self,
+        receiver: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<bool, Self::Error> {
+        self._transfer(msg::sender(), receiver, id, amount)?;
+        Ok(true)
+    }
+
+    fn transfer_from(
+        &mut self,
+        sender: Address,
+        receiver: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<bool, Self::Error> {
+        self.authorize_transfer(sender, id, amount)?;
+        self._transfer(sender, receiver, id, amount)?;
+        Ok(true)
+    }
+}
+
+impl IErc165 for Erc6909 {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IErc6909>::INTERFACE_ID == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+impl Erc6909 {
+    /// Transfers `amount` of tokens of type `id` from `from` to `to`, or
+    /// alternatively mints (or burns) if `from` (or `to`) is the zero address.
+    /// All customizations to transfers, mints, and burns should be done by
+    /// overriding this function.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account to transfer tokens from.
+    /// * `to` - Account of the recipient.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens to be transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientBalance`] - If `amount` is greater than the
+    ///   balance of the `from` account.
+    ///
+    /// # Events
+    ///
+    /// * [`Transfer`].
+    ///
+    /// # Panics
+    ///
+    /// * If updated balance exceeds `U256::MAX`, may happen during `mint`
+    ///   operation.
+    fn _update(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), Error> {
+        if !from.is_zero() {
+            let from_balance = self.balance_of(from, id);
+            if from_balance < amount {
+                return Err(Error::InsufficientBalance(
+                    ERC6909InsufficientBalance {
+                        sender: from,
+                        balance: from_balance,
+                        needed: amount,
+                        id,
+                    },
+                ));
+            }
+            self.balances.setter(from).setter(id).sub_assign_unchecked(amount);
+        }
+
+        if !to.is_zero() {
+            self.balances.setter(to).setter(id).add_assign_checked(
+                amount,
+                "should not exceed `U256::MAX` for `balances`",
+            );
+        }
+
+        evm::log(Transfer {
+            caller: msg::sender(),
+            sender: from,
+            receiver: to,
+            id,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Creates `amount` of tokens of type `id` and assigns them to `to`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens to be minted.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`Transfer`].
+    ///
+    /// # Panics
+    ///
+    /// * If updated balance exceeds `U256::MAX`.
+    pub fn _mint(
+        &mut self,
+        to: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), Error> {
+        Self::require_receiver(to)?;
+        self._update(Address::ZERO, to, id, amount)
+    }
+
+    /// Moves `amount` of tokens of type `id` from `from` to `to` without
+    /// checking for approvals.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account to transfer tokens from.
+    /// * `to` - Account of the recipient.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens to be transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidSender`] - If `from` is `Address::ZERO`.
+    /// * [`Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    /// * [`Error::InsufficientBalance`] - If `amount` is greater than the
+    ///   balance of the `from` account.
+    ///
+    /// # Events
+    ///
+    /// * [`Transfer`].
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), Error> {
+        Self::require_sender(from)?;
+        Self::require_receiver(to)?;
+        self._update(from, to, id, amount)
+    }
+
+    /// Destroys `amount` of tokens of type `id` from `from`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account to burn tokens from.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens to be burnt.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidSender`] - If `from` is `Address::ZERO`.
+    /// * [`Error::InsufficientBalance`] - If `amount` is greater than the
+    ///   balance of the `from` account.
+    ///
+    /// # Events
+    ///
+    /// * [`Transfer`].
+    pub fn _burn(
+        &mut self,
+        from: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), Error> {
+        Self::require_sender(from)?;
+        self._update(from, Address::ZERO, id, amount)
+    }
+
+    /// Sets `amount` as the allowance of `spender` over the `owner`'s tokens
+    /// of type `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - Account of the token's owner.
+    /// * `spender` - Account that will spend the tokens.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens `spender` is allowed to spend.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidApprover`] - If `owner` is `Address::ZERO`.
+    /// * [`Error::InvalidSpender`] - If `spender` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`Approval`].
+    pub fn _approve(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), Error> {
+        Self::require_approver(owner)?;
+        Self::require_spender(spender)?;
+
+        self.allowances.setter(owner).setter(spender).insert(id, amount);
+        evm::log(Approval { owner, spender, id, amount });
+        Ok(())
+    }
+
+    /// Approves or revokes `spender` as an operator for `owner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - Account of the token's owner.
+    /// * `spender` - Account to add to or remove from the set of operators.
+    /// * `approved` - Flag that determines whether or not permission will be
+    ///   granted to `spender`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidApprover`] - If `owner` is `Address::ZERO`.
+    /// * [`Error::InvalidSpender`] - If `spender` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`OperatorSet`].
+    pub fn _set_operator(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        approved: bool,
+    ) -> Result<(), Error> {
+        Self::require_approver(owner)?;
+        Self::require_spender(spender)?;
+
+        self.operator_approvals.setter(owner).insert(spender, approved);
+        evm::log(OperatorSet { owner, spender, approved });
+        Ok(())
+    }
+
+    /// Updates `owner`'s allowance for `spender` of tokens of type `id` based
+    /// on spent `amount`.
+    ///
+    /// Does not update the allowance value in case of infinite allowance, and
+    /// does not emit an [`Approval`] event.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - Account of the token's owner.
+    /// * `spender` - Account that spends the tokens.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens spent.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientAllowance`] - If not enough allowance is
+    ///   available.
+    pub fn _spend_allowance(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), Error> {
+        let current_allowance = self.allowance(owner, spender, id);
+        if current_allowance != U256::MAX {
+            if current_allowance < amount {
+                return Err(Error::InsufficientAllowance(
+                    ERC6909InsufficientAllowance {
+                        spender,
+                        allowance: current_allowance,
+                        needed: amount,
+                        id,
+                    },
+                ));
+            }
+
+            self.allowances
+                .setter(owner)
+                .setter(spender)
+                .setter(id)
+                .sub_assign_unchecked(amount);
+        }
+
+        Ok(())
+    }
+}
+
+impl Erc6909 {
+    /// Checks that the caller may move `amount` of tokens of type `id` owned
+    /// by `sender`, spending its allowance if it is neither `sender` nor one
+    /// of its operators.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `sender` - Account of the token's owner.
+    /// * `id` - Token id as a number.
+    /// * `amount` - Amount of tokens to be transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InsufficientAllowance`] - If not enough allowance is
+    ///   available.
+    fn authorize_transfer(
+        &mut self,
+        sender: Address,
+        id: U256,
+        amount: U256,
+    ) -> Result<(), Error> {
+        let caller = msg::sender();
+        if sender != caller && !self.is_operator(sender, caller) {
+            self._spend_allowance(sender, caller, id, amount)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that `sender` is not `Address::ZERO`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidSender`] - If `sender` is `Address::ZERO`.
+    fn require_sender(sender: Address) -> Result<(), Error> {
+        if sender.is_zero() {
+            return Err(Error::InvalidSender(ERC6909InvalidSender { sender }));
+        }
+        Ok(())
+    }
+
+    /// Checks that `receiver` is not `Address::ZERO`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidReceiver`] - If `receiver` is `Address::ZERO`.
+    fn require_receiver(receiver: Address) -> Result<(), Error> {
+        if receiver.is_zero() {
+            return Err(Error::InvalidReceiver(ERC6909InvalidReceiver {
+                receiver,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Checks that `approver` is not `Address::ZERO`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidApprover`] - If `approver` is `Address::ZERO`.
+    fn require_approver(approver: Address) -> Result<(), Error> {
+        if approver.is_zero() {
+            return Err(Error::InvalidApprover(ERC6909InvalidApprover {
+                approver,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Checks that `spender` is not `Address::ZERO`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidSpender`] - If `spender` is `Address::ZERO`.
+    fn require_spender(spender: Address) -> Result<(), Error> {
+        if spender.is_zero() {
+            return Err(Error::InvalidSpender(ERC6909InvalidSpender {
+                spender,
+            }));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::TopLevelStorage;
+
+    use super::{
+        ERC6909InsufficientAllowance, ERC6909InsufficientBalance,
+        ERC6909InvalidApprover, ERC6909InvalidReceiver, ERC6909InvalidSender,
+        ERC6909InvalidSpender, Erc6909, Error, IErc6909,
+    };
+    use crate::utils::introspection::erc165::IErc165;
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+    const AMOUNT: U256 = uint!(100_U256);
+
+    unsafe impl TopLevelStorage for Erc6909 {}
+
+    #[motsu::test]
+    fn mints(contract: Contract<Erc6909>, alice: Address) {
+        contract
+            .sender(alice)
+            ._mint(alice, TOKEN_ID, AMOUNT)
+            .expect("should mint tokens to Alice");
+
+        assert_eq!(AMOUNT, contract.sender(alice).balance_of(alice, TOKEN_ID));
+        assert_eq!(
+            U256::ZERO,
+            contract.sender(alice).balance_of(alice, uint!(2_U256))
+        );
+    }
+
+    #[motsu::test]
+    fn error_when_minting_to_invalid_receiver(
+        contract: Contract<Erc6909>,
+        alice: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            ._mint(Address::ZERO, TOKEN_ID, AMOUNT)
+            .expect_err("should not mint tokens to `Address::ZERO`");
+
+        assert!(matches!(
+            err,
+            Error::InvalidReceiver(ERC6909InvalidReceiver { receiver })
+                if receiver.is_zero()
+        ));
+    }
+
+    #[motsu::test]
+    fn burns(contract: Contract<Erc6909>, alice: Address) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+
+        contract
+            .sender(alice)
+            ._burn(alice, TOKEN_ID, uint!(40_U256))
+            .expect("should burn Alice's tokens");
+
+        assert_eq!(
+            uint!(60_U256),
+            contract.sender(alice).balance_of(alice, TOKEN_ID)
+        );
+    }
+
+    #[motsu::test]
+    fn error_when_burning_from_invalid_sender(
+        contract: Contract<Erc6909>,
+        alice: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            ._burn(Address::ZERO, TOKEN_ID, AMOUNT)
+            .expect_err("should not burn tokens from `Address::ZERO`");
+
+        assert!(matches!(
+            err,
+            Error::InvalidSender(ERC6909InvalidSender { sender })
+                if sender.is_zero()
+        ));
+    }
+
+    #[motsu::test]
+    fn transfers(contract: Contract<Erc6909>, alice: Address, bob: Address) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+
+        let result = contract
+            .sender(alice)
+            .transfer(bob, TOKEN_ID, uint!(30_U256))
+            .expect("should transfer tokens from Alice to Bob");
+
+        assert!(result);
+        assert_eq!(
+            uint!(70_U256),
+            contract.sender(alice).balance_of(alice, TOKEN_ID)
+        );
+        assert_eq!(
+            uint!(30_U256),
+            contract.sender(alice).balance_of(bob, TOKEN_ID)
+        );
+    }
+
+    #[motsu::test]
+    fn error_when_transfer_with_insufficient_balance(
+        contract: Contract<Erc6909>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+
+        let needed = AMOUNT + uint!(1_U256);
+        let err = contract
+            .sender(alice)
+            .transfer(bob, TOKEN_ID, needed)
+            .expect_err("should not transfer more than the balance");
+
+        assert!(matches!(
+            err,
+            Error::InsufficientBalance(ERC6909InsufficientBalance {
+                sender,
+                balance,
+                needed: err_needed,
+                id,
+            }) if sender == alice
+                && balance == AMOUNT
+                && err_needed == needed
+                && id == TOKEN_ID
+        ));
+    }
+
+    #[motsu::test]
+    fn error_when_transfer_to_invalid_receiver(
+        contract: Contract<Erc6909>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+
+        let err = contract
+            .sender(alice)
+            .transfer(Address::ZERO, TOKEN_ID, AMOUNT)
+            .expect_err("should not transfer tokens to `Address::ZERO`");
+
+        assert!(matches!(
+            err,
+            Error::InvalidReceiver(ERC6909InvalidReceiver { receiver })
+                if receiver.is_zero()
+        ));
+    }
+
+    #[motsu::test]
+    fn approves_and_reads_allowance(
+        contract: Contract<Erc6909>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let result = contract
+            .sender(alice)
+            .approve(bob, TOKEN_ID, AMOUNT)
+            .expect("should approve Bob for Alice's tokens");
+
+        assert!(result);
+        assert_eq!(
+            AMOUNT,
+            contract.sender(alice).allowance(alice, bob, TOKEN_ID)
+        );
+        assert_eq!(
+            U256::ZERO,
+            contract.sender(alice).allowance(alice, bob, uint!(2_U256))
+        );
+    }
+
+    #[motsu::test]
+    fn error_when_approve_for_invalid_spender(
+        contract: Contract<Erc6909>,
+        alice: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            .approve(Address::ZERO, TOKEN_ID, AMOUNT)
+            .expect_err("should not approve `Address::ZERO`");
+
+        assert!(matches!(
+            err,
+            Error::InvalidSpender(ERC6909InvalidSpender { spender })
+                if spender.is_zero()
+        ));
+    }
+
+    #[motsu::test]
+    fn error_when_invalid_approver(
+        contract: Contract<Erc6909>,
+        alice: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            ._approve(Address::ZERO, alice, TOKEN_ID, AMOUNT)
+            .expect_err("should not approve for `Address::ZERO`");
+
+        assert!(matches!(
+            err,
+            Error::InvalidApprover(ERC6909InvalidApprover { approver })
+                if approver.is_zero()
+        ));
+    }
+
+    #[motsu::test]
+    fn transfer_from_spends_allowance(
+        contract: Contract<Erc6909>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+        contract.sender(alice).approve(bob, TOKEN_ID, AMOUNT).unwrap();
+
+        let result = contract
+            .sender(bob)
+            .transfer_from(alice, bob, TOKEN_ID, uint!(40_U256))
+            .expect("should transfer Alice's tokens with Bob's allowance");
+
+        assert!(result);
+        assert_eq!(
+            uint!(40_U256),
+            contract.sender(alice).balance_of(bob, TOKEN_ID)
+        );
+        assert_eq!(
+            uint!(60_U256),
+            contract.sender(alice).allowance(alice, bob, TOKEN_ID)
+        );
+    }
+
+    #[motsu::test]
+    fn transfer_from_keeps_infinite_allowance(
+        contract: Contract<Erc6909>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+        contract.sender(alice).approve(bob, TOKEN_ID, U256::MAX).unwrap();
+
+        contract
+            .sender(bob)
+            .transfer_from(alice, bob, TOKEN_ID, AMOUNT)
+            .expect("should transfer Alice's tokens with Bob's allowance");
+
+        assert_eq!(
+            U256::MAX,
+            contract.sender(alice).allowance(alice, bob, TOKEN_ID)
+        );
+    }
+
+    #[motsu::test]
+    fn error_when_transfer_from_with_insufficient_allowance(
+        contract: Contract<Erc6909>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+        contract.sender(alice).approve(bob, TOKEN_ID, uint!(10_U256)).unwrap();
+
+        let err = contract
+            .sender(bob)
+            .transfer_from(alice, bob, TOKEN_ID, AMOUNT)
+            .expect_err("should not transfer more than the allowance");
+
+        assert!(matches!(
+            err,
+            Error::InsufficientAllowance(ERC6909InsufficientAllowance {
+                spender,
+                allowance,
+                needed,
+                id,
+            }) if spender == bob
+                && allowance == uint!(10_U256)
+                && needed == AMOUNT
+                && id == TOKEN_ID
+        ));
+    }
+
+    #[motsu::test]
+    fn operator_transfers_without_allowance(
+        contract: Contract<Erc6909>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID, AMOUNT).unwrap();
+
+        let result = contract
+            .sender(alice)
+            .set_operator(bob, true)
+            .expect("should set Bob as Alice's operator");
+        assert!(result);
+        assert!(contract.sender(alice).is_operator(alice, bob));
+
+        contract
+            .sender(bob)
+            .transfer_from(alice, bob, TOKEN_ID, AMOUNT)
+            .expect("should transfer Alice's tokens as her operator");
+
+        assert_eq!(AMOUNT, contract.sender(alice).balance_of(bob, TOKEN_ID));
+        assert_eq!(
+            U256::ZERO,
+            contract.sender(alice).allowance(alice, bob, TOKEN_ID)
+        );
+
+        contract.sender(alice).set_operator(bob, false).unwrap();
+        assert!(!contract.sender(alice).is_operator(alice, bob));
+    }
+
+    #[motsu::test]
+    fn error_when_set_operator_for_invalid_spender(
+        contract: Contract<Erc6909>,
+        alice: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            .set_operator(Address::ZERO, true)
+            .expect_err("should not set `Address::ZERO` as operator");
+
+        assert!(matches!(
+            err,
+            Error::InvalidSpender(ERC6909InvalidSpender { spender })
+                if spender.is_zero()
+        ));
+    }
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <Erc6909 as IErc6909>::INTERFACE_ID;
+        let expected = 0x0f63_2fb3;
+        assert_eq!(actual, expected);
+
+        let actual = <Erc6909 as IErc165>::INTERFACE_ID;
+        let expected = 0x01ff_c9a7;
+        assert_eq!(actual, expected);
+    }
+}
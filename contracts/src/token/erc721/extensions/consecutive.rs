@@ -17,11 +17,18 @@
 //! solidity constructor file.
 //!
 //! IMPORTANT: Consecutive mint of [`Erc721Consecutive`] tokens is only allowed
-//! inside the contract's Solidity constructor.
+//! inside the contract's Solidity constructor, unless post-construction batch
+//! minting is enabled with
+//! [`Erc721Consecutive::_enable_post_construction_batch_mint`].
 //! As opposed to the Solidity implementation of Consecutive, there is no
 //! restriction on the [`Erc721Consecutive::_update`] function call since it is
 //! not possible to call a Rust function from the Solidity constructor.
 //!
+//! Tokens that still resolve their ownership through the batches can be burnt
+//! or transferred as whole ranges with [`Erc721Consecutive::_burn_range`] and
+//! [`Erc721Consecutive::_transfer_range`]. A token stops resolving through the
+//! batches once it is burnt, transferred or approved on its own.
+//!
 //! [ERC]: https://eips.ethereum.org/EIPS/eip-2309
 
 use alloc::{vec, vec::Vec};
@@ -30,8 +37,10 @@ use core::ops::{Deref, DerefMut};
 use alloy_primitives::{uint, Address, U256};
 use stylus_sdk::{
     abi::Bytes,
+    call::MethodError,
     evm, msg,
     prelude::{storage, TopLevelStorage},
+    storage::StorageBool,
     stylus_proc::{public, SolidityError},
 };
 
@@ -61,7 +70,9 @@ pub struct Erc721Consecutive {
     pub erc721: Erc721,
     /// [`Trace`] contract for sequential ownership.
     pub(crate) sequential_ownership: Trace<S160>,
-    /// [`BitMap`] contract for sequential burn of tokens.
+    /// [`BitMap`] contract marking batched tokens whose ownership no longer
+    /// resolves through `sequential_ownership`, because they were burnt,
+    /// transferred or approved on their own.
     pub(crate) sequential_burn: BitMap,
     /// Used to offset the first token id in `next_consecutive_id` calculation.
     pub(crate) first_consecutive_id: StorageU96,
@@ -70,6 +81,9 @@ pub struct Erc721Consecutive {
     /// entry per token, and have protections against "unreasonably large"
     /// batches of tokens.
     pub(crate) max_batch_size: StorageU96,
+    /// Whether [`Erc721Consecutive::_mint_consecutive`] may be called after
+    /// construction.
+    pub(crate) post_construction_batch_mint: StorageBool,
 }
 
 impl Deref for Erc721Consecutive {
@@ -129,6 +143,18 @@ mod sol {
         #[derive(Debug)]
         #[allow(missing_docs)]
         error ERC721ForbiddenBatchBurn();
+
+        /// Range from `from_token_id` to `to_token_id` is empty or not within
+        /// the consecutively minted tokens.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC721InvalidTokenRange(uint256 from_token_id, uint256 to_token_id);
+
+        /// Token `token_id` was burnt, transferred or approved on its own, so
+        /// its ownership no longer resolves through the consecutive batches.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC721DetachedToken(uint256 token_id);
     }
 }
 
@@ -145,10 +171,23 @@ pub enum Error {
     ForbiddenBatchMint(ERC721ForbiddenBatchMint),
     /// Exceeds the max amount of mints per batch.
     ExceededMaxBatchMint(ERC721ExceededMaxBatchMint),
-    /// Individual minting is not allowed.
+    /// Individual minting is not allowed, e.g. of a token id reserved for
+    /// future batches while post-construction batch minting is enabled.
     ForbiddenMint(ERC721ForbiddenMint),
     /// Batch burn is not supported.
     ForbiddenBatchBurn(ERC721ForbiddenBatchBurn),
+    /// Range of tokens is empty or not within the consecutively minted
+    /// tokens.
+    InvalidTokenRange(ERC721InvalidTokenRange),
+    /// Ownership of a token no longer resolves through the consecutive
+    /// batches.
+    DetachedToken(ERC721DetachedToken),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
 }
 
 /// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
@@ -287,8 +326,11 @@ impl Erc721Consecutive {
     /// far.
     ///
     /// CAUTION: Does not emit a [`Transfer`] event. This is ERC-721 compliant
-    /// as long as it is done inside of the constructor, which is enforced by
-    /// this function.
+    /// as long as it is done inside of the constructor. After construction,
+    /// only call it once post-construction batch minting was enabled with
+    /// [`Self::_enable_post_construction_batch_mint`], in which case the batch
+    /// is announced to indexers by the ERC-2309 [`ConsecutiveTransfer`] event
+    /// alone.
     ///
     /// CAUTION: Does not invoke
     /// [`erc721::IERC721Receiver::on_erc_721_received`] on the receiver.
@@ -382,14 +424,12 @@ impl Erc721Consecutive {
     ) -> Result<Address, Error> {
         let previous_owner = self._update_base(to, token_id, auth)?;
 
-        // if we burn
-        if to.is_zero()
-            // and the token_id was minted in a batch
-            && token_id < U256::from(self._next_consecutive_id())
-            // and the token was never marked as burnt
+        // if the token_id was minted in a batch
+        if token_id < U256::from(self._next_consecutive_id())
+            // and the token was never marked as detached
             && !self.sequential_burn.get(token_id)
         {
-            // record burn
+            // record that ownership is now stored in the core (zero on burn)
             self.sequential_burn.set(token_id);
         }
 
@@ -403,6 +443,300 @@ impl Erc721Consecutive {
     /// # Arguments
     ///
     /// * `&self` - Read access to the contract's state.
+    /// Destroys the tokens from `from_id` to `to_id`, both inclusive, by
+    /// marking them burnt word by word and decreasing the balance of each
+    /// owner once.
+    ///
+    /// CAUTION: Does not emit a [`Transfer`] event per token and does not go
+    /// through [`Self::_update`], so extensions tracking transfers in their
+    /// own `_update` are not notified.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from_id` - First token id of the range.
+    /// * `to_id` - Last token id of the range.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidTokenRange`] - If the range is empty or not within the
+    ///   consecutively minted tokens.
+    /// * [`Error::DetachedToken`] - If a token of the range was burnt,
+    ///   transferred or approved on its own.
+    ///
+    /// # Events
+    ///
+    /// * [`ConsecutiveTransfer`] - For each owner of tokens in the range.
+    pub fn _burn_range(
+        &mut self,
+        from_id: U256,
+        to_id: U256,
+    ) -> Result<(), Error> {
+        self._check_sequential_range(from_id, to_id)?;
+
+        for (start, end, owner) in self._sequential_segments(from_id, to_id) {
+            self.erc721
+                .balances
+                .setter(owner)
+                .sub_assign_unchecked(end - start + uint!(1_U256));
+            evm::log(ConsecutiveTransfer {
+                from_token_id: start,
+                to_token_id: end,
+                from_address: owner,
+                to_address: Address::ZERO,
+            });
+        }
+
+        self.sequential_burn.set_range(from_id, to_id);
+        Ok(())
+    }
+
+    /// Splits the range from `from_id` to `to_id`, both inclusive, into the
+    /// segments owned by a single account according to the sequential
+    /// ownership checkpoints, as `(start, end, owner)` tuples.
+    ///
+    /// The range must have been checked with
+    /// [`Self::_check_sequential_range`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `from_id` - First token id of the range.
+    /// * `to_id` - Last token id of the range.
+    pub(crate) fn _sequential_segments(
+        &self,
+        from_id: U256,
+        to_id: U256,
+    ) -> Vec<(U256, U256, Address)> {
+        let mut segments = Vec::new();
+        let mut start = from_id;
+        let mut pos =
+            self.sequential_ownership.lower_position(U96::from(start));
+        while start <= to_id {
+            let (key, owner) = self.sequential_ownership.at(pos.to());
+            let end = to_id.min(U256::from(key));
+            segments.push((start, end, owner.into()));
+
+            start = end + uint!(1_U256);
+            pos += uint!(1_U256);
+        }
+        segments
+    }
+
+    /// Transfers the tokens from `from_id` to `to_id`, both inclusive, from
+    /// `from` to `to`, by splitting the sequential ownership checkpoints at the
+    /// range bounds.
+    ///
+    /// CAUTION: Does not emit a [`Transfer`] event per token, does not invoke
+    /// [`erc721::IERC721Receiver::on_erc_721_received`] on the receiver and
+    /// does not go through [`Self::_update`], so extensions tracking
+    /// transfers in their own `_update` are not notified.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account of the sender.
+    /// * `to` - Account of the recipient.
+    /// * `from_id` - First token id of the range.
+    /// * `to_id` - Last token id of the range.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    /// * [`Error::InvalidTokenRange`] - If the range is empty or not within the
+    ///   consecutively minted tokens.
+    /// * [`Error::DetachedToken`] - If a token of the range was burnt,
+    ///   transferred or approved on its own.
+    /// * [`erc721::Error::IncorrectOwner`] - If a token of the range is not
+    ///   owned by `from`.
+    ///
+    /// # Events
+    ///
+    /// * [`ConsecutiveTransfer`].
+    pub fn _transfer_range(
+        &mut self,
+        from: Address,
+        to: Address,
+        from_id: U256,
+        to_id: U256,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        self._check_sequential_range(from_id, to_id)?;
+
+        let mut start = from_id;
+        let mut pos =
+            self.sequential_ownership.lower_position(U96::from(start));
+        while start <= to_id {
+            let (key, owner) = self.sequential_ownership.at(pos.to());
+            let owner: Address = owner.into();
+            if owner != from {
+                return Err(erc721::Error::IncorrectOwner(
+                    ERC721IncorrectOwner {
+                        sender: from,
+                        token_id: start,
+                        owner,
+                    },
+                )
+                .into());
+            }
+            start = U256::from(key) + uint!(1_U256);
+            pos += uint!(1_U256);
+        }
+
+        // Split the checkpoints so that the range is covered by whole
+        // checkpoints only, then hand these checkpoints over to `to`.
+        self.sequential_ownership.split(U96::from(to_id));
+        if from_id > U256::from(self._first_consecutive_id()) {
+            self.sequential_ownership.split(U96::from(from_id - uint!(1_U256)));
+        }
+        let first_pos =
+            self.sequential_ownership.lower_position(U96::from(from_id));
+        let last_pos =
+            self.sequential_ownership.lower_position(U96::from(to_id));
+        let mut pos = first_pos;
+        while pos <= last_pos {
+            self.sequential_ownership.set_value_at(pos, to.into());
+            pos += uint!(1_U256);
+        }
+
+        let size = to_id - from_id + uint!(1_U256);
+        self.erc721.balances.setter(from).sub_assign_unchecked(size);
+        self.erc721.balances.setter(to).add_assign_unchecked(size);
+
+        evm::log(ConsecutiveTransfer {
+            from_token_id: from_id,
+            to_token_id: to_id,
+            from_address: from,
+            to_address: to,
+        });
+        Ok(())
+    }
+
+    /// Checks that the range from `from_id` to `to_id`, both inclusive, is
+    /// within the consecutively minted tokens, and that each of its tokens
+    /// still resolves its ownership through the sequential ownership
+    /// checkpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `from_id` - First token id of the range.
+    /// * `to_id` - Last token id of the range.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidTokenRange`] - If the range is empty or not within the
+    ///   consecutively minted tokens.
+    /// * [`Error::DetachedToken`] - If a token of the range was burnt,
+    ///   transferred or approved on its own.
+    fn _check_sequential_range(
+        &self,
+        from_id: U256,
+        to_id: U256,
+    ) -> Result<(), Error> {
+        if from_id > to_id
+            || from_id < U256::from(self._first_consecutive_id())
+            || to_id >= U256::from(self._next_consecutive_id())
+        {
+            return Err(ERC721InvalidTokenRange {
+                from_token_id: from_id,
+                to_token_id: to_id,
+            }
+            .into());
+        }
+
+        if let Some(token_id) =
+            self.sequential_burn.first_set_in_range(from_id, to_id)
+        {
+            return Err(ERC721DetachedToken { token_id }.into());
+        }
+
+        Ok(())
+    }
+
+    /// Marks `token_id` as detached from the consecutive batches, by storing
+    /// its current owner in the core, if it was minted in a batch and still
+    /// resolves its ownership through the sequential ownership checkpoints.
+    ///
+    /// Tokens are detached automatically when they are transferred, burnt or
+    /// approved on their own. Contracts upgraded from a version that did not
+    /// detach tokens should call it for every batched token transferred or
+    /// approved before the upgrade, so that range operations reject them.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    pub fn _detach(&mut self, token_id: U256) {
+        if token_id >= U256::from(self._first_consecutive_id())
+            && token_id < U256::from(self._next_consecutive_id())
+            && !self.sequential_burn.get(token_id)
+        {
+            let owner = self._owner_of(token_id);
+            self.erc721.owners.setter(token_id).set(owner);
+            self.sequential_burn.set(token_id);
+        }
+    }
+
+    /// Enables batch minting after construction, in the style of ERC-721A.
+    ///
+    /// From then on, every token id from the next consecutive id up to
+    /// `U96::MAX` is reserved for future batches, so that ownership of
+    /// batched tokens keeps resolving through the sequential ownership
+    /// checkpoints. [`Self::_mint`] reverts for these ids.
+    ///
+    /// IMPORTANT: Enable it before any of these ids is minted individually,
+    /// since a later batch would otherwise also attribute them to its
+    /// receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    pub fn _enable_post_construction_batch_mint(&mut self) {
+        self.post_construction_batch_mint.set(true);
+    }
+
+    /// Returns whether batch minting after construction is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn _post_construction_batch_mint(&self) -> bool {
+        self.post_construction_batch_mint.get()
+    }
+
+    /// Checks that `token_id` is not reserved for future batches, which is
+    /// the case for every id from the next consecutive id up to `U96::MAX`
+    /// while post-construction batch minting is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ForbiddenMint`] - If `token_id` is reserved for future
+    ///   batches.
+    pub(crate) fn _check_mint_allowed(
+        &self,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        if self._post_construction_batch_mint()
+            && token_id >= U256::from(self._next_consecutive_id())
+            && token_id <= U256::from(U96::MAX)
+        {
+            return Err(ERC721ForbiddenMint {}.into());
+        }
+        Ok(())
+    }
+
     fn _next_consecutive_id(&self) -> U96 {
         match self.sequential_ownership.latest_checkpoint() {
             None => self._first_consecutive_id(),
@@ -494,6 +828,8 @@ impl Erc721Consecutive {
     ///
     /// * [`erc721::Error::InvalidSender`] - If `token_id` already exists.
     /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    /// * [`Error::ForbiddenMint`] - If post-construction batch minting is
+    ///   enabled and `token_id` is reserved for future batches.
     ///
     /// # Events
     ///
@@ -506,6 +842,8 @@ impl Erc721Consecutive {
             .into());
         }
 
+        self._check_mint_allowed(token_id)?;
+
         let previous_owner = self._update(to, token_id, Address::ZERO)?;
         if !previous_owner.is_zero() {
             return Err(erc721::Error::InvalidSender(ERC721InvalidSender {
@@ -736,6 +1074,11 @@ impl Erc721Consecutive {
             }
         }
 
+        // Range operations never move a token with a pending approval.
+        if !to.is_zero() {
+            self._detach(token_id);
+        }
+
         self.erc721.token_approvals.setter(token_id).set(to);
         Ok(())
     }
@@ -775,7 +1118,8 @@ mod tests {
         erc721,
         erc721::{
             extensions::consecutive::{
-                ERC721ExceededMaxBatchMint, Erc721Consecutive, Error, U96,
+                ERC721DetachedToken, ERC721ExceededMaxBatchMint,
+                Erc721Consecutive, Error, U96,
             },
             ERC721IncorrectOwner, ERC721InvalidApprover, ERC721InvalidReceiver,
             ERC721InvalidSender, ERC721NonexistentToken, IErc721,
@@ -1381,4 +1725,322 @@ mod tests {
             })) if TOKEN_ID == t_id
         ));
     }
+
+    #[motsu::test]
+    fn mints_consecutive_after_construction_when_enabled(
+        contract: Contract<Erc721Consecutive>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.init(alice, |contract| {
+            mint_consecutive(contract, vec![alice], vec![uint!(10_U96)]);
+        });
+        contract.sender(alice)._enable_post_construction_batch_mint();
+
+        let first = contract
+            .sender(alice)
+            ._mint_consecutive(bob, uint!(5_U96))
+            .expect("should mint consecutively after construction");
+        assert_eq!(uint!(10_U96), first);
+
+        assert_eq!(
+            uint!(5_U256),
+            contract
+                .sender(alice)
+                .balance_of(bob)
+                .expect("should return the balance")
+        );
+        for token_id in 10..15 {
+            let owner = contract
+                .sender(alice)
+                .owner_of(U256::from(token_id))
+                .expect("should return the owner");
+            assert_eq!(bob, owner);
+        }
+
+        let err = contract
+            .sender(alice)
+            ._mint_consecutive(bob, MAX_BATCH_SIZE + uint!(1_U96))
+            .expect_err("should not mint more than the max batch size");
+        assert!(matches!(err, Error::ExceededMaxBatchMint(_)));
+    }
+
+    #[motsu::test]
+    fn error_when_minting_reserved_token_id(
+        contract: Contract<Erc721Consecutive>,
+        alice: Address,
+    ) {
+        contract.init(alice, |contract| {
+            mint_consecutive(contract, vec![alice], vec![uint!(10_U96)]);
+        });
+        contract.sender(alice)._enable_post_construction_batch_mint();
+
+        let err = contract
+            .sender(alice)
+            ._mint(alice, uint!(10_U256))
+            .expect_err("should not mint a token id reserved for batches");
+        assert!(matches!(err, Error::ForbiddenMint(_)));
+
+        let token_id = U256::from(U96::MAX) + uint!(1_U256);
+        contract
+            .sender(alice)
+            ._mint(alice, token_id)
+            .expect("should mint a token id beyond the consecutive range");
+        assert_eq!(
+            alice,
+            contract
+                .sender(alice)
+                .owner_of(token_id)
+                .expect("should return the owner")
+        );
+    }
+
+    fn init_ranges(
+        contract: &mut Erc721Consecutive,
+        alice: Address,
+        bob: Address,
+    ) {
+        mint_consecutive(
+            contract,
+            vec![alice, bob],
+            vec![uint!(10_U96), uint!(5_U96)],
+        );
+    }
+
+    #[motsu::test]
+    fn burns_range(
+        contract: Contract<Erc721Consecutive>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.init(alice, |contract| init_ranges(contract, alice, bob));
+
+        contract
+            .sender(alice)
+            ._burn_range(uint!(8_U256), uint!(11_U256))
+            .expect("should burn a range across two batches");
+
+        assert_eq!(
+            uint!(8_U256),
+            contract
+                .sender(alice)
+                .balance_of(alice)
+                .expect("should return the balance")
+        );
+        assert_eq!(
+            uint!(3_U256),
+            contract
+                .sender(alice)
+                .balance_of(bob)
+                .expect("should return the balance")
+        );
+        for token_id in 8..12 {
+            let err = contract
+                .sender(alice)
+                .owner_of(U256::from(token_id))
+                .expect_err("should not return the owner of a burnt token");
+            assert!(matches!(
+                err,
+                Error::Erc721(erc721::Error::NonexistentToken(_))
+            ));
+        }
+        assert_eq!(
+            alice,
+            contract
+                .sender(alice)
+                .owner_of(uint!(7_U256))
+                .expect("should return the owner")
+        );
+        assert_eq!(
+            bob,
+            contract
+                .sender(alice)
+                .owner_of(uint!(12_U256))
+                .expect("should return the owner")
+        );
+
+        let err = contract
+            .sender(alice)
+            ._burn_range(uint!(9_U256), uint!(9_U256))
+            .expect_err("should not burn a burnt token again");
+        assert!(matches!(
+            err,
+            Error::DetachedToken(ERC721DetachedToken { token_id })
+                if token_id == uint!(9_U256)
+        ));
+    }
+
+    #[motsu::test]
+    fn transfers_range(
+        contract: Contract<Erc721Consecutive>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.init(alice, |contract| init_ranges(contract, alice, bob));
+
+        contract
+            .sender(alice)
+            ._transfer_range(alice, bob, uint!(3_U256), uint!(5_U256))
+            .expect("should transfer a range");
+
+        assert_eq!(
+            uint!(7_U256),
+            contract
+                .sender(alice)
+                .balance_of(alice)
+                .expect("should return the balance")
+        );
+        assert_eq!(
+            uint!(8_U256),
+            contract
+                .sender(alice)
+                .balance_of(bob)
+                .expect("should return the balance")
+        );
+        for (token_id, owner) in
+            [(2, alice), (3, bob), (5, bob), (6, alice), (9, alice), (10, bob)]
+        {
+            assert_eq!(
+                owner,
+                contract
+                    .sender(alice)
+                    .owner_of(U256::from(token_id))
+                    .expect("should return the owner")
+            );
+        }
+
+        contract
+            .sender(bob)
+            .transfer_from(bob, alice, uint!(4_U256))
+            .expect("should transfer a token of the range");
+        let err = contract
+            .sender(alice)
+            ._transfer_range(bob, alice, uint!(3_U256), uint!(5_U256))
+            .expect_err("should not transfer a detached token");
+        assert!(matches!(
+            err,
+            Error::DetachedToken(ERC721DetachedToken { token_id })
+                if token_id == uint!(4_U256)
+        ));
+
+        let err = contract
+            .sender(alice)
+            ._transfer_range(alice, bob, uint!(6_U256), uint!(12_U256))
+            .expect_err("should not transfer tokens of another owner");
+        assert!(matches!(
+            err,
+            Error::Erc721(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender,
+                token_id,
+                owner,
+            })) if sender == alice && token_id == uint!(10_U256) && owner == bob
+        ));
+    }
+
+    #[motsu::test]
+    fn resolves_ownership_of_detached_tokens(
+        contract: Contract<Erc721Consecutive>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let legacy_id = uint!(2_U256);
+        let detached_id = uint!(3_U256);
+        contract.init(alice, |contract| {
+            init_ranges(contract, alice, bob);
+            // Ownership written by a transfer before tokens were detached:
+            // stored in the core, without marking the sequential burn.
+            contract.erc721.owners.setter(legacy_id).set(bob);
+        });
+
+        // Both the legacy and the current ownership resolve through the core.
+        assert!(!contract.sender(alice).sequential_burn.get(legacy_id));
+        assert_eq!(
+            bob,
+            contract
+                .sender(alice)
+                .owner_of(legacy_id)
+                .expect("should return the owner")
+        );
+
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, detached_id)
+            .expect("should transfer a token of the batch");
+        assert!(contract.sender(alice).sequential_burn.get(detached_id));
+        assert_eq!(
+            bob,
+            contract
+                .sender(alice)
+                .owner_of(detached_id)
+                .expect("should return the owner")
+        );
+
+        // Detaching a legacy token keeps its owner and excludes it from
+        // range operations.
+        contract.sender(alice)._detach(legacy_id);
+        assert!(contract.sender(alice).sequential_burn.get(legacy_id));
+        assert_eq!(
+            bob,
+            contract
+                .sender(alice)
+                .owner_of(legacy_id)
+                .expect("should return the owner")
+        );
+        let err = contract
+            .sender(alice)
+            ._transfer_range(alice, bob, U256::ZERO, legacy_id)
+            .expect_err("should not transfer a detached token");
+        assert!(matches!(
+            err,
+            Error::DetachedToken(ERC721DetachedToken { token_id })
+                if token_id == legacy_id
+        ));
+    }
+
+    #[motsu::test]
+    fn error_when_range_contains_approved_token(
+        contract: Contract<Erc721Consecutive>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.init(alice, |contract| init_ranges(contract, alice, bob));
+
+        contract
+            .sender(alice)
+            .approve(bob, uint!(2_U256))
+            .expect("should approve a token of the batch");
+        let err = contract
+            .sender(alice)
+            ._transfer_range(alice, bob, U256::ZERO, uint!(3_U256))
+            .expect_err("should not transfer an approved token");
+        assert!(matches!(
+            err,
+            Error::DetachedToken(ERC721DetachedToken { token_id })
+                if token_id == uint!(2_U256)
+        ));
+        assert_eq!(
+            alice,
+            contract
+                .sender(alice)
+                .owner_of(uint!(2_U256))
+                .expect("should return the owner")
+        );
+    }
+
+    #[motsu::test]
+    fn error_when_range_is_invalid(
+        contract: Contract<Erc721Consecutive>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.init(alice, |contract| init_ranges(contract, alice, bob));
+
+        for (from_id, to_id) in [(5, 4), (0, 15)] {
+            let err = contract
+                .sender(alice)
+                ._burn_range(U256::from(from_id), U256::from(to_id))
+                .expect_err("should not burn an invalid range");
+            assert!(matches!(err, Error::InvalidTokenRange(_)));
+        }
+    }
 }
@@ -7,24 +7,38 @@
 //! CAUTION: [`super::super::Erc721`] extensions that implement custom
 //! [`super::super::Erc721::balance_of`] logic, such as `Erc721Consecutive`,
 //! interfere with enumerability and should not be used together with
-//! [`Erc721Enumerable`].
+//! [`Erc721Enumerable`]. Use [`Erc721ConsecutiveEnumerable`] instead, which
+//! enumerates consecutively minted batches lazily.
 
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
 
 use alloy_primitives::{uint, Address, FixedBytes, U256};
 use openzeppelin_stylus_proc::interface_id;
 pub use sol::*;
 use stylus_sdk::{
-    prelude::storage,
+    abi::Bytes,
+    msg,
+    prelude::{storage, TopLevelStorage},
     storage::{StorageMap, StorageU256, StorageVec},
     stylus_proc::{public, SolidityError},
 };
 
 use crate::{
-    token::erc721::{self, IErc721},
-    utils::introspection::erc165::IErc165,
+    token::erc721::{
+        self,
+        extensions::consecutive::{self, Erc721Consecutive},
+        ERC721IncorrectOwner, ERC721InvalidReceiver, ERC721InvalidSender,
+        ERC721NonexistentToken, Erc721, IErc721,
+    },
+    utils::{
+        introspection::erc165::IErc165,
+        structs::checkpoints::{Size, S160},
+    },
 };
 
+type U96 = <S160 as Size>::Key;
+
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod sol {
     use alloy_sol_macro::sol;
@@ -334,6 +348,483 @@ impl Erc721Enumerable {
     }
 }
 
+/// A range of consecutively minted tokens that occupies the positions
+/// `offset..offset + size` of an enumeration list without writing them.
+#[storage]
+pub struct ConsecutiveRange {
+    /// Position of the range's first token in the list.
+    pub(crate) offset: StorageU256,
+    /// Id of the range's first token.
+    pub(crate) first_token_id: StorageU256,
+    /// Number of tokens in the range.
+    pub(crate) size: StorageU256,
+}
+
+/// Returns the token at `index` of a list whose slot at `index` was never
+/// written, i.e. the token laid out there by the latest range covering it.
+fn range_token_at(
+    ranges: &StorageVec<ConsecutiveRange>,
+    index: U256,
+) -> Option<U256> {
+    (0..ranges.len()).rev().find_map(|i| {
+        let range = ranges.get(i)?;
+        let offset = range.offset.get();
+        (index >= offset && index - offset < range.size.get())
+            .then(|| range.first_token_id.get() + index - offset)
+    })
+}
+
+/// Returns the position of `token_id` in a list whose index for
+/// `token_id` was never written, i.e. the position laid out by the range
+/// containing it.
+fn range_index_of(
+    ranges: &StorageVec<ConsecutiveRange>,
+    token_id: U256,
+) -> Option<U256> {
+    (0..ranges.len()).find_map(|i| {
+        let range = ranges.get(i)?;
+        let first_token_id = range.first_token_id.get();
+        (token_id >= first_token_id
+            && token_id - first_token_id < range.size.get())
+        .then(|| range.offset.get() + token_id - first_token_id)
+    })
+}
+
+/// State of an [`Erc721ConsecutiveEnumerable`] token.
+///
+/// Batches minted through [`Erc721ConsecutiveEnumerable::_mint_consecutive`]
+/// are recorded as [`ConsecutiveRange`]s instead of being written token by
+/// token. A slot of an enumeration list is only written once a token moves
+/// into it, and unwritten slots are resolved from the ranges on read. Token
+/// ids and indices are stored offset by one, so that zero marks an unwritten
+/// slot.
+#[storage]
+pub struct Erc721ConsecutiveEnumerable {
+    /// [`Erc721Consecutive`] contract.
+    pub erc721_consecutive: Erc721Consecutive,
+    /// Maps owners to a mapping of indices to tokens ids.
+    pub(crate) owned_tokens: StorageMap<Address, StorageMap<U256, StorageU256>>,
+    /// Maps tokens ids to indices in `owned_tokens`.
+    pub(crate) owned_tokens_index: StorageMap<U256, StorageU256>,
+    /// Maps owners to the consecutive ranges laid out in `owned_tokens`.
+    pub(crate) owned_ranges: StorageMap<Address, StorageVec<ConsecutiveRange>>,
+    /// Maps indices to tokens ids.
+    pub(crate) all_tokens: StorageMap<U256, StorageU256>,
+    /// Maps tokens ids to indices in `all_tokens`.
+    pub(crate) all_tokens_index: StorageMap<U256, StorageU256>,
+    /// Number of tokens in `all_tokens`.
+    pub(crate) all_tokens_length: StorageU256,
+    /// Consecutive ranges laid out in `all_tokens`.
+    pub(crate) all_ranges: StorageVec<ConsecutiveRange>,
+}
+
+impl Deref for Erc721ConsecutiveEnumerable {
+    type Target = Erc721Consecutive;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721_consecutive
+    }
+}
+
+impl DerefMut for Erc721ConsecutiveEnumerable {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721_consecutive
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc721ConsecutiveEnumerable {}
+
+#[public]
+impl IErc721 for Erc721ConsecutiveEnumerable {
+    type Error = consecutive::Error;
+
+    fn balance_of(&self, owner: Address) -> Result<U256, consecutive::Error> {
+        self.erc721_consecutive.balance_of(owner)
+    }
+
+    fn owner_of(&self, token_id: U256) -> Result<Address, consecutive::Error> {
+        self.erc721_consecutive.owner_of(token_id)
+    }
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), consecutive::Error> {
+        self.safe_transfer_from_with_data(from, to, token_id, vec![].into())
+    }
+
+    #[selector(name = "safeTransferFrom")]
+    fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), consecutive::Error> {
+        self.transfer_from(from, to, token_id)?;
+        Ok(self.erc721_consecutive.erc721._check_on_erc721_received(
+            msg::sender(),
+            from,
+            to,
+            token_id,
+            &data,
+        )?)
+    }
+
+    fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), consecutive::Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        // Setting an "auth" argument enables the `_is_authorized` check which
+        // verifies that the token exists (`from != 0`). Therefore, it is
+        // not needed to verify that the return value is not 0 here.
+        let previous_owner = self._update(to, token_id, msg::sender())?;
+        if previous_owner != from {
+            return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    fn approve(
+        &mut self,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), consecutive::Error> {
+        self.erc721_consecutive.approve(to, token_id)
+    }
+
+    fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), consecutive::Error> {
+        self.erc721_consecutive.set_approval_for_all(operator, approved)
+    }
+
+    fn get_approved(
+        &self,
+        token_id: U256,
+    ) -> Result<Address, consecutive::Error> {
+        self.erc721_consecutive.get_approved(token_id)
+    }
+
+    fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
+        self.erc721_consecutive.is_approved_for_all(owner, operator)
+    }
+}
+
+impl IErc721Enumerable for Erc721ConsecutiveEnumerable {
+    type Error = Error;
+
+    fn token_of_owner_by_index(
+        &self,
+        owner: Address,
+        index: U256,
+    ) -> Result<U256, Self::Error> {
+        let balance = self.erc721_consecutive.erc721.balances.get(owner);
+        if owner.is_zero() || index >= balance {
+            return Err(ERC721OutOfBoundsIndex { owner, index }.into());
+        }
+
+        self._owned_token_at(owner, index)
+            .ok_or(ERC721OutOfBoundsIndex { owner, index }.into())
+    }
+
+    fn total_supply(&self) -> U256 {
+        self.all_tokens_length.get()
+    }
+
+    fn token_by_index(&self, index: U256) -> Result<U256, Self::Error> {
+        if index >= self.total_supply() {
+            return Err(
+                ERC721OutOfBoundsIndex { owner: Address::ZERO, index }.into()
+            );
+        }
+
+        self._token_at(index).ok_or(
+            ERC721OutOfBoundsIndex { owner: Address::ZERO, index }.into(),
+        )
+    }
+}
+
+impl IErc165 for Erc721ConsecutiveEnumerable {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        Erc721Enumerable::supports_interface(interface_id)
+            || Erc721::supports_interface(interface_id)
+    }
+}
+
+impl Erc721ConsecutiveEnumerable {
+    /// Mints a batch of tokens with length `batch_size` for `to`, and lays
+    /// the batch out at the end of the enumeration lists of `to` and of all
+    /// tokens.
+    ///
+    /// Re-export of [`Erc721Consecutive::_mint_consecutive`] that also tracks
+    /// enumeration.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint_consecutive(
+        &mut self,
+        to: Address,
+        batch_size: U96,
+    ) -> Result<U96, consecutive::Error> {
+        let next = self.erc721_consecutive._mint_consecutive(to, batch_size)?;
+
+        if batch_size > U96::ZERO {
+            let size = U256::from(batch_size);
+            let first_token_id = U256::from(next);
+
+            let balance = self.erc721_consecutive.erc721.balances.get(to);
+            let mut owned_ranges = self.owned_ranges.setter(to);
+            let mut range = owned_ranges.grow();
+            range.offset.set(balance - size);
+            range.first_token_id.set(first_token_id);
+            range.size.set(size);
+
+            let length = self.all_tokens_length.get();
+            let mut range = self.all_ranges.grow();
+            range.offset.set(length);
+            range.first_token_id.set(first_token_id);
+            range.size.set(size);
+            self.all_tokens_length.set(length + size);
+        }
+
+        Ok(next)
+    }
+
+    /// Extended version of [`Erc721Consecutive::_update`] that keeps the
+    /// enumeration lists in sync with token ownership.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `auth` - Account used for authorization of the update.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist and
+    ///   `auth` is not `Address::ZERO`.
+    /// * [`erc721::Error::InsufficientApproval`] - If `auth` is not
+    ///   `Address::ZERO` and `auth` does not have a right to approve this
+    ///   token.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    pub fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, consecutive::Error> {
+        let previous_owner =
+            self.erc721_consecutive._update(to, token_id, auth)?;
+
+        if previous_owner.is_zero() {
+            self._add_token_to_all_tokens_enumeration(token_id);
+        } else if previous_owner != to {
+            self._remove_token_from_owner_enumeration(previous_owner, token_id);
+        }
+
+        if to.is_zero() {
+            self._remove_token_from_all_tokens_enumeration(token_id);
+        } else if previous_owner != to {
+            self._add_token_to_owner_enumeration(to, token_id);
+        }
+
+        Ok(previous_owner)
+    }
+
+    /// Mints `token_id` and transfers it to `to`.
+    ///
+    /// Re-export of [`Erc721Consecutive::_mint`] that also tracks
+    /// enumeration.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), consecutive::Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        self.erc721_consecutive._check_mint_allowed(token_id)?;
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if !previous_owner.is_zero() {
+            return Err(erc721::Error::InvalidSender(ERC721InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Destroys `token_id`.
+    ///
+    /// Re-export of [`Erc721Consecutive::_burn`] that also tracks
+    /// enumeration.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(&mut self, token_id: U256) -> Result<(), consecutive::Error> {
+        let previous_owner =
+            self._update(Address::ZERO, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(erc721::Error::NonexistentToken(
+                ERC721NonexistentToken { token_id },
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Returns the token at `index` of `owner`'s tokens list, if any.
+    fn _owned_token_at(&self, owner: Address, index: U256) -> Option<U256> {
+        let token_id = self.owned_tokens.getter(owner).get(index);
+        if token_id.is_zero() {
+            range_token_at(&self.owned_ranges.getter(owner), index)
+        } else {
+            Some(token_id - uint!(1_U256))
+        }
+    }
+
+    /// Returns the index of `token_id` in `owner`'s tokens list.
+    fn _owned_token_index(&self, owner: Address, token_id: U256) -> U256 {
+        let index = self.owned_tokens_index.get(token_id);
+        if index.is_zero() {
+            range_index_of(&self.owned_ranges.getter(owner), token_id)
+                .expect("token must be enumerated for `owner`")
+        } else {
+            index - uint!(1_U256)
+        }
+    }
+
+    /// Returns the token at `index` of all tokens list, if any.
+    fn _token_at(&self, index: U256) -> Option<U256> {
+        let token_id = self.all_tokens.get(index);
+        if token_id.is_zero() {
+            range_token_at(&self.all_ranges, index)
+        } else {
+            Some(token_id - uint!(1_U256))
+        }
+    }
+
+    /// Returns the index of `token_id` in all tokens list.
+    fn _token_index(&self, token_id: U256) -> U256 {
+        let index = self.all_tokens_index.get(token_id);
+        if index.is_zero() {
+            range_index_of(&self.all_ranges, token_id)
+                .expect("token must be enumerated")
+        } else {
+            index - uint!(1_U256)
+        }
+    }
+
+    /// Appends `token_id` to `to`'s tokens list. Must be called after the
+    /// balance of `to` was increased.
+    fn _add_token_to_owner_enumeration(&mut self, to: Address, token_id: U256) {
+        let index =
+            self.erc721_consecutive.erc721.balances.get(to) - uint!(1_U256);
+        self.owned_tokens
+            .setter(to)
+            .setter(index)
+            .set(token_id + uint!(1_U256));
+        self.owned_tokens_index.setter(token_id).set(index + uint!(1_U256));
+    }
+
+    /// Appends `token_id` to all tokens list.
+    fn _add_token_to_all_tokens_enumeration(&mut self, token_id: U256) {
+        let index = self.all_tokens_length.get();
+        self.all_tokens.setter(index).set(token_id + uint!(1_U256));
+        self.all_tokens_index.setter(token_id).set(index + uint!(1_U256));
+        self.all_tokens_length.set(index + uint!(1_U256));
+    }
+
+    /// Removes `token_id` from `from`'s tokens list. Must be called after the
+    /// balance of `from` was decreased.
+    fn _remove_token_from_owner_enumeration(
+        &mut self,
+        from: Address,
+        token_id: U256,
+    ) {
+        // To prevent a gap in from's tokens array,
+        // we store the last token in the index of the token to delete,
+        // and then delete the last slot (swap and pop).
+        let last_token_index =
+            self.erc721_consecutive.erc721.balances.get(from);
+        let token_index = self._owned_token_index(from, token_id);
+
+        if token_index != last_token_index {
+            let last_token_id = self
+                ._owned_token_at(from, last_token_index)
+                .expect("token at given index must exist");
+
+            // Move the last token to the slot of the to-delete token.
+            self.owned_tokens
+                .setter(from)
+                .setter(token_index)
+                .set(last_token_id + uint!(1_U256));
+            // Update the moved token's index.
+            self.owned_tokens_index
+                .setter(last_token_id)
+                .set(token_index + uint!(1_U256));
+        }
+
+        // Deleting the last slot hands it back to the ranges, which no longer
+        // cover it once a later token or range is laid out there.
+        self.owned_tokens_index.delete(token_id);
+        self.owned_tokens.setter(from).delete(last_token_index);
+    }
+
+    /// Removes `token_id` from all tokens list.
+    fn _remove_token_from_all_tokens_enumeration(&mut self, token_id: U256) {
+        let last_token_index = self.all_tokens_length.get() - uint!(1_U256);
+        let token_index = self._token_index(token_id);
+
+        if token_index != last_token_index {
+            let last_token_id = self
+                ._token_at(last_token_index)
+                .expect("token at given index must exist");
+
+            // Move the last token to the slot of the to-delete token.
+            self.all_tokens
+                .setter(token_index)
+                .set(last_token_id + uint!(1_U256));
+            // Update the moved token's index.
+            self.all_tokens_index
+                .setter(last_token_id)
+                .set(token_index + uint!(1_U256));
+        }
+
+        self.all_tokens_index.delete(token_id);
+        self.all_tokens.delete(last_token_index);
+        self.all_tokens_length.set(last_token_index);
+    }
+}
+
 #[cfg(all(test, feature = "std"))]
 mod tests {
     use motsu::prelude::Contract;
@@ -344,7 +835,8 @@ mod tests {
 
     use super::{
         ERC721EnumerableForbiddenBatchMint, ERC721OutOfBoundsIndex,
-        Erc721Enumerable, Error, IErc721Enumerable,
+        Erc721ConsecutiveEnumerable, Erc721Enumerable, Error,
+        IErc721Enumerable,
     };
     use crate::token::erc721::{Erc721, IErc721};
 
@@ -718,4 +1210,132 @@ mod tests {
         let expected = 0x780e9d63;
         assert_eq!(actual, expected);
     }
+
+    fn init_consecutive(
+        contract: &mut Erc721ConsecutiveEnumerable,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.erc721_consecutive.max_batch_size.set(uint!(5000_U96));
+        contract
+            ._mint_consecutive(alice, uint!(3_U96))
+            .expect("should mint consecutively");
+        contract
+            ._mint_consecutive(bob, uint!(2_U96))
+            .expect("should mint consecutively");
+    }
+
+    fn owned_tokens(
+        contract: &Erc721ConsecutiveEnumerable,
+        owner: Address,
+    ) -> Vec<U256> {
+        let balance =
+            contract.balance_of(owner).expect("should return the balance");
+        let mut tokens: Vec<U256> = (0..balance.to::<u64>())
+            .map(|index| {
+                contract
+                    .token_of_owner_by_index(owner, U256::from(index))
+                    .expect("should return a token")
+            })
+            .collect();
+        tokens.sort();
+        tokens
+    }
+
+    fn all_tokens(contract: &Erc721ConsecutiveEnumerable) -> Vec<U256> {
+        let mut tokens: Vec<U256> = (0..contract.total_supply().to::<u64>())
+            .map(|index| {
+                contract
+                    .token_by_index(U256::from(index))
+                    .expect("should return a token")
+            })
+            .collect();
+        tokens.sort();
+        tokens
+    }
+
+    fn ids(ids: &[u64]) -> Vec<U256> {
+        ids.iter().copied().map(U256::from).collect()
+    }
+
+    #[motsu::test]
+    fn enumerates_batch_minted_tokens(
+        contract: Contract<Erc721ConsecutiveEnumerable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.init(alice, |contract| init_consecutive(contract, alice, bob));
+
+        let contract = contract.sender(alice);
+        assert_eq!(uint!(5_U256), contract.total_supply());
+        for index in 0..5 {
+            assert_eq!(
+                U256::from(index),
+                contract
+                    .token_by_index(U256::from(index))
+                    .expect("should return a token")
+            );
+        }
+        assert_eq!(ids(&[0, 1, 2]), owned_tokens(&contract, alice));
+        assert_eq!(ids(&[3, 4]), owned_tokens(&contract, bob));
+    }
+
+    #[motsu::test]
+    fn enumerates_batch_minted_tokens_after_updates(
+        contract: Contract<Erc721ConsecutiveEnumerable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.init(alice, |contract| init_consecutive(contract, alice, bob));
+
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, U256::ZERO)
+            .expect("should transfer");
+        contract
+            .sender(bob)
+            .transfer_from(bob, alice, uint!(4_U256))
+            .expect("should transfer");
+        contract.sender(alice)._burn(uint!(1_U256)).expect("should burn");
+        contract.sender(alice)._mint(bob, uint!(10_U256)).expect("should mint");
+        contract
+            .sender(alice)
+            ._mint(alice, uint!(1_U256))
+            .expect("should mint a burnt token again");
+
+        let contract = contract.sender(alice);
+        assert_eq!(ids(&[1, 2, 4]), owned_tokens(&contract, alice));
+        assert_eq!(ids(&[0, 3, 10]), owned_tokens(&contract, bob));
+        assert_eq!(ids(&[0, 1, 2, 3, 4, 10]), all_tokens(&contract));
+    }
+
+    #[motsu::test]
+    fn error_when_consecutive_index_out_of_bounds(
+        contract: Contract<Erc721ConsecutiveEnumerable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.init(alice, |contract| init_consecutive(contract, alice, bob));
+        contract.sender(alice)._burn(uint!(4_U256)).expect("should burn");
+
+        let err = contract
+            .sender(alice)
+            .token_by_index(uint!(4_U256))
+            .expect_err("should return Error::OutOfBoundsIndex");
+        assert!(matches!(err, Error::OutOfBoundsIndex(ERC721OutOfBoundsIndex {
+                owner,
+                index
+            }) if owner.is_zero() && index == uint!(4_U256)
+        ));
+
+        let err = contract
+            .sender(alice)
+            .token_of_owner_by_index(bob, U256::from(1))
+            .expect_err("should return Error::OutOfBoundsIndex");
+        assert!(matches!(err, Error::OutOfBoundsIndex(ERC721OutOfBoundsIndex {
+                owner,
+                index
+            }) if owner == bob && index == U256::from(1)
+        ));
+    }
 }
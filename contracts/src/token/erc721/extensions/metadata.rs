@@ -8,11 +8,13 @@ use alloc::{
 use alloy_primitives::{FixedBytes, U256};
 use openzeppelin_stylus_proc::interface_id;
 use stylus_sdk::{
-    prelude::storage, storage::StorageString, stylus_proc::public,
+    evm, prelude::storage, storage::StorageString, stylus_proc::public,
 };
 
 use crate::{
-    token::erc721::{Error, IErc721},
+    token::erc721::{
+        extensions::uri_storage::BatchMetadataUpdate, Error, IErc721,
+    },
     utils::{introspection::erc165::IErc165, Metadata},
 };
 
@@ -80,6 +82,30 @@ impl Erc721Metadata {
         self.base_uri.get_string()
     }
 
+    /// Sets `base_uri` as the base of Uniform Resource Identifier (URI) for
+    /// tokens' collection.
+    ///
+    /// Since the URI of every token changes, a [`BatchMetadataUpdate`] event
+    /// covering all token ids is emitted, so that indexers refresh the
+    /// metadata of the collection. The ERC-4906 interface is advertised by
+    /// [`super::Erc721UriStorage`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `base_uri` - New base URI for tokens.
+    ///
+    /// # Events
+    ///
+    /// * [`BatchMetadataUpdate`].
+    pub fn _set_base_uri(&mut self, base_uri: String) {
+        self.base_uri.set_str(base_uri);
+        evm::log(BatchMetadataUpdate {
+            from_token_id: U256::ZERO,
+            to_token_id: U256::MAX,
+        });
+    }
+
     /// Returns the Uniform Resource Identifier (URI) for `token_id` token.
     ///
     /// NOTE: To expose this function in your contract's ABI, implement it as
@@ -129,8 +155,14 @@ impl Erc721Metadata {
 
 #[cfg(all(test, feature = "std"))]
 mod tests {
+    use alloy_primitives::Address;
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::TopLevelStorage;
+
     use super::{Erc721Metadata, IErc165, IErc721Metadata};
 
+    unsafe impl TopLevelStorage for Erc721Metadata {}
+
     #[motsu::test]
     fn interface_id() {
         let actual = <Erc721Metadata as IErc721Metadata>::INTERFACE_ID;
@@ -143,5 +175,15 @@ mod tests {
         assert!(<Erc721Metadata as IErc165>::supports_interface(
             0x5b5e139f.into()
         ));
+        assert!(!<Erc721Metadata as IErc165>::supports_interface(
+            0x4906_4906.into()
+        ));
+    }
+
+    #[motsu::test]
+    fn sets_base_uri(contract: Contract<Erc721Metadata>, alice: Address) {
+        let base_uri = String::from("https://example.com/revealed/");
+        contract.sender(alice)._set_base_uri(base_uri.clone());
+        assert_eq!(base_uri, contract.sender(alice).base_uri());
     }
 }
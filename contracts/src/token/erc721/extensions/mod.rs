@@ -3,9 +3,25 @@ pub mod burnable;
 pub mod consecutive;
 pub mod enumerable;
 pub mod metadata;
+pub mod pausable;
+pub mod permit;
+pub mod rentable;
+pub mod royalty;
+pub mod soulbound;
 pub mod uri_storage;
+pub mod votes;
+pub mod wrapper;
 
 pub use burnable::IErc721Burnable;
-pub use enumerable::{Erc721Enumerable, IErc721Enumerable};
+pub use enumerable::{
+    Erc721ConsecutiveEnumerable, Erc721Enumerable, IErc721Enumerable,
+};
 pub use metadata::{Erc721Metadata, IErc721Metadata};
+pub use pausable::Erc721Pausable;
+pub use permit::Erc721Permit;
+pub use rentable::{Erc721Rentable, IErc4907};
+pub use royalty::Erc721Royalty;
+pub use soulbound::{Erc721Soulbound, IErc5192};
 pub use uri_storage::Erc721UriStorage;
+pub use votes::{Erc721ConsecutiveVotes, Erc721Votes, IVotes, Votes};
+pub use wrapper::{Erc721Wrapper, IErc721Wrapped};
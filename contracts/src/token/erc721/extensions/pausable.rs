@@ -0,0 +1,388 @@
+//! ERC-721 token with pausable token transfers, minting, and burning.
+//!
+//! Useful for scenarios such as preventing trades until the end of an
+//! evaluation period, or having an emergency switch for freezing all token
+//! transfers in the event of a large bug.
+//!
+//! Every path through [`Erc721Pausable::_update`] reverts while the contract
+//! is paused.
+//!
+//! NOTE: [`Erc721Pausable`] only wraps a plain [`Erc721`]. It does not guard
+//! other ERC-721 variants, e.g. an
+//! [`crate::token::erc721::extensions::Erc721Consecutive`] collection, which
+//! should call [`Pausable::when_not_paused`] before its own `_update`.
+//!
+//! NOTE: [`Pausable::pause`] and [`Pausable::unpause`] are not exposed by
+//! default. You should expose them manually in your contract's abi, with the
+//! access control of your choice.
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use stylus_sdk::{
+    abi::Bytes,
+    msg,
+    prelude::{storage, TopLevelStorage},
+    stylus_proc::{public, SolidityError},
+};
+
+use crate::{
+    token::erc721::{
+        self, extensions::IErc721Burnable, ERC721IncorrectOwner,
+        ERC721InvalidReceiver, ERC721InvalidSender, ERC721NonexistentToken,
+        Erc721, IErc721,
+    },
+    utils::{
+        introspection::erc165::IErc165,
+        pausable::{self, Pausable},
+    },
+};
+
+/// An [`Erc721Pausable`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Error type from [`Erc721`] contract [`erc721::Error`].
+    Erc721(erc721::Error),
+    /// Error type from [`Pausable`] contract [`pausable::Error`].
+    Pausable(pausable::Error),
+}
+
+/// State of an [`Erc721Pausable`] token.
+#[storage]
+pub struct Erc721Pausable {
+    /// [`Erc721`] contract.
+    pub erc721: Erc721,
+    /// [`Pausable`] contract.
+    pub pausable: Pausable,
+}
+
+impl Deref for Erc721Pausable {
+    type Target = Erc721;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721
+    }
+}
+
+impl DerefMut for Erc721Pausable {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc721Pausable {}
+
+#[public]
+impl IErc721 for Erc721Pausable {
+    type Error = Error;
+
+    fn balance_of(&self, owner: Address) -> Result<U256, Error> {
+        Ok(self.erc721.balance_of(owner)?)
+    }
+
+    fn owner_of(&self, token_id: U256) -> Result<Address, Error> {
+        Ok(self.erc721.owner_of(token_id)?)
+    }
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        self.safe_transfer_from_with_data(from, to, token_id, vec![].into())
+    }
+
+    #[selector(name = "safeTransferFrom")]
+    fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        self.transfer_from(from, to, token_id)?;
+        Ok(self.erc721._check_on_erc721_received(
+            msg::sender(),
+            from,
+            to,
+            token_id,
+            &data,
+        )?)
+    }
+
+    fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        // Setting an "auth" argument enables the `_is_authorized` check which
+        // verifies that the token exists (`from != 0`). Therefore, it is
+        // not needed to verify that the return value is not 0 here.
+        let previous_owner = self._update(to, token_id, msg::sender())?;
+        if previous_owner != from {
+            return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    fn approve(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
+        Ok(self.erc721.approve(to, token_id)?)
+    }
+
+    fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Error> {
+        Ok(self.erc721.set_approval_for_all(operator, approved)?)
+    }
+
+    fn get_approved(&self, token_id: U256) -> Result<Address, Error> {
+        Ok(self.erc721.get_approved(token_id)?)
+    }
+
+    fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
+        self.erc721.is_approved_for_all(owner, operator)
+    }
+}
+
+impl IErc721Burnable for Erc721Pausable {
+    type Error = Error;
+
+    fn burn(&mut self, token_id: U256) -> Result<(), Error> {
+        // Setting an "auth" arguments enables the
+        // [`Erc721::_is_authorized`] check which verifies that the token
+        // exists (from != `Address::ZERO`).
+        //
+        // Therefore, it is not needed to verify that the return value is not 0
+        // here.
+        self._update(Address::ZERO, token_id, msg::sender())?;
+        Ok(())
+    }
+}
+
+impl IErc165 for Erc721Pausable {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        Erc721::supports_interface(interface_id)
+    }
+}
+
+impl Erc721Pausable {
+    /// Extended version of [`Erc721::_update`] that reverts while the
+    /// contract is paused.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `auth` - Account used for authorization of the update.
+    ///
+    /// # Errors
+    ///
+    /// * [`pausable::Error::EnforcedPause`] - If the contract is paused.
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist and
+    ///   `auth` is not `Address::ZERO`.
+    /// * [`erc721::Error::InsufficientApproval`] - If `auth` is not
+    ///   `Address::ZERO` and `auth` does not have a right to approve this
+    ///   token.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    pub fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Error> {
+        self.pausable.when_not_paused()?;
+        Ok(self.erc721._update(to, token_id, auth)?)
+    }
+
+    /// Mints `token_id` and transfers it to `to`.
+    ///
+    /// Re-export of [`Erc721::_mint`] that reverts while paused.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if !previous_owner.is_zero() {
+            return Err(erc721::Error::InvalidSender(ERC721InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Mints `token_id`, transfers it to `to`, and checks for `to`'s
+    /// acceptance.
+    ///
+    /// Re-export of [`Erc721::_safe_mint`] that reverts while paused.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _safe_mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        self._mint(to, token_id)?;
+        Ok(self.erc721._check_on_erc721_received(
+            msg::sender(),
+            Address::ZERO,
+            to,
+            token_id,
+            data,
+        )?)
+    }
+
+    /// Destroys `token_id`.
+    ///
+    /// Re-export of [`Erc721::_burn`] that reverts while paused.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(&mut self, token_id: U256) -> Result<(), Error> {
+        let previous_owner =
+            self._update(Address::ZERO, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(erc721::Error::NonexistentToken(
+                ERC721NonexistentToken { token_id },
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Transfers `token_id` from `from` to `to`.
+    ///
+    /// Re-export of [`Erc721::_transfer`] that reverts while paused.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(erc721::Error::NonexistentToken(
+                ERC721NonexistentToken { token_id },
+            )
+            .into());
+        } else if previous_owner != from {
+            return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            })
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+
+    use super::{Erc721Pausable, Error};
+    use crate::{
+        token::erc721::{extensions::IErc721Burnable, IErc721},
+        utils::pausable,
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    #[motsu::test]
+    fn every_update_reverts_when_paused(
+        contract: Contract<Erc721Pausable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract.sender(alice).pausable.pause().expect("should pause");
+
+        let err = contract
+            .sender(alice)
+            ._mint(alice, uint!(2_U256))
+            .expect_err("should not mint while paused");
+        assert!(matches!(
+            err,
+            Error::Pausable(pausable::Error::EnforcedPause(_))
+        ));
+
+        let err = contract
+            .sender(alice)
+            .transfer_from(alice, bob, TOKEN_ID)
+            .expect_err("should not transfer while paused");
+        assert!(matches!(
+            err,
+            Error::Pausable(pausable::Error::EnforcedPause(_))
+        ));
+
+        let err = contract
+            .sender(alice)
+            .burn(TOKEN_ID)
+            .expect_err("should not burn while paused");
+        assert!(matches!(
+            err,
+            Error::Pausable(pausable::Error::EnforcedPause(_))
+        ));
+
+        assert_eq!(
+            alice,
+            contract.sender(alice).owner_of(TOKEN_ID).expect("should exist")
+        );
+    }
+
+    #[motsu::test]
+    fn transfers_after_unpause(
+        contract: Contract<Erc721Pausable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract.sender(alice).pausable.pause().expect("should pause");
+        contract.sender(alice).pausable.unpause().expect("should unpause");
+
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, TOKEN_ID)
+            .expect("should transfer");
+        assert_eq!(
+            bob,
+            contract.sender(alice).owner_of(TOKEN_ID).expect("should exist")
+        );
+    }
+}
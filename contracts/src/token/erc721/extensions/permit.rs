@@ -0,0 +1,694 @@
+//! Permit Contract.
+//!
+//! Extension of the ERC-721 standard allowing approvals to be made
+//! via signatures, as defined in the [ERC].
+//!
+//! Adds the `permit` method, which can be used to approve a `spender` for a
+//! single token (see [`crate::token::erc721::IErc721::get_approved`]) by
+//! presenting a message signed by the owner of the token. By not relying on
+//! [`erc721::IErc721::approve`], the token holder account doesn’t need to
+//! send a transaction, and thus is not required to hold Ether at all.
+//!
+//! Nonces are tracked per token, and the nonce of a token is incremented
+//! every time the token is transferred or a permit for it is used. If the
+//! owner of the token is a contract, the signature is validated through
+//! [ERC-1271] instead of `ecrecover`.
+//!
+//! [ERC]: https://eips.ethereum.org/EIPS/eip-4494
+//! [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{keccak256, Address, FixedBytes, B256, U256};
+use alloy_sol_types::SolType;
+use stylus_sdk::{
+    abi::Bytes,
+    block,
+    call::Call,
+    function_selector, msg,
+    prelude::{storage, AddressVM, StorageType},
+    storage::{StorageMap, StorageU256, TopLevelStorage},
+    stylus_proc::{public, SolidityError},
+};
+
+use crate::{
+    token::erc721::{
+        self, ERC721IncorrectOwner, ERC721InvalidReceiver, ERC721InvalidSender,
+        ERC721NonexistentToken, Erc721, IErc721,
+    },
+    utils::{
+        cryptography::{ecdsa, eip712::IEip712},
+        introspection::erc165::IErc165,
+    },
+};
+
+const PERMIT_TYPEHASH: [u8; 32] =
+    keccak_const::Keccak256::new()
+        .update(b"Permit(address spender,uint256 tokenId,uint256 nonce,uint256 deadline)")
+        .finalize();
+
+/// The value returned by [`IERC1271::is_valid_signature`] for a valid
+/// signature.
+pub const ERC1271_MAGIC_VALUE: [u8; 4] =
+    function_selector!("isValidSignature", B256, Bytes);
+
+/// Interface id of the ERC-4494 standard.
+pub const ERC4494_INTERFACE_ID: u32 =
+    u32::from_be_bytes(function_selector!(
+        "permit", Address, U256, U256, Bytes
+    )) ^ u32::from_be_bytes(function_selector!("nonces", U256))
+        ^ u32::from_be_bytes(function_selector!("DOMAIN_SEPARATOR"));
+
+pub use sol::*;
+pub use wallet::IERC1271;
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    pub(crate) type StructHashTuple = sol! {
+        tuple(bytes32, address, uint256, uint256, uint256)
+    };
+
+    sol! {
+        /// Indicates an error related to the fact that
+        /// permit deadline has expired.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4494ExpiredSignature(uint256 deadline);
+
+        /// Indicates an error related to the issue about a signature that
+        /// was not made by the `owner` of the token.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC4494InvalidSignature(address owner);
+    }
+}
+
+mod wallet {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::stylus_proc::sol_interface;
+
+    sol_interface! {
+        /// Interface of a contract wallet that validates signatures, as
+        /// defined in ERC-1271.
+        interface IERC1271 {
+            function isValidSignature(bytes32 hash, bytes calldata signature) external view returns (bytes4);
+        }
+    }
+}
+
+/// A Permit error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates an error related to the fact that
+    /// permit deadline has expired.
+    ExpiredSignature(ERC4494ExpiredSignature),
+    /// Indicates an error related to the issue about a signature that
+    /// was not made by the `owner` of the token.
+    InvalidSignature(ERC4494InvalidSignature),
+    /// Error type from [`Erc721`] contract [`erc721::Error`].
+    Erc721(erc721::Error),
+    /// Error type from [`ecdsa`] contract [`ecdsa::Error`].
+    ECDSA(ecdsa::Error),
+}
+
+/// State of an [`Erc721Permit`] Contract.
+#[storage]
+pub struct Erc721Permit<T: IEip712 + StorageType> {
+    /// [`Erc721`] contract.
+    // We leave the parent ERC-721 contract instance public, so that
+    // inheritting contract have access to its internal functions.
+    pub erc721: Erc721,
+    /// Mapping from token id to its nonce.
+    pub(crate) nonces: StorageMap<U256, StorageU256>,
+    /// Contract implementing [`IEip712`] trait.
+    pub(crate) eip712: T,
+}
+
+impl<T: IEip712 + StorageType> Deref for Erc721Permit<T> {
+    type Target = Erc721;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721
+    }
+}
+
+impl<T: IEip712 + StorageType> DerefMut for Erc721Permit<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl<T: IEip712 + StorageType> TopLevelStorage for Erc721Permit<T> {}
+
+#[public]
+impl<T: IEip712 + StorageType> Erc721Permit<T> {
+    /// Returns the current nonce for `token_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    #[must_use]
+    pub fn nonces(&self, token_id: U256) -> U256 {
+        self.nonces.get(token_id)
+    }
+
+    /// Returns the domain separator used in the encoding of the signature for
+    /// [`Self::permit`], as defined by EIP712.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[selector(name = "DOMAIN_SEPARATOR")]
+    #[must_use]
+    pub fn domain_separator(&self) -> B256 {
+        self.eip712.domain_separator_v4()
+    }
+
+    /// Approves `spender` to operate on `token_id`, given the signed approval
+    /// of the owner of the token.
+    ///
+    /// The signature is a 65 bytes `r`, `s`, `v` ECDSA signature, unless the
+    /// owner of the token is a contract, in which case it is validated
+    /// through [`IERC1271::is_valid_signature`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `spender` - Account that will operate on the token.
+    /// * `token_id` - Token id as a number.
+    /// * `deadline` - Deadline for the permit action.
+    /// * `signature` - Signature of the owner of the token.
+    ///
+    /// # Errors
+    ///
+    /// * [`ERC4494ExpiredSignature`] - If the `deadline` param is from the
+    ///   past.
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    /// * [`ERC4494InvalidSignature`] - If the signature was not made by the
+    ///   owner of the token, or is malformed.
+    /// * [`ecdsa::Error::InvalidSignatureS`] - If the `s` value is grater than
+    ///   [`ecdsa::SIGNATURE_S_UPPER_BOUND`].
+    /// * [`ecdsa::Error::InvalidSignature`] - If the recovered address is
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Approval`]
+    pub fn permit(
+        &mut self,
+        spender: Address,
+        token_id: U256,
+        deadline: U256,
+        signature: Bytes,
+    ) -> Result<(), Error> {
+        if U256::from(block::timestamp()) > deadline {
+            return Err(ERC4494ExpiredSignature { deadline }.into());
+        }
+
+        let owner = self.erc721._require_owned(token_id)?;
+
+        let struct_hash = keccak256(StructHashTuple::abi_encode(&(
+            PERMIT_TYPEHASH,
+            spender,
+            token_id,
+            self._use_nonce(token_id),
+            deadline,
+        )));
+
+        let hash: B256 = self.eip712.hash_typed_data_v4(struct_hash);
+
+        if !self._is_valid_signature(owner, hash, signature)? {
+            return Err(ERC4494InvalidSignature { owner }.into());
+        }
+
+        self.erc721._approve(spender, token_id, Address::ZERO, true)?;
+
+        Ok(())
+    }
+
+    /// Returns the number of tokens in `owner`'s account.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account of the token's owner.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidOwner`] - If owner address is `Address::ZERO`.
+    pub fn balance_of(&self, owner: Address) -> Result<U256, erc721::Error> {
+        self.erc721.balance_of(owner)
+    }
+
+    /// Returns the owner of the `token_id` token.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    pub fn owner_of(&self, token_id: U256) -> Result<Address, erc721::Error> {
+        self.erc721.owner_of(token_id)
+    }
+
+    /// Safely transfers `token_id` token from `from` to `to`, and increments
+    /// its nonce.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account of the sender.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::safe_transfer_from_with_data`].
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    pub fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.safe_transfer_from_with_data(from, to, token_id, vec![].into())
+    }
+
+    /// Safely transfers `token_id` token from `from` to `to`, and increments
+    /// its nonce.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account of the sender.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `data` - Additional data with no specified format, sent in the call to
+    ///   `to`.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`, or it
+    ///   does not accept the token.
+    /// * [`erc721::Error::IncorrectOwner`] - If `from` is not the owner of the
+    ///   token.
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    /// * [`erc721::Error::InsufficientApproval`] - If the caller is not
+    ///   approved to move the token.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    #[selector(name = "safeTransferFrom")]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), erc721::Error> {
+        self.transfer_from(from, to, token_id)?;
+        self.erc721._check_on_erc721_received(
+            msg::sender(),
+            from,
+            to,
+            token_id,
+            &data,
+        )
+    }
+
+    /// Transfers `token_id` token from `from` to `to`, and increments its
+    /// nonce.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account of the sender.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    /// * [`erc721::Error::IncorrectOwner`] - If `from` is not the owner of the
+    ///   token.
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    /// * [`erc721::Error::InsufficientApproval`] - If the caller is not
+    ///   approved to move the token.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        if to.is_zero() {
+            return Err(
+                ERC721InvalidReceiver { receiver: Address::ZERO }.into()
+            );
+        }
+
+        // Setting an "auth" argument enables the `_is_authorized` check which
+        // verifies that the token exists (`from != 0`). Therefore, it is
+        // not needed to verify that the return value is not 0 here.
+        let previous_owner = self._update(to, token_id, msg::sender())?;
+        if previous_owner != from {
+            return Err(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Gives permission to `to` to transfer `token_id` token to another
+    /// account.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    /// * [`erc721::Error::InvalidApprover`] - If the caller is neither the
+    ///   owner of the token nor an approved operator.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Approval`].
+    pub fn approve(
+        &mut self,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.approve(to, token_id)
+    }
+
+    /// Approves or removes `operator` as an operator for the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `operator` - Account to add to the set of authorized operators.
+    /// * `approved` - Flag that determines whether or not permission will be
+    ///   granted to `operator`.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidOperator`] - If `operator` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::ApprovalForAll`].
+    pub fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.set_approval_for_all(operator, approved)
+    }
+
+    /// Returns the account approved for `token_id` token.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    pub fn get_approved(
+        &self,
+        token_id: U256,
+    ) -> Result<Address, erc721::Error> {
+        self.erc721.get_approved(token_id)
+    }
+
+    /// Returns whether the `operator` is allowed to manage all the assets of
+    /// `owner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account of the token's owner.
+    /// * `operator` - Account to be checked.
+    pub fn is_approved_for_all(
+        &self,
+        owner: Address,
+        operator: Address,
+    ) -> bool {
+        self.erc721.is_approved_for_all(owner, operator)
+    }
+}
+
+impl<T: IEip712 + StorageType> IErc165 for Erc721Permit<T> {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        ERC4494_INTERFACE_ID == u32::from_be_bytes(*interface_id)
+            || Erc721::supports_interface(interface_id)
+    }
+}
+
+impl<T: IEip712 + StorageType> Erc721Permit<T> {
+    /// Consumes the nonce of `token_id`, returning its current value.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    fn _use_nonce(&mut self, token_id: U256) -> U256 {
+        let nonce = self.nonces.get(token_id);
+        // Nonces can't realistically overflow.
+        self.nonces.setter(token_id).set(nonce + U256::from(1));
+        nonce
+    }
+
+    /// Returns whether `signature` of `hash` was made by `owner`.
+    ///
+    /// Contract owners are asked through [`IERC1271::is_valid_signature`],
+    /// and the signature of any other owner is recovered with
+    /// [`ecdsa::recover`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - Account of the token's owner.
+    /// * `hash` - EIP-712 hash of the permit.
+    /// * `signature` - Signature of the owner of the token.
+    ///
+    /// # Errors
+    ///
+    /// * [`ecdsa::Error::InvalidSignatureS`] - If the `s` value is grater than
+    ///   [`ecdsa::SIGNATURE_S_UPPER_BOUND`].
+    /// * [`ecdsa::Error::InvalidSignature`] - If the recovered address is
+    ///   `Address::ZERO`.
+    fn _is_valid_signature(
+        &mut self,
+        owner: Address,
+        hash: B256,
+        signature: Bytes,
+    ) -> Result<bool, Error> {
+        if owner.has_code() {
+            let wallet = IERC1271::new(owner);
+            let result = wallet.is_valid_signature(
+                Call::new_in(self),
+                hash,
+                signature.0.into(),
+            );
+            return Ok(result.is_ok_and(|value| value == ERC1271_MAGIC_VALUE));
+        }
+
+        if signature.len() != 65 {
+            return Ok(false);
+        }
+
+        let r = B256::from_slice(&signature[0..32]);
+        let s = B256::from_slice(&signature[32..64]);
+        let v = signature[64];
+        let signer = ecdsa::recover(self, hash, v, r, s)?;
+        Ok(signer == owner)
+    }
+
+    /// Extended version of [`Erc721::_update`] that increments the nonce of
+    /// `token_id`, invalidating the permits signed for its previous owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `auth` - Account used for authorization of the update.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist and
+    ///   `auth` is not `Address::ZERO`.
+    /// * [`erc721::Error::InsufficientApproval`] - If `auth` is not
+    ///   `Address::ZERO` and `auth` does not have a right to approve this
+    ///   token.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    pub fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, erc721::Error> {
+        let previous_owner = self.erc721._update(to, token_id, auth)?;
+        self._use_nonce(token_id);
+        Ok(previous_owner)
+    }
+
+    /// Mints `token_id` and transfers it to `to`.
+    ///
+    /// Re-export of [`Erc721::_mint`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        if to.is_zero() {
+            return Err(
+                ERC721InvalidReceiver { receiver: Address::ZERO }.into()
+            );
+        }
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if !previous_owner.is_zero() {
+            return Err(ERC721InvalidSender { sender: Address::ZERO }.into());
+        }
+        Ok(())
+    }
+
+    /// Destroys `token_id`.
+    ///
+    /// Re-export of [`Erc721::_burn`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(&mut self, token_id: U256) -> Result<(), erc721::Error> {
+        let previous_owner =
+            self._update(Address::ZERO, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(ERC721NonexistentToken { token_id }.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::{block, prelude::storage};
+
+    use super::{Erc721Permit, Error, ERC4494_INTERFACE_ID};
+    use crate::{
+        token::erc721,
+        utils::{
+            cryptography::eip712::IEip712, introspection::erc165::IErc165,
+        },
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    #[storage]
+    struct Eip712 {}
+
+    impl IEip712 for Eip712 {
+        const NAME: &'static str = "ERC-721 Permit Test";
+        const VERSION: &'static str = "1";
+    }
+
+    #[motsu::test]
+    fn transfer_increments_nonce(
+        contract: Contract<Erc721Permit<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        let nonce = contract.sender(alice).nonces(TOKEN_ID);
+
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, TOKEN_ID)
+            .expect("should transfer");
+
+        assert_eq!(
+            nonce + uint!(1_U256),
+            contract.sender(alice).nonces(TOKEN_ID)
+        );
+    }
+
+    #[motsu::test]
+    fn error_when_deadline_expired(
+        contract: Contract<Erc721Permit<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        let deadline = U256::from(block::timestamp() - 1);
+
+        let err = contract
+            .sender(bob)
+            .permit(bob, TOKEN_ID, deadline, vec![0; 65].into())
+            .expect_err("should not accept an expired permit");
+
+        assert!(matches!(err, Error::ExpiredSignature(_)));
+    }
+
+    #[motsu::test]
+    fn error_when_token_does_not_exist(
+        contract: Contract<Erc721Permit<Eip712>>,
+        bob: Address,
+    ) {
+        let deadline = U256::from(block::timestamp() + 1);
+
+        let err = contract
+            .sender(bob)
+            .permit(bob, TOKEN_ID, deadline, vec![0; 65].into())
+            .expect_err("should not permit a nonexistent token");
+
+        assert!(matches!(
+            err,
+            Error::Erc721(erc721::Error::NonexistentToken(_))
+        ));
+    }
+
+    #[motsu::test]
+    fn interface_id() {
+        assert_eq!(0x5604_e225, ERC4494_INTERFACE_ID);
+        assert!(Erc721Permit::<Eip712>::supports_interface(
+            0x5604_e225_u32.into()
+        ));
+        assert!(Erc721Permit::<Eip712>::supports_interface(
+            0x80ac_58cd_u32.into()
+        ));
+    }
+}
@@ -0,0 +1,512 @@
+//! Extension of ERC-721 for rentable tokens, as defined in [ERC-4907].
+//!
+//! Adds a "user" role to each token, which can be granted by the owner, or an
+//! approved account, until an expiry timestamp. The user can use the token,
+//! e.g. in a game, but can not transfer it. The user is cleared when the token
+//! is transferred.
+//!
+//! [ERC-4907]: https://eips.ethereum.org/EIPS/eip-4907
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256, U64};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    block, evm, msg,
+    prelude::{storage, TopLevelStorage},
+    storage::{StorageAddress, StorageMap, StorageU64},
+    stylus_proc::public,
+};
+
+use crate::{
+    token::erc721::{
+        self, ERC721IncorrectOwner, ERC721InvalidReceiver, ERC721InvalidSender,
+        ERC721NonexistentToken, Erc721, IErc721,
+    },
+    utils::introspection::erc165::IErc165,
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the `user` of an NFT or the `expires` of the `user`
+        /// is changed. The zero address for `user` indicates that there is
+        /// no user.
+        ///
+        /// * `token_id` - Token id as a number.
+        /// * `user` - New user of the token.
+        /// * `expires` - UNIX timestamp until which `user` can use the token.
+        #[allow(missing_docs)]
+        event UpdateUser(
+            uint256 indexed token_id,
+            address indexed user,
+            uint64 expires
+        );
+    }
+}
+
+/// User of a token and the expiry of its role.
+#[storage]
+pub struct UserInfo {
+    /// Account that can use the token.
+    pub(crate) user: StorageAddress,
+    /// UNIX timestamp until which `user` can use the token.
+    pub(crate) expires: StorageU64,
+}
+
+/// State of an [`Erc721Rentable`] token.
+#[storage]
+pub struct Erc721Rentable {
+    /// [`Erc721`] contract.
+    pub erc721: Erc721,
+    /// Mapping from token id to its user.
+    pub(crate) users: StorageMap<U256, UserInfo>,
+}
+
+impl Deref for Erc721Rentable {
+    type Target = Erc721;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721
+    }
+}
+
+impl DerefMut for Erc721Rentable {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc721Rentable {}
+
+/// Interface of the Rental NFT standard.
+#[interface_id]
+pub trait IErc4907 {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Sets the `user` and `expires` of `token_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    /// * `user` - New user of the token.
+    /// * `expires` - UNIX timestamp until which `user` can use the token.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    /// * [`erc721::Error::InsufficientApproval`] - If the caller is neither the
+    ///   owner of the token nor approved to operate on it.
+    ///
+    /// # Events
+    ///
+    /// * [`UpdateUser`].
+    fn set_user(
+        &mut self,
+        token_id: U256,
+        user: Address,
+        expires: U64,
+    ) -> Result<(), Self::Error>;
+
+    /// Returns the user of `token_id`, or `Address::ZERO` if there is no user
+    /// or the role has expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    fn user_of(&self, token_id: U256) -> Address;
+
+    /// Returns the UNIX timestamp until which the user can use `token_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    fn user_expires(&self, token_id: U256) -> U256;
+}
+
+#[public]
+impl IErc721 for Erc721Rentable {
+    type Error = erc721::Error;
+
+    fn balance_of(&self, owner: Address) -> Result<U256, erc721::Error> {
+        self.erc721.balance_of(owner)
+    }
+
+    fn owner_of(&self, token_id: U256) -> Result<Address, erc721::Error> {
+        self.erc721.owner_of(token_id)
+    }
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.safe_transfer_from_with_data(from, to, token_id, vec![].into())
+    }
+
+    #[selector(name = "safeTransferFrom")]
+    fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), erc721::Error> {
+        self.transfer_from(from, to, token_id)?;
+        self.erc721._check_on_erc721_received(
+            msg::sender(),
+            from,
+            to,
+            token_id,
+            &data,
+        )
+    }
+
+    fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        if to.is_zero() {
+            return Err(
+                ERC721InvalidReceiver { receiver: Address::ZERO }.into()
+            );
+        }
+
+        // Setting an "auth" argument enables the `_is_authorized` check which
+        // verifies that the token exists (`from != 0`). Therefore, it is
+        // not needed to verify that the return value is not 0 here.
+        let previous_owner = self._update(to, token_id, msg::sender())?;
+        if previous_owner != from {
+            return Err(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    fn approve(
+        &mut self,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.approve(to, token_id)
+    }
+
+    fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.set_approval_for_all(operator, approved)
+    }
+
+    fn get_approved(&self, token_id: U256) -> Result<Address, erc721::Error> {
+        self.erc721.get_approved(token_id)
+    }
+
+    fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
+        self.erc721.is_approved_for_all(owner, operator)
+    }
+}
+
+impl IErc4907 for Erc721Rentable {
+    type Error = erc721::Error;
+
+    fn set_user(
+        &mut self,
+        token_id: U256,
+        user: Address,
+        expires: U64,
+    ) -> Result<(), erc721::Error> {
+        let owner = self.erc721._require_owned(token_id)?;
+        self.erc721._check_authorized(owner, msg::sender(), token_id)?;
+        self._set_user(token_id, user, expires);
+        Ok(())
+    }
+
+    fn user_of(&self, token_id: U256) -> Address {
+        let info = self.users.get(token_id);
+        if U64::from(block::timestamp()) <= info.expires.get() {
+            info.user.get()
+        } else {
+            Address::ZERO
+        }
+    }
+
+    fn user_expires(&self, token_id: U256) -> U256 {
+        U256::from(self.users.get(token_id).expires.get())
+    }
+}
+
+impl IErc165 for Erc721Rentable {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IErc4907>::INTERFACE_ID == u32::from_be_bytes(*interface_id)
+            || Erc721::supports_interface(interface_id)
+    }
+}
+
+impl Erc721Rentable {
+    /// Sets the `user` and `expires` of `token_id`, without checking that
+    /// the token exists or that the caller is authorized.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    /// * `user` - New user of the token.
+    /// * `expires` - UNIX timestamp until which `user` can use the token.
+    ///
+    /// # Events
+    ///
+    /// * [`UpdateUser`].
+    pub fn _set_user(&mut self, token_id: U256, user: Address, expires: U64) {
+        let mut info = self.users.setter(token_id);
+        info.user.set(user);
+        info.expires.set(expires);
+        evm::log(UpdateUser { token_id, user, expires: expires.to() });
+    }
+
+    /// Extended version of [`Erc721::_update`] that clears the user of the
+    /// token when it changes owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `auth` - Account used for authorization of the update.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist and
+    ///   `auth` is not `Address::ZERO`.
+    /// * [`erc721::Error::InsufficientApproval`] - If `auth` is not
+    ///   `Address::ZERO` and `auth` does not have a right to approve this
+    ///   token.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    /// * [`UpdateUser`] - If the token had a user and changed owner.
+    pub fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, erc721::Error> {
+        let previous_owner = self.erc721._update(to, token_id, auth)?;
+
+        if previous_owner != to
+            && !self.users.get(token_id).user.get().is_zero()
+        {
+            self._set_user(token_id, Address::ZERO, U64::ZERO);
+        }
+
+        Ok(previous_owner)
+    }
+
+    /// Mints `token_id` and transfers it to `to`.
+    ///
+    /// Re-export of [`Erc721::_mint`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        if to.is_zero() {
+            return Err(
+                ERC721InvalidReceiver { receiver: Address::ZERO }.into()
+            );
+        }
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if !previous_owner.is_zero() {
+            return Err(ERC721InvalidSender { sender: Address::ZERO }.into());
+        }
+        Ok(())
+    }
+
+    /// Destroys `token_id` and clears its user.
+    ///
+    /// Re-export of [`Erc721::_burn`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(&mut self, token_id: U256) -> Result<(), erc721::Error> {
+        let previous_owner =
+            self._update(Address::ZERO, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(ERC721NonexistentToken { token_id }.into());
+        }
+        Ok(())
+    }
+
+    /// Transfers `token_id` from `from` to `to` and clears its user.
+    ///
+    /// Re-export of [`Erc721::_transfer`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        if to.is_zero() {
+            return Err(
+                ERC721InvalidReceiver { receiver: Address::ZERO }.into()
+            );
+        }
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(ERC721NonexistentToken { token_id }.into());
+        } else if previous_owner != from {
+            return Err(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256, U64};
+    use motsu::prelude::Contract;
+    use stylus_sdk::block;
+
+    use super::{Erc721Rentable, IErc4907};
+    use crate::{
+        token::erc721::{self, IErc721},
+        utils::introspection::erc165::IErc165,
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    fn in_one_day() -> U64 {
+        U64::from(block::timestamp() + 86_400)
+    }
+
+    #[motsu::test]
+    fn owner_sets_user_until_expiry(
+        contract: Contract<Erc721Rentable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        let expires = in_one_day();
+
+        contract
+            .sender(alice)
+            .set_user(TOKEN_ID, bob, expires)
+            .expect("should set the user");
+
+        assert_eq!(bob, contract.sender(alice).user_of(TOKEN_ID));
+        assert_eq!(
+            U256::from(expires),
+            contract.sender(alice).user_expires(TOKEN_ID)
+        );
+    }
+
+    #[motsu::test]
+    fn expired_user_is_not_returned(
+        contract: Contract<Erc721Rentable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        let expired = U64::from(block::timestamp() - 1);
+
+        contract
+            .sender(alice)
+            .set_user(TOKEN_ID, bob, expired)
+            .expect("should set the user");
+
+        assert_eq!(Address::ZERO, contract.sender(alice).user_of(TOKEN_ID));
+        assert_eq!(
+            U256::from(expired),
+            contract.sender(alice).user_expires(TOKEN_ID)
+        );
+    }
+
+    #[motsu::test]
+    fn error_when_caller_is_not_authorized(
+        contract: Contract<Erc721Rentable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let err = contract
+            .sender(bob)
+            .set_user(TOKEN_ID, bob, in_one_day())
+            .expect_err("should not set the user of a nonexistent token");
+        assert!(matches!(err, erc721::Error::NonexistentToken(_)));
+
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        let err = contract
+            .sender(bob)
+            .set_user(TOKEN_ID, bob, in_one_day())
+            .expect_err("should not set the user without approval");
+        assert!(matches!(err, erc721::Error::InsufficientApproval(_)));
+
+        contract.sender(alice).approve(bob, TOKEN_ID).expect("should approve");
+        contract
+            .sender(bob)
+            .set_user(TOKEN_ID, bob, in_one_day())
+            .expect("should set the user when approved");
+        assert_eq!(bob, contract.sender(alice).user_of(TOKEN_ID));
+    }
+
+    #[motsu::test]
+    fn transfer_clears_user(
+        contract: Contract<Erc721Rentable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract
+            .sender(alice)
+            .set_user(TOKEN_ID, bob, in_one_day())
+            .expect("should set the user");
+
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, TOKEN_ID)
+            .expect("should transfer");
+
+        assert_eq!(Address::ZERO, contract.sender(alice).user_of(TOKEN_ID));
+        assert_eq!(U256::ZERO, contract.sender(alice).user_expires(TOKEN_ID));
+    }
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <Erc721Rentable as IErc4907>::INTERFACE_ID;
+        let expected = 0xad09_2b5c;
+        assert_eq!(actual, expected);
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc721Rentable::supports_interface(0xad09_2b5c_u32.into()));
+        assert!(Erc721Rentable::supports_interface(0x80ac_58cd_u32.into()));
+        assert!(!Erc721Rentable::supports_interface(0x1234_5678_u32.into()));
+    }
+}
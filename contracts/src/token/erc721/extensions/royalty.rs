@@ -0,0 +1,342 @@
+//! Extension of ERC-721 with the ERC-2981 NFT Royalty Standard, a
+//! standardized way to retrieve royalty payment information.
+//!
+//! Royalty information can be specified globally for all token ids via
+//! [`Erc2981::_set_default_royalty`], and/or individually for specific token
+//! ids via [`Erc2981::_set_token_royalty`]. The royalty of a token is cleared
+//! when the token is burned.
+//!
+//! IMPORTANT: ERC-2981 only specifies a way to signal royalty information and
+//! does not enforce its payment.
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use stylus_sdk::{
+    abi::Bytes,
+    prelude::{storage, TopLevelStorage},
+    stylus_proc::public,
+};
+
+use crate::{
+    token::{
+        common::erc2981::{Erc2981, IErc2981},
+        erc721::{self, extensions::IErc721Burnable, Erc721, IErc721},
+    },
+    utils::introspection::erc165::IErc165,
+};
+
+/// State of an [`Erc721Royalty`] token.
+#[storage]
+pub struct Erc721Royalty {
+    /// [`Erc721`] contract.
+    pub erc721: Erc721,
+    /// [`Erc2981`] contract.
+    pub erc2981: Erc2981,
+}
+
+impl Deref for Erc721Royalty {
+    type Target = Erc721;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721
+    }
+}
+
+impl DerefMut for Erc721Royalty {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc721Royalty {}
+
+/// ABI of an [`Erc721Royalty`] token: the [`IErc721`] functions together with
+/// [`IErc2981::royalty_info`], since a contract can only export a single
+/// `#[public]` block.
+#[public]
+impl Erc721Royalty {
+    /// Re-export of [`IErc721::balance_of`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn balance_of(&self, owner: Address) -> Result<U256, erc721::Error> {
+        self.erc721.balance_of(owner)
+    }
+
+    /// Re-export of [`IErc721::owner_of`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn owner_of(&self, token_id: U256) -> Result<Address, erc721::Error> {
+        self.erc721.owner_of(token_id)
+    }
+
+    /// Re-export of [`IErc721::safe_transfer_from`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.safe_transfer_from_with_data(
+            from,
+            to,
+            token_id,
+            vec![].into(),
+        )
+    }
+
+    /// Re-export of [`IErc721::safe_transfer_from_with_data`].
+    #[allow(clippy::missing_errors_doc)]
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.safe_transfer_from_with_data(from, to, token_id, data)
+    }
+
+    /// Re-export of [`IErc721::transfer_from`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.transfer_from(from, to, token_id)
+    }
+
+    /// Re-export of [`IErc721::approve`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn approve(
+        &mut self,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.approve(to, token_id)
+    }
+
+    /// Re-export of [`IErc721::set_approval_for_all`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.set_approval_for_all(operator, approved)
+    }
+
+    /// Re-export of [`IErc721::get_approved`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn get_approved(
+        &self,
+        token_id: U256,
+    ) -> Result<Address, erc721::Error> {
+        self.erc721.get_approved(token_id)
+    }
+
+    /// Re-export of [`IErc721::is_approved_for_all`].
+    pub fn is_approved_for_all(
+        &self,
+        owner: Address,
+        operator: Address,
+    ) -> bool {
+        self.erc721.is_approved_for_all(owner, operator)
+    }
+
+    /// Re-export of [`IErc2981::royalty_info`].
+    pub fn royalty_info(
+        &self,
+        token_id: U256,
+        sale_price: U256,
+    ) -> (Address, U256) {
+        self.erc2981.royalty_info(token_id, sale_price)
+    }
+}
+
+impl IErc721 for Erc721Royalty {
+    type Error = erc721::Error;
+
+    fn balance_of(&self, owner: Address) -> Result<U256, erc721::Error> {
+        self.erc721.balance_of(owner)
+    }
+
+    fn owner_of(&self, token_id: U256) -> Result<Address, erc721::Error> {
+        self.erc721.owner_of(token_id)
+    }
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.safe_transfer_from_with_data(
+            from,
+            to,
+            token_id,
+            vec![].into(),
+        )
+    }
+
+    fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.safe_transfer_from_with_data(from, to, token_id, data)
+    }
+
+    fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.transfer_from(from, to, token_id)
+    }
+
+    fn approve(
+        &mut self,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.approve(to, token_id)
+    }
+
+    fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.set_approval_for_all(operator, approved)
+    }
+
+    fn get_approved(&self, token_id: U256) -> Result<Address, erc721::Error> {
+        self.erc721.get_approved(token_id)
+    }
+
+    fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
+        self.erc721.is_approved_for_all(owner, operator)
+    }
+}
+
+impl IErc2981 for Erc721Royalty {
+    fn royalty_info(
+        &self,
+        token_id: U256,
+        sale_price: U256,
+    ) -> (Address, U256) {
+        self.erc2981.royalty_info(token_id, sale_price)
+    }
+}
+
+impl IErc721Burnable for Erc721Royalty {
+    type Error = erc721::Error;
+
+    fn burn(&mut self, token_id: U256) -> Result<(), erc721::Error> {
+        self.erc721.burn(token_id)?;
+        self.erc2981._reset_token_royalty(token_id);
+        Ok(())
+    }
+}
+
+impl IErc165 for Erc721Royalty {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        Erc2981::supports_interface(interface_id)
+            || Erc721::supports_interface(interface_id)
+    }
+}
+
+impl Erc721Royalty {
+    /// Destroys `token_id` and clears its royalty information.
+    ///
+    /// Re-export of [`Erc721::_burn`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(&mut self, token_id: U256) -> Result<(), erc721::Error> {
+        self.erc721._burn(token_id)?;
+        self.erc2981._reset_token_royalty(token_id);
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+
+    use super::Erc721Royalty;
+    use crate::{
+        token::erc721::{extensions::IErc721Burnable, Erc721},
+        utils::introspection::erc165::IErc165,
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+    const SALE_PRICE: U256 = uint!(1_000_U256);
+
+    #[motsu::test]
+    fn burn_clears_token_royalty(
+        contract: Contract<Erc721Royalty>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract
+            .sender(alice)
+            .erc2981
+            ._set_default_royalty(bob, uint!(100_U96))
+            .expect("should set the default royalty");
+        contract
+            .sender(alice)
+            .erc2981
+            ._set_token_royalty(TOKEN_ID, alice, uint!(500_U96))
+            .expect("should set the token royalty");
+        assert_eq!(
+            (alice, uint!(50_U256)),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+
+        contract.sender(alice).burn(TOKEN_ID).expect("should burn");
+
+        assert_eq!(
+            (bob, uint!(10_U256)),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+    }
+
+    #[motsu::test]
+    fn internal_burn_clears_token_royalty(
+        contract: Contract<Erc721Royalty>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract
+            .sender(alice)
+            .erc2981
+            ._set_token_royalty(TOKEN_ID, alice, uint!(500_U96))
+            .expect("should set the token royalty");
+
+        contract.sender(alice)._burn(TOKEN_ID).expect("should burn");
+
+        assert_eq!(
+            (Address::ZERO, U256::ZERO),
+            contract.sender(alice).royalty_info(TOKEN_ID, SALE_PRICE)
+        );
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc721Royalty::supports_interface(0x2a55_205a_u32.into()));
+        assert!(Erc721Royalty::supports_interface(
+            (<Erc721 as crate::token::erc721::IErc721>::INTERFACE_ID).into()
+        ));
+        assert!(!Erc721Royalty::supports_interface(0x1234_5678_u32.into()));
+    }
+}
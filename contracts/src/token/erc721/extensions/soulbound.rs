@@ -0,0 +1,681 @@
+//! Extension of ERC-721 for soulbound (non-transferable) tokens, as defined
+//! in [ERC-5192].
+//!
+//! A token can be locked individually, through
+//! [`Erc721Soulbound::_lock`], or the whole collection can be locked through
+//! [`Erc721Soulbound::_lock_collection`] before the first mint. Locked tokens
+//! can be minted and burned, but they can not be transferred or approved.
+//!
+//! The internal transfer and approval functions of [`Erc721`] are shadowed to
+//! enforce the lock policy. Calling them on the `erc721` field directly skips
+//! the lock checks.
+//!
+//! [ERC-5192]: https://eips.ethereum.org/EIPS/eip-5192
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    evm, msg,
+    prelude::{storage, TopLevelStorage},
+    storage::{StorageBool, StorageMap},
+    stylus_proc::{public, SolidityError},
+};
+
+use crate::{
+    token::erc721::{
+        self, ERC721IncorrectOwner, ERC721InvalidReceiver, ERC721InvalidSender,
+        ERC721NonexistentToken, Erc721, IErc721,
+    },
+    utils::introspection::erc165::IErc165,
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the locking status is changed to locked.
+        ///
+        /// * `token_id` - Token id as a number.
+        #[allow(missing_docs)]
+        event Locked(uint256 token_id);
+
+        /// Emitted when the locking status is changed to unlocked.
+        ///
+        /// * `token_id` - Token id as a number.
+        #[allow(missing_docs)]
+        event Unlocked(uint256 token_id);
+    }
+
+    sol! {
+        /// Indicates that a locked token was transferred or approved.
+        ///
+        /// * `token_id` - Token id as a number.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC5192LockedToken(uint256 token_id);
+
+        /// Indicates that an operator was approved while the collection is
+        /// locked.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC5192LockedCollection();
+
+        /// Indicates that the collection was locked after a token was minted.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC5192CollectionAlreadyMinted();
+    }
+}
+
+/// An [`Erc721Soulbound`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Error type from [`Erc721`] contract [`erc721::Error`].
+    Erc721(erc721::Error),
+    /// Indicates that a locked token was transferred or approved.
+    LockedToken(ERC5192LockedToken),
+    /// Indicates that an operator was approved while the collection is
+    /// locked.
+    LockedCollection(ERC5192LockedCollection),
+    /// Indicates that the collection was locked after a token was minted.
+    CollectionAlreadyMinted(ERC5192CollectionAlreadyMinted),
+}
+
+/// State of an [`Erc721Soulbound`] token.
+#[storage]
+pub struct Erc721Soulbound {
+    /// [`Erc721`] contract.
+    pub erc721: Erc721,
+    /// Whether every token of the collection is locked.
+    pub(crate) collection_locked: StorageBool,
+    /// Whether a token of the collection was ever minted.
+    pub(crate) minted: StorageBool,
+    /// Mapping from token id to whether the token is locked.
+    pub(crate) token_locked: StorageMap<U256, StorageBool>,
+}
+
+impl Deref for Erc721Soulbound {
+    type Target = Erc721;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721
+    }
+}
+
+impl DerefMut for Erc721Soulbound {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc721Soulbound {}
+
+/// Interface of the Minimal Soulbound NFTs standard.
+#[interface_id]
+pub trait IErc5192 {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Returns the locking status of a soulbound token.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    fn locked(&self, token_id: U256) -> Result<bool, Self::Error>;
+}
+
+#[public]
+impl IErc721 for Erc721Soulbound {
+    type Error = Error;
+
+    fn balance_of(&self, owner: Address) -> Result<U256, Error> {
+        Ok(self.erc721.balance_of(owner)?)
+    }
+
+    fn owner_of(&self, token_id: U256) -> Result<Address, Error> {
+        Ok(self.erc721.owner_of(token_id)?)
+    }
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        self.safe_transfer_from_with_data(from, to, token_id, vec![].into())
+    }
+
+    #[selector(name = "safeTransferFrom")]
+    fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        self.transfer_from(from, to, token_id)?;
+        Ok(self.erc721._check_on_erc721_received(
+            msg::sender(),
+            from,
+            to,
+            token_id,
+            &data,
+        )?)
+    }
+
+    fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        // Setting an "auth" argument enables the `_is_authorized` check which
+        // verifies that the token exists (`from != 0`). Therefore, it is
+        // not needed to verify that the return value is not 0 here.
+        let previous_owner = self._update(to, token_id, msg::sender())?;
+        if previous_owner != from {
+            return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    fn approve(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
+        if self._is_locked(token_id) {
+            return Err(ERC5192LockedToken { token_id }.into());
+        }
+        Ok(self.erc721.approve(to, token_id)?)
+    }
+
+    fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Error> {
+        self._set_approval_for_all(msg::sender(), operator, approved)
+    }
+
+    fn get_approved(&self, token_id: U256) -> Result<Address, Error> {
+        Ok(self.erc721.get_approved(token_id)?)
+    }
+
+    fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
+        self.erc721.is_approved_for_all(owner, operator)
+    }
+}
+
+impl IErc5192 for Erc721Soulbound {
+    type Error = Error;
+
+    fn locked(&self, token_id: U256) -> Result<bool, Error> {
+        self.erc721._require_owned(token_id)?;
+        Ok(self._is_locked(token_id))
+    }
+}
+
+impl IErc165 for Erc721Soulbound {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IErc5192>::INTERFACE_ID == u32::from_be_bytes(*interface_id)
+            || Erc721::supports_interface(interface_id)
+    }
+}
+
+impl Erc721Soulbound {
+    /// Returns whether `token_id` is locked, either individually or because
+    /// the whole collection is locked. Does NOT revert if the token doesn't
+    /// exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    #[must_use]
+    pub fn _is_locked(&self, token_id: U256) -> bool {
+        self.collection_locked.get() || self.token_locked.get(token_id)
+    }
+
+    /// Permanently locks every token of the collection. A [`Locked`] event is
+    /// emitted for each token minted afterwards.
+    ///
+    /// The collection can only be locked before the first mint, since ERC-5192
+    /// requires an event for every token whose locking status changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::CollectionAlreadyMinted`] - If a token was already minted.
+    pub fn _lock_collection(&mut self) -> Result<(), Error> {
+        if self.minted.get() {
+            return Err(ERC5192CollectionAlreadyMinted {}.into());
+        }
+        self.collection_locked.set(true);
+        Ok(())
+    }
+
+    /// Locks `token_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    ///
+    /// # Events
+    ///
+    /// * [`Locked`] - If the token was unlocked.
+    pub fn _lock(&mut self, token_id: U256) -> Result<(), Error> {
+        self.erc721._require_owned(token_id)?;
+        let was_locked = self._is_locked(token_id);
+        self.token_locked.setter(token_id).set(true);
+        if !was_locked {
+            evm::log(Locked { token_id });
+        }
+        Ok(())
+    }
+
+    /// Unlocks `token_id`. The token stays locked while the whole collection
+    /// is locked.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    ///
+    /// # Events
+    ///
+    /// * [`Unlocked`] - If the token becomes unlocked.
+    pub fn _unlock(&mut self, token_id: U256) -> Result<(), Error> {
+        self.erc721._require_owned(token_id)?;
+        let was_locked = self._is_locked(token_id);
+        self.token_locked.setter(token_id).set(false);
+        if was_locked && !self._is_locked(token_id) {
+            evm::log(Unlocked { token_id });
+        }
+        Ok(())
+    }
+
+    /// Extended version of [`Erc721::_update`] that only allows locked tokens
+    /// to be minted or burned.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `auth` - Account used for authorization of the update.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::LockedToken`] - If the token is locked, and is neither minted
+    ///   nor burned.
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist and
+    ///   `auth` is not `Address::ZERO`.
+    /// * [`erc721::Error::InsufficientApproval`] - If `auth` is not
+    ///   `Address::ZERO` and `auth` does not have a right to approve this
+    ///   token.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    /// * [`Locked`] - If a token is minted while the collection is locked.
+    pub fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Error> {
+        let from = self.erc721._owner_of(token_id);
+        if !from.is_zero() && !to.is_zero() && self._is_locked(token_id) {
+            return Err(ERC5192LockedToken { token_id }.into());
+        }
+
+        let previous_owner = self.erc721._update(to, token_id, auth)?;
+
+        if to.is_zero() {
+            self.token_locked.setter(token_id).set(false);
+        } else if previous_owner.is_zero() {
+            self.minted.set(true);
+            if self.collection_locked.get() {
+                evm::log(Locked { token_id });
+            }
+        }
+
+        Ok(previous_owner)
+    }
+
+    /// Mints `token_id` and transfers it to `to`.
+    ///
+    /// Re-export of [`Erc721::_mint`] that respects the lock policy.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if !previous_owner.is_zero() {
+            return Err(erc721::Error::InvalidSender(ERC721InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Mints `token_id`, transfers it to `to`, and checks for `to`'s
+    /// acceptance.
+    ///
+    /// Re-export of [`Erc721::_safe_mint`] that respects the lock policy.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _safe_mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        self._mint(to, token_id)?;
+        Ok(self.erc721._check_on_erc721_received(
+            msg::sender(),
+            Address::ZERO,
+            to,
+            token_id,
+            data,
+        )?)
+    }
+
+    /// Destroys `token_id`, even if it is locked.
+    ///
+    /// Re-export of [`Erc721::_burn`] that respects the lock policy.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(&mut self, token_id: U256) -> Result<(), Error> {
+        let previous_owner =
+            self._update(Address::ZERO, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(erc721::Error::NonexistentToken(
+                ERC721NonexistentToken { token_id },
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Transfers `token_id` from `from` to `to`.
+    ///
+    /// Re-export of [`Erc721::_transfer`] that respects the lock policy.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(erc721::Error::NonexistentToken(
+                ERC721NonexistentToken { token_id },
+            )
+            .into());
+        } else if previous_owner != from {
+            return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Safely transfers `token_id` from `from` to `to`, checking for `to`'s
+    /// acceptance.
+    ///
+    /// Re-export of [`Erc721::_safe_transfer`] that respects the lock policy.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _safe_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        self._transfer(from, to, token_id)?;
+        Ok(self.erc721._check_on_erc721_received(
+            msg::sender(),
+            from,
+            to,
+            token_id,
+            data,
+        )?)
+    }
+
+    /// Approves `to` to operate on `token_id`.
+    ///
+    /// Re-export of [`Erc721::_approve`] that respects the lock policy.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _approve(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+        emit_event: bool,
+    ) -> Result<(), Error> {
+        if self._is_locked(token_id) {
+            return Err(ERC5192LockedToken { token_id }.into());
+        }
+        Ok(self.erc721._approve(to, token_id, auth, emit_event)?)
+    }
+
+    /// Approves `operator` to operate on all of `owner`'s tokens.
+    ///
+    /// Re-export of [`Erc721::_set_approval_for_all`] that respects the lock
+    /// policy.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _set_approval_for_all(
+        &mut self,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Error> {
+        if approved && self.collection_locked.get() {
+            return Err(ERC5192LockedCollection {}.into());
+        }
+        Ok(self.erc721._set_approval_for_all(owner, operator, approved)?)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+
+    use super::{Erc721Soulbound, Error, IErc5192};
+    use crate::{
+        token::erc721::{self, IErc721},
+        utils::introspection::erc165::IErc165,
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    #[motsu::test]
+    fn locked_token_can_be_minted_and_burned(
+        contract: Contract<Erc721Soulbound>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract.sender(alice)._lock(TOKEN_ID).expect("should lock");
+        assert!(contract.sender(alice).locked(TOKEN_ID).expect("should exist"));
+
+        contract.sender(alice)._burn(TOKEN_ID).expect("should burn");
+        let err = contract
+            .sender(alice)
+            .locked(TOKEN_ID)
+            .expect_err("should not exist");
+        assert!(matches!(
+            err,
+            Error::Erc721(erc721::Error::NonexistentToken(_))
+        ));
+
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        assert!(!contract
+            .sender(alice)
+            .locked(TOKEN_ID)
+            .expect("should exist"));
+    }
+
+    #[motsu::test]
+    fn locked_token_can_not_be_transferred_or_approved(
+        contract: Contract<Erc721Soulbound>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract.sender(alice)._lock(TOKEN_ID).expect("should lock");
+
+        let err = contract
+            .sender(alice)
+            .transfer_from(alice, bob, TOKEN_ID)
+            .expect_err("should not transfer a locked token");
+        assert!(matches!(err, Error::LockedToken(_)));
+
+        let err = contract
+            .sender(alice)
+            .approve(bob, TOKEN_ID)
+            .expect_err("should not approve a locked token");
+        assert!(matches!(err, Error::LockedToken(_)));
+
+        contract.sender(alice)._unlock(TOKEN_ID).expect("should unlock");
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, TOKEN_ID)
+            .expect("should transfer an unlocked token");
+        assert_eq!(
+            bob,
+            contract.sender(alice).owner_of(TOKEN_ID).expect("should exist")
+        );
+    }
+
+    #[motsu::test]
+    fn collection_lock_applies_to_every_token(
+        contract: Contract<Erc721Soulbound>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._lock_collection().expect("should lock");
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        assert!(contract.sender(alice).locked(TOKEN_ID).expect("should exist"));
+
+        let err = contract
+            .sender(alice)
+            .set_approval_for_all(bob, true)
+            .expect_err("should not approve an operator");
+        assert!(matches!(err, Error::LockedCollection(_)));
+
+        contract.sender(alice)._unlock(TOKEN_ID).expect("should unlock");
+        assert!(contract.sender(alice).locked(TOKEN_ID).expect("should exist"));
+
+        let err = contract
+            .sender(alice)
+            .transfer_from(alice, bob, TOKEN_ID)
+            .expect_err("should not transfer a locked token");
+        assert!(matches!(err, Error::LockedToken(_)));
+    }
+
+    #[motsu::test]
+    fn collection_can_not_be_locked_after_mint(
+        contract: Contract<Erc721Soulbound>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract.sender(alice)._burn(TOKEN_ID).expect("should burn");
+
+        let err = contract
+            .sender(alice)
+            ._lock_collection()
+            .expect_err("should not lock a minted collection");
+        assert!(matches!(err, Error::CollectionAlreadyMinted(_)));
+    }
+
+    #[motsu::test]
+    fn internal_transfers_respect_the_lock(
+        contract: Contract<Erc721Soulbound>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract.sender(alice)._lock(TOKEN_ID).expect("should lock");
+
+        let err = contract
+            .sender(alice)
+            ._transfer(alice, bob, TOKEN_ID)
+            .expect_err("should not transfer a locked token");
+        assert!(matches!(err, Error::LockedToken(_)));
+
+        let err = contract
+            .sender(alice)
+            ._approve(bob, TOKEN_ID, Address::ZERO, true)
+            .expect_err("should not approve a locked token");
+        assert!(matches!(err, Error::LockedToken(_)));
+        assert_eq!(
+            alice,
+            contract.sender(alice).owner_of(TOKEN_ID).expect("should exist")
+        );
+    }
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <Erc721Soulbound as IErc5192>::INTERFACE_ID;
+        let expected = 0xb45a_3c0e;
+        assert_eq!(actual, expected);
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc721Soulbound::supports_interface(0xb45a_3c0e_u32.into()));
+        assert!(Erc721Soulbound::supports_interface(0x80ac_58cd_u32.into()));
+        assert!(!Erc721Soulbound::supports_interface(0x1234_5678_u32.into()));
+    }
+}
@@ -1,9 +1,12 @@
 //! ERC-721 token with storage-based token URI management.
 //!
-//! It also implements IERC4096, which is an ERC-721 Metadata Update Extension.
+//! It also implements [ERC-4906], which is an ERC-721 Metadata Update
+//! Extension.
+//!
+//! [ERC-4906]: https://eips.ethereum.org/EIPS/eip-4906
 use alloc::string::String;
 
-use alloy_primitives::U256;
+use alloy_primitives::{FixedBytes, U256};
 pub use sol::*;
 use stylus_sdk::{
     evm,
@@ -11,7 +14,18 @@ use stylus_sdk::{
     storage::{StorageMap, StorageString},
 };
 
-use crate::token::erc721::{extensions::Erc721Metadata, Error, IErc721};
+use crate::{
+    token::erc721::{extensions::Erc721Metadata, Error, IErc721},
+    utils::introspection::erc165::{Erc165, IErc165},
+};
+
+/// Interface id of the ERC-4906 Metadata Update Extension.
+///
+/// The [ERC-4906] interface only contains events, so its id is a fixed value
+/// set by the standard.
+///
+/// [ERC-4906]: https://eips.ethereum.org/EIPS/eip-4906
+pub const ERC4906_INTERFACE_ID: u32 = 0x4906_4906;
 
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod sol {
@@ -20,14 +34,14 @@ mod sol {
     sol! {
         /// This event gets emitted when the metadata of a token is changed.
         ///
-        /// The event comes from IERC4096.
+        /// The event comes from ERC-4906.
         #[allow(missing_docs)]
         event MetadataUpdate(uint256 token_id);
 
         /// This event gets emitted when the metadata of a range of tokens
         /// is changed.
         ///
-        /// The event comes from IERC4096.
+        /// The event comes from ERC-4906.
         #[allow(missing_docs)]
         event BatchMetadataUpdate(uint256 from_token_id, uint256 to_token_id);
     }
@@ -40,6 +54,13 @@ pub struct Erc721UriStorage {
     pub token_uris: StorageMap<U256, StorageString>,
 }
 
+impl IErc165 for Erc721UriStorage {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        ERC4906_INTERFACE_ID == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
 impl Erc721UriStorage {
     /// Sets `token_uri` as the token URI of `token_id`.
     ///
@@ -123,7 +144,10 @@ mod tests {
     use stylus_sdk::prelude::{public, storage, TopLevelStorage};
 
     use super::Erc721UriStorage;
-    use crate::token::erc721::{extensions::Erc721Metadata, Erc721};
+    use crate::{
+        token::erc721::{extensions::Erc721Metadata, Erc721},
+        utils::introspection::erc165::IErc165,
+    };
 
     const TOKEN_ID: U256 = uint!(1_U256);
 
@@ -175,4 +199,11 @@ mod tests {
                 .expect("should return token URI")
         );
     }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc721UriStorage::supports_interface(0x4906_4906.into()));
+        assert!(Erc721UriStorage::supports_interface(0x01ff_c9a7.into()));
+        assert!(!Erc721UriStorage::supports_interface(0x1234_5678.into()));
+    }
 }
@@ -0,0 +1,1309 @@
+//! Extension of ERC-721 to support voting and delegation as implemented by
+//! [ERC-5805], where each individual NFT counts as 1 vote unit.
+//!
+//! Tokens do not count as votes until they are delegated, because votes must
+//! be tracked which incurs an additional cost on every transfer. Token holders
+//! can either delegate to a trusted representative who will decide how to make
+//! use of the votes in governance decisions, or they can delegate to
+//! themselves to be their own representative.
+//!
+//! [`Erc721Votes`] adds voting to [`Erc721`], and [`Erc721ConsecutiveVotes`]
+//! adds it to [`Erc721Consecutive`], so that tokens minted in a batch are
+//! counted as well.
+//!
+//! The clock of this extension is the block timestamp, as described by
+//! [ERC-6372].
+//!
+//! [ERC-5805]: https://eips.ethereum.org/EIPS/eip-5805
+//! [ERC-6372]: https://eips.ethereum.org/EIPS/eip-6372
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, U256};
+use stylus_sdk::{
+    abi::Bytes,
+    block, evm, msg,
+    prelude::{storage, TopLevelStorage},
+    storage::{StorageAddress, StorageMap},
+    stylus_proc::{public, SolidityError},
+};
+
+use crate::{
+    token::erc721::{
+        self,
+        extensions::consecutive::{self, Erc721Consecutive},
+        ERC721IncorrectOwner, ERC721InvalidReceiver, ERC721InvalidSender,
+        ERC721NonexistentToken, Erc721, IErc721,
+    },
+    utils::structs::checkpoints::{self, Size, Trace, S160, S208},
+};
+
+type U48 = <S208 as Size>::Key;
+type U208 = <S208 as Size>::Value;
+type U96 = <S160 as Size>::Key;
+
+pub use sol::*;
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when an account changes its delegate.
+        ///
+        /// * `delegator` - Account whose delegate changed.
+        /// * `from_delegate` - Previous delegate of `delegator`.
+        /// * `to_delegate` - New delegate of `delegator`.
+        #[allow(missing_docs)]
+        event DelegateChanged(
+            address indexed delegator,
+            address indexed from_delegate,
+            address indexed to_delegate
+        );
+
+        /// Emitted when a token transfer or delegate change results in changes
+        /// to a delegate's number of voting units.
+        ///
+        /// * `delegate` - Account whose votes changed.
+        /// * `previous_votes` - Votes of `delegate` before the change.
+        /// * `new_votes` - Votes of `delegate` after the change.
+        #[allow(missing_docs)]
+        event DelegateVotesChanged(
+            address indexed delegate,
+            uint256 previous_votes,
+            uint256 new_votes
+        );
+    }
+
+    sol! {
+        /// Lookup to future votes is not available.
+        ///
+        /// * `timepoint` - Requested timepoint.
+        /// * `clock` - Current timepoint of the clock.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC5805FutureLookup(uint256 timepoint, uint48 clock);
+    }
+}
+
+/// An [`Erc721Votes`] or [`Erc721ConsecutiveVotes`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Error type from [`Erc721`] contract [`erc721::Error`].
+    Erc721(erc721::Error),
+    /// Error type from [`Erc721Consecutive`] contract [`consecutive::Error`].
+    Erc721Consecutive(consecutive::Error),
+    /// Error type from checkpoint contract [`checkpoints::Error`].
+    Checkpoints(checkpoints::Error),
+    /// Lookup to future votes is not available.
+    FutureLookup(ERC5805FutureLookup),
+}
+
+/// State of a [`Votes`] contract.
+///
+/// Keeps track of delegates and of the voting power of each delegate. It is
+/// used by [`Erc721Votes`] and [`Erc721ConsecutiveVotes`], which report the
+/// voting units moved by every token transfer.
+#[storage]
+pub struct Votes {
+    /// Maps an account to the delegate of its voting units.
+    pub(crate) delegatees: StorageMap<Address, StorageAddress>,
+    /// Maps a delegate to the history of its votes.
+    pub(crate) delegate_checkpoints: StorageMap<Address, Trace<S208>>,
+    /// History of the total supply of voting units.
+    pub(crate) total_checkpoints: Trace<S208>,
+}
+
+/// Interface of a token with voting power, as defined by [ERC-5805].
+///
+/// [ERC-5805]: https://eips.ethereum.org/EIPS/eip-5805
+pub trait IVotes {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Returns the current timepoint of the clock, which is the block
+    /// timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn clock(&self) -> U48;
+
+    /// Returns a machine-readable description of the clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[allow(non_snake_case)]
+    fn CLOCK_MODE(&self) -> String;
+
+    /// Returns the current amount of votes that `account` has.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to query.
+    fn get_votes(&self, account: Address) -> U256;
+
+    /// Returns the amount of votes that `account` had at `timepoint`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to query.
+    /// * `timepoint` - Timepoint of the clock to query.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FutureLookup`] - If `timepoint` is not in the past.
+    fn get_past_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Self::Error>;
+
+    /// Returns the total supply of votes available at `timepoint`.
+    ///
+    /// NOTE: This value is the sum of all available votes, which is not
+    /// necessarily the sum of all delegated votes. Votes that have not been
+    /// delegated are still part of total supply, even though they would not
+    /// participate in a vote.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `timepoint` - Timepoint of the clock to query.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FutureLookup`] - If `timepoint` is not in the past.
+    fn get_past_total_supply(
+        &self,
+        timepoint: U256,
+    ) -> Result<U256, Self::Error>;
+
+    /// Returns the delegate that `account` has chosen.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to query.
+    fn delegates(&self, account: Address) -> Address;
+
+    /// Delegates votes from the sender to `delegatee`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `delegatee` - Account that receives the votes of the sender.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Checkpoints`] - If a checkpoint can not be written.
+    ///
+    /// # Events
+    ///
+    /// * [`DelegateChanged`].
+    /// * [`DelegateVotesChanged`] - For each delegate whose votes changed.
+    fn delegate(&mut self, delegatee: Address) -> Result<(), Self::Error>;
+}
+
+impl Votes {
+    /// Returns the current timepoint of the clock, which is the block
+    /// timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn clock(&self) -> U48 {
+        U48::from(block::timestamp())
+    }
+
+    /// Returns a machine-readable description of the clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn clock_mode(&self) -> String {
+        "mode=timestamp".to_string()
+    }
+
+    /// Returns the current amount of votes that `account` has.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to query.
+    #[must_use]
+    pub fn get_votes(&self, account: Address) -> U256 {
+        U256::from(self.delegate_checkpoints.get(account).latest())
+    }
+
+    /// Returns the amount of votes that `account` had at `timepoint`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to query.
+    /// * `timepoint` - Timepoint of the clock to query.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FutureLookup`] - If `timepoint` is not in the past.
+    pub fn get_past_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Error> {
+        let timepoint = self._validate_timepoint(timepoint)?;
+        Ok(U256::from(
+            self.delegate_checkpoints
+                .get(account)
+                .upper_lookup_recent(timepoint),
+        ))
+    }
+
+    /// Returns the total supply of votes available at `timepoint`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `timepoint` - Timepoint of the clock to query.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FutureLookup`] - If `timepoint` is not in the past.
+    pub fn get_past_total_supply(
+        &self,
+        timepoint: U256,
+    ) -> Result<U256, Error> {
+        let timepoint = self._validate_timepoint(timepoint)?;
+        Ok(U256::from(self.total_checkpoints.upper_lookup_recent(timepoint)))
+    }
+
+    /// Returns the current total supply of votes.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn get_total_supply(&self) -> U256 {
+        U256::from(self.total_checkpoints.latest())
+    }
+
+    /// Returns the delegate that `account` has chosen.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to query.
+    #[must_use]
+    pub fn delegates(&self, account: Address) -> Address {
+        self.delegatees.get(account)
+    }
+
+    /// Returns the number of checkpoints of `account`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to query.
+    #[must_use]
+    pub fn num_checkpoints(&self, account: Address) -> U256 {
+        self.delegate_checkpoints.get(account).length()
+    }
+
+    /// Returns the `pos`-th checkpoint of `account` as a (timepoint, votes)
+    /// pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to query.
+    /// * `pos` - Index of the checkpoint.
+    ///
+    /// # Panics
+    ///
+    /// * If `pos` exceeds [`Self::num_checkpoints`].
+    #[must_use]
+    pub fn checkpoints(
+        &self,
+        account: Address,
+        pos: alloy_primitives::U32,
+    ) -> (U48, U208) {
+        self.delegate_checkpoints.get(account).at(pos)
+    }
+
+    /// Delegates all the voting units of `account` to `delegatee`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account whose delegate changes.
+    /// * `delegatee` - New delegate of `account`.
+    /// * `units` - Voting units held by `account`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Checkpoints`] - If a checkpoint can not be written.
+    ///
+    /// # Events
+    ///
+    /// * [`DelegateChanged`].
+    /// * [`DelegateVotesChanged`] - For each delegate whose votes changed.
+    pub fn _delegate(
+        &mut self,
+        account: Address,
+        delegatee: Address,
+        units: U256,
+    ) -> Result<(), Error> {
+        let old_delegate = self.delegates(account);
+        self.delegatees.setter(account).set(delegatee);
+
+        evm::log(DelegateChanged {
+            delegator: account,
+            from_delegate: old_delegate,
+            to_delegate: delegatee,
+        });
+
+        self._move_delegate_votes(old_delegate, delegatee, units)
+    }
+
+    /// Transfers, mints, or burns voting units. To register a mint, `from`
+    /// should be `Address::ZERO`. To register a burn, `to` should be
+    /// `Address::ZERO`. Total supply of voting units will be adjusted with
+    /// mints and burns.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account the units are moved from.
+    /// * `to` - Account the units are moved to.
+    /// * `amount` - Number of voting units.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Checkpoints`] - If a checkpoint can not be written.
+    ///
+    /// # Events
+    ///
+    /// * [`DelegateVotesChanged`] - For each delegate whose votes changed.
+    ///
+    /// # Panics
+    ///
+    /// * If the total supply of voting units exceeds `U208::MAX`.
+    pub fn _transfer_voting_units(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), Error> {
+        let key = self.clock();
+        let amount = U208::from(amount);
+        if from.is_zero() {
+            let total = self
+                .total_checkpoints
+                .latest()
+                .checked_add(amount)
+                .expect("should not exceed `U208::MAX` for total votes");
+            self.total_checkpoints.push(key, total)?;
+        }
+        if to.is_zero() {
+            // SAFETY: Burned units were minted before, so they are part of
+            // the total supply.
+            let total = self.total_checkpoints.latest() - amount;
+            self.total_checkpoints.push(key, total)?;
+        }
+        self._move_delegate_votes(
+            self.delegates(from),
+            self.delegates(to),
+            U256::from(amount),
+        )
+    }
+
+    /// Moves delegated votes from one delegate to another.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Delegate losing the votes.
+    /// * `to` - Delegate receiving the votes.
+    /// * `amount` - Number of votes.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Checkpoints`] - If a checkpoint can not be written.
+    ///
+    /// # Events
+    ///
+    /// * [`DelegateVotesChanged`] - For each delegate whose votes changed.
+    fn _move_delegate_votes(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), Error> {
+        if from == to || amount.is_zero() {
+            return Ok(());
+        }
+
+        let key = self.clock();
+        let amount = U208::from(amount);
+
+        if !from.is_zero() {
+            let mut checkpoints = self.delegate_checkpoints.setter(from);
+            // SAFETY: A delegate's votes include the units of every account
+            // delegating to it.
+            let new_votes = checkpoints.latest() - amount;
+            let (old_votes, new_votes) = checkpoints.push(key, new_votes)?;
+            evm::log(DelegateVotesChanged {
+                delegate: from,
+                previous_votes: U256::from(old_votes),
+                new_votes: U256::from(new_votes),
+            });
+        }
+
+        if !to.is_zero() {
+            let mut checkpoints = self.delegate_checkpoints.setter(to);
+            // SAFETY: Overflow not possible, since the votes of a delegate
+            // never exceed the total supply of voting units.
+            let new_votes = checkpoints.latest() + amount;
+            let (old_votes, new_votes) = checkpoints.push(key, new_votes)?;
+            evm::log(DelegateVotesChanged {
+                delegate: to,
+                previous_votes: U256::from(old_votes),
+                new_votes: U256::from(new_votes),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `timepoint` is in the past, and narrows it to the key type
+    /// of the checkpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `timepoint` - Timepoint of the clock to check.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FutureLookup`] - If `timepoint` is not in the past.
+    fn _validate_timepoint(&self, timepoint: U256) -> Result<U48, Error> {
+        let clock = self.clock();
+        if timepoint >= U256::from(clock) {
+            return Err(ERC5805FutureLookup { timepoint, clock }.into());
+        }
+        // SAFETY: `timepoint` is lower than `clock`, so it fits in `U48`.
+        Ok(U48::from(timepoint))
+    }
+}
+
+/// State of an [`Erc721Votes`] token.
+#[storage]
+pub struct Erc721Votes {
+    /// [`Erc721`] contract.
+    pub erc721: Erc721,
+    /// [`Votes`] contract.
+    pub votes: Votes,
+}
+
+impl Deref for Erc721Votes {
+    type Target = Erc721;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721
+    }
+}
+
+impl DerefMut for Erc721Votes {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc721Votes {}
+
+#[public]
+impl IErc721 for Erc721Votes {
+    type Error = Error;
+
+    fn balance_of(&self, owner: Address) -> Result<U256, Error> {
+        Ok(self.erc721.balance_of(owner)?)
+    }
+
+    fn owner_of(&self, token_id: U256) -> Result<Address, Error> {
+        Ok(self.erc721.owner_of(token_id)?)
+    }
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        self.safe_transfer_from_with_data(from, to, token_id, vec![].into())
+    }
+
+    #[selector(name = "safeTransferFrom")]
+    fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        self.transfer_from(from, to, token_id)?;
+        Ok(self.erc721._check_on_erc721_received(
+            msg::sender(),
+            from,
+            to,
+            token_id,
+            &data,
+        )?)
+    }
+
+    fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        // Setting an "auth" argument enables the `_is_authorized` check which
+        // verifies that the token exists (`from != 0`). Therefore, it is
+        // not needed to verify that the return value is not 0 here.
+        let previous_owner = self._update(to, token_id, msg::sender())?;
+        if previous_owner != from {
+            return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    fn approve(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
+        Ok(self.erc721.approve(to, token_id)?)
+    }
+
+    fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Error> {
+        Ok(self.erc721.set_approval_for_all(operator, approved)?)
+    }
+
+    fn get_approved(&self, token_id: U256) -> Result<Address, Error> {
+        Ok(self.erc721.get_approved(token_id)?)
+    }
+
+    fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
+        self.erc721.is_approved_for_all(owner, operator)
+    }
+}
+
+impl IVotes for Erc721Votes {
+    type Error = Error;
+
+    fn clock(&self) -> U48 {
+        self.votes.clock()
+    }
+
+    fn CLOCK_MODE(&self) -> String {
+        self.votes.clock_mode()
+    }
+
+    fn get_votes(&self, account: Address) -> U256 {
+        self.votes.get_votes(account)
+    }
+
+    fn get_past_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Error> {
+        self.votes.get_past_votes(account, timepoint)
+    }
+
+    fn get_past_total_supply(&self, timepoint: U256) -> Result<U256, Error> {
+        self.votes.get_past_total_supply(timepoint)
+    }
+
+    fn delegates(&self, account: Address) -> Address {
+        self.votes.delegates(account)
+    }
+
+    fn delegate(&mut self, delegatee: Address) -> Result<(), Error> {
+        let account = msg::sender();
+        let units = self.erc721.balances.get(account);
+        self.votes._delegate(account, delegatee, units)
+    }
+}
+
+impl Erc721Votes {
+    /// Extended version of [`Erc721::_update`] that moves the voting units
+    /// of the token from the delegate of the previous owner to the delegate
+    /// of `to`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `auth` - Account used for authorization of the update.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist and
+    ///   `auth` is not `Address::ZERO`.
+    /// * [`erc721::Error::InsufficientApproval`] - If `auth` is not
+    ///   `Address::ZERO` and `auth` does not have a right to approve this
+    ///   token.
+    /// * [`Error::Checkpoints`] - If a checkpoint can not be written.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    /// * [`DelegateVotesChanged`] - For each delegate whose votes changed.
+    pub fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Error> {
+        let previous_owner = self.erc721._update(to, token_id, auth)?;
+        self.votes._transfer_voting_units(
+            previous_owner,
+            to,
+            alloy_primitives::uint!(1_U256),
+        )?;
+        Ok(previous_owner)
+    }
+
+    /// Mints `token_id` and transfers it to `to`.
+    ///
+    /// Re-export of [`Erc721::_mint`] that also tracks voting units.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if !previous_owner.is_zero() {
+            return Err(erc721::Error::InvalidSender(ERC721InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Mints `token_id`, transfers it to `to`, and checks for `to`'s
+    /// acceptance.
+    ///
+    /// Re-export of [`Erc721::_safe_mint`] that also tracks voting units.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _safe_mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        self._mint(to, token_id)?;
+        Ok(self.erc721._check_on_erc721_received(
+            msg::sender(),
+            Address::ZERO,
+            to,
+            token_id,
+            data,
+        )?)
+    }
+
+    /// Destroys `token_id`.
+    ///
+    /// Re-export of [`Erc721::_burn`] that also tracks voting units.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(&mut self, token_id: U256) -> Result<(), Error> {
+        let previous_owner =
+            self._update(Address::ZERO, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(erc721::Error::NonexistentToken(
+                ERC721NonexistentToken { token_id },
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Transfers `token_id` from `from` to `to`.
+    ///
+    /// Re-export of [`Erc721::_transfer`] that also tracks voting units.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(erc721::Error::NonexistentToken(
+                ERC721NonexistentToken { token_id },
+            )
+            .into());
+        } else if previous_owner != from {
+            return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            })
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// State of an [`Erc721ConsecutiveVotes`] token.
+#[storage]
+pub struct Erc721ConsecutiveVotes {
+    /// [`Erc721Consecutive`] contract.
+    pub erc721_consecutive: Erc721Consecutive,
+    /// [`Votes`] contract.
+    pub votes: Votes,
+}
+
+impl Deref for Erc721ConsecutiveVotes {
+    type Target = Erc721Consecutive;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721_consecutive
+    }
+}
+
+impl DerefMut for Erc721ConsecutiveVotes {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721_consecutive
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc721ConsecutiveVotes {}
+
+#[public]
+impl IErc721 for Erc721ConsecutiveVotes {
+    type Error = Error;
+
+    fn balance_of(&self, owner: Address) -> Result<U256, Error> {
+        Ok(self.erc721_consecutive.balance_of(owner)?)
+    }
+
+    fn owner_of(&self, token_id: U256) -> Result<Address, Error> {
+        Ok(self.erc721_consecutive.owner_of(token_id)?)
+    }
+
+    fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        self.safe_transfer_from_with_data(from, to, token_id, vec![].into())
+    }
+
+    #[selector(name = "safeTransferFrom")]
+    fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        self.transfer_from(from, to, token_id)?;
+        Ok(self.erc721_consecutive.erc721._check_on_erc721_received(
+            msg::sender(),
+            from,
+            to,
+            token_id,
+            &data,
+        )?)
+    }
+
+    fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        // Setting an "auth" argument enables the `_is_authorized` check which
+        // verifies that the token exists (`from != 0`). Therefore, it is
+        // not needed to verify that the return value is not 0 here.
+        let previous_owner = self._update(to, token_id, msg::sender())?;
+        if previous_owner != from {
+            return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    fn approve(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
+        Ok(self.erc721_consecutive.approve(to, token_id)?)
+    }
+
+    fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Error> {
+        Ok(self.erc721_consecutive.set_approval_for_all(operator, approved)?)
+    }
+
+    fn get_approved(&self, token_id: U256) -> Result<Address, Error> {
+        Ok(self.erc721_consecutive.get_approved(token_id)?)
+    }
+
+    fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
+        self.erc721_consecutive.is_approved_for_all(owner, operator)
+    }
+}
+
+impl IVotes for Erc721ConsecutiveVotes {
+    type Error = Error;
+
+    fn clock(&self) -> U48 {
+        self.votes.clock()
+    }
+
+    fn CLOCK_MODE(&self) -> String {
+        self.votes.clock_mode()
+    }
+
+    fn get_votes(&self, account: Address) -> U256 {
+        self.votes.get_votes(account)
+    }
+
+    fn get_past_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Error> {
+        self.votes.get_past_votes(account, timepoint)
+    }
+
+    fn get_past_total_supply(&self, timepoint: U256) -> Result<U256, Error> {
+        self.votes.get_past_total_supply(timepoint)
+    }
+
+    fn delegates(&self, account: Address) -> Address {
+        self.votes.delegates(account)
+    }
+
+    fn delegate(&mut self, delegatee: Address) -> Result<(), Error> {
+        let account = msg::sender();
+        let units = self.erc721_consecutive.erc721.balances.get(account);
+        self.votes._delegate(account, delegatee, units)
+    }
+}
+
+impl Erc721ConsecutiveVotes {
+    /// Mints a batch of tokens with length `batch_size` for `to`, and adds
+    /// the batch to the voting units of `to`.
+    ///
+    /// Re-export of [`Erc721Consecutive::_mint_consecutive`] that also tracks
+    /// voting units.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint_consecutive(
+        &mut self,
+        to: Address,
+        batch_size: U96,
+    ) -> Result<U96, Error> {
+        let next = self.erc721_consecutive._mint_consecutive(to, batch_size)?;
+        self.votes._transfer_voting_units(
+            Address::ZERO,
+            to,
+            U256::from(batch_size),
+        )?;
+        Ok(next)
+    }
+
+    /// Extended version of [`Erc721Consecutive::_update`] that moves the
+    /// voting units of the token from the delegate of the previous owner to
+    /// the delegate of `to`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `auth` - Account used for authorization of the update.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist and
+    ///   `auth` is not `Address::ZERO`.
+    /// * [`erc721::Error::InsufficientApproval`] - If `auth` is not
+    ///   `Address::ZERO` and `auth` does not have a right to approve this
+    ///   token.
+    /// * [`Error::Checkpoints`] - If a checkpoint can not be written.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    /// * [`DelegateVotesChanged`] - For each delegate whose votes changed.
+    pub fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Error> {
+        let previous_owner =
+            self.erc721_consecutive._update(to, token_id, auth)?;
+        self.votes._transfer_voting_units(
+            previous_owner,
+            to,
+            alloy_primitives::uint!(1_U256),
+        )?;
+        Ok(previous_owner)
+    }
+
+    /// Mints `token_id` and transfers it to `to`.
+    ///
+    /// Re-export of [`Erc721Consecutive::_mint`] that also tracks voting
+    /// units.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _mint(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        self.erc721_consecutive._check_mint_allowed(token_id)?;
+
+        let previous_owner = self._update(to, token_id, Address::ZERO)?;
+        if !previous_owner.is_zero() {
+            return Err(erc721::Error::InvalidSender(ERC721InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Destroys `token_id`.
+    ///
+    /// Re-export of [`Erc721Consecutive::_burn`] that also tracks voting
+    /// units.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn(&mut self, token_id: U256) -> Result<(), Error> {
+        let previous_owner =
+            self._update(Address::ZERO, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(erc721::Error::NonexistentToken(
+                ERC721NonexistentToken { token_id },
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Destroys the tokens from `from_id` to `to_id`, both inclusive.
+    ///
+    /// Re-export of [`Erc721Consecutive::_burn_range`] that also removes the
+    /// tokens from the voting units of their owners.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _burn_range(
+        &mut self,
+        from_id: U256,
+        to_id: U256,
+    ) -> Result<(), Error> {
+        self.erc721_consecutive._burn_range(from_id, to_id)?;
+        // Burning a range keeps the sequential ownership checkpoints, so the
+        // previous owners can still be read from them.
+        for (start, end, owner) in
+            self.erc721_consecutive._sequential_segments(from_id, to_id)
+        {
+            self.votes._transfer_voting_units(
+                owner,
+                Address::ZERO,
+                end - start + alloy_primitives::uint!(1_U256),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Transfers the tokens from `from_id` to `to_id`, both inclusive, from
+    /// `from` to `to`.
+    ///
+    /// Re-export of [`Erc721Consecutive::_transfer_range`] that also moves the
+    /// voting units of the tokens.
+    #[allow(clippy::missing_errors_doc)]
+    pub fn _transfer_range(
+        &mut self,
+        from: Address,
+        to: Address,
+        from_id: U256,
+        to_id: U256,
+    ) -> Result<(), Error> {
+        self.erc721_consecutive._transfer_range(from, to, from_id, to_id)?;
+        self.votes._transfer_voting_units(
+            from,
+            to,
+            to_id - from_id + alloy_primitives::uint!(1_U256),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+
+    use super::{Erc721ConsecutiveVotes, Erc721Votes, Error, IVotes, U96};
+    use crate::token::erc721::IErc721;
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+    const OTHER_TOKEN_ID: U256 = uint!(2_U256);
+
+    #[motsu::test]
+    fn minted_tokens_count_after_delegation(
+        contract: Contract<Erc721Votes>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract
+            .sender(alice)
+            ._mint(alice, OTHER_TOKEN_ID)
+            .expect("should mint");
+
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+        assert_eq!(
+            uint!(2_U256),
+            contract.sender(alice).votes.get_total_supply()
+        );
+
+        contract.sender(alice).delegate(alice).expect("should delegate");
+
+        assert_eq!(alice, contract.sender(alice).delegates(alice));
+        assert_eq!(uint!(2_U256), contract.sender(alice).get_votes(alice));
+    }
+
+    #[motsu::test]
+    fn transfers_move_votes_between_delegates(
+        contract: Contract<Erc721Votes>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract.sender(alice).delegate(alice).expect("should delegate");
+        contract.sender(bob).delegate(bob).expect("should delegate");
+
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, TOKEN_ID)
+            .expect("should transfer");
+
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+        assert_eq!(uint!(1_U256), contract.sender(alice).get_votes(bob));
+    }
+
+    #[motsu::test]
+    fn delegation_moves_existing_votes(
+        contract: Contract<Erc721Votes>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract.sender(alice).delegate(alice).expect("should delegate");
+        contract.sender(alice).delegate(bob).expect("should delegate");
+
+        assert_eq!(bob, contract.sender(alice).delegates(alice));
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+        assert_eq!(uint!(1_U256), contract.sender(alice).get_votes(bob));
+    }
+
+    #[motsu::test]
+    fn burns_remove_votes_and_supply(
+        contract: Contract<Erc721Votes>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract.sender(alice).delegate(alice).expect("should delegate");
+
+        contract.sender(alice)._burn(TOKEN_ID).expect("should burn");
+
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+        assert_eq!(U256::ZERO, contract.sender(alice).votes.get_total_supply());
+    }
+
+    #[motsu::test]
+    fn past_votes_only_for_past_timepoints(
+        contract: Contract<Erc721Votes>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        contract.sender(alice).delegate(alice).expect("should delegate");
+
+        let clock = U256::from(contract.sender(alice).clock());
+        let past = clock - uint!(1_U256);
+
+        assert_eq!(
+            U256::ZERO,
+            contract
+                .sender(alice)
+                .get_past_votes(alice, past)
+                .expect("should return past votes")
+        );
+        assert_eq!(
+            U256::ZERO,
+            contract
+                .sender(alice)
+                .get_past_total_supply(past)
+                .expect("should return past total supply")
+        );
+
+        let err = contract
+            .sender(alice)
+            .get_past_votes(alice, clock)
+            .expect_err("should not look up the current timepoint");
+        assert!(matches!(err, Error::FutureLookup(_)));
+
+        let err = contract
+            .sender(alice)
+            .get_past_total_supply(clock)
+            .expect_err("should not look up the current timepoint");
+        assert!(matches!(err, Error::FutureLookup(_)));
+    }
+
+    #[motsu::test]
+    fn batch_minted_tokens_count(
+        contract: Contract<Erc721ConsecutiveVotes>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            .erc721_consecutive
+            .max_batch_size
+            .set(uint!(5000_U96));
+        contract
+            .sender(alice)
+            ._mint_consecutive(alice, uint!(1000_U96))
+            .expect("should mint consecutively");
+        let _: U96 = contract
+            .sender(alice)
+            ._mint_consecutive(bob, uint!(500_U96))
+            .expect("should mint consecutively");
+
+        assert_eq!(
+            uint!(1500_U256),
+            contract.sender(alice).votes.get_total_supply()
+        );
+
+        contract.sender(alice).delegate(alice).expect("should delegate");
+        contract.sender(bob).delegate(alice).expect("should delegate");
+        assert_eq!(uint!(1500_U256), contract.sender(alice).get_votes(alice));
+
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, U256::ZERO)
+            .expect("should transfer");
+        contract.sender(bob).delegate(bob).expect("should delegate");
+        assert_eq!(uint!(999_U256), contract.sender(alice).get_votes(alice));
+        assert_eq!(uint!(501_U256), contract.sender(alice).get_votes(bob));
+
+        contract.sender(alice)._burn(uint!(1_U256)).expect("should burn");
+        assert_eq!(uint!(998_U256), contract.sender(alice).get_votes(alice));
+        assert_eq!(
+            uint!(1499_U256),
+            contract.sender(alice).votes.get_total_supply()
+        );
+    }
+
+    #[motsu::test]
+    fn range_operations_move_votes(
+        contract: Contract<Erc721ConsecutiveVotes>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            .erc721_consecutive
+            .max_batch_size
+            .set(uint!(5000_U96));
+        contract
+            .sender(alice)
+            ._mint_consecutive(alice, uint!(1000_U96))
+            .expect("should mint consecutively");
+        contract.sender(alice).delegate(alice).expect("should delegate");
+        contract.sender(bob).delegate(bob).expect("should delegate");
+
+        contract
+            .sender(alice)
+            ._transfer_range(alice, bob, uint!(100_U256), uint!(399_U256))
+            .expect("should transfer the range");
+        assert_eq!(uint!(700_U256), contract.sender(alice).get_votes(alice));
+        assert_eq!(uint!(300_U256), contract.sender(alice).get_votes(bob));
+
+        // The burnt range spans tokens of both alice and bob.
+        contract
+            .sender(alice)
+            ._burn_range(uint!(50_U256), uint!(149_U256))
+            .expect("should burn the range");
+        assert_eq!(uint!(650_U256), contract.sender(alice).get_votes(alice));
+        assert_eq!(uint!(250_U256), contract.sender(alice).get_votes(bob));
+        assert_eq!(
+            uint!(900_U256),
+            contract.sender(alice).votes.get_total_supply()
+        );
+        assert_eq!(
+            uint!(650_U256),
+            contract.sender(alice).balance_of(alice).expect("should read")
+        );
+    }
+}
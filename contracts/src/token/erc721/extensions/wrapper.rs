@@ -0,0 +1,1026 @@
+//! Extension of the ERC-721 token contract to support token wrapping.
+//!
+//! Users can deposit and withdraw an "underlying token" and receive a
+//! "wrapped token" with a matching token id. This is useful in conjunction
+//! with other modules, e.g. to add voting or soulbound behaviour to an
+//! existing collection without migrating it.
+//!
+//! Tokens of the underlying collection sent directly to the wrapper through
+//! `safeTransferFrom` are wrapped for their previous owner.
+//!
+//! By default the wrapped tokens are minted by the [`Erc721Wrapper::erc721`]
+//! field. To combine the wrapper with another ERC-721 variant, e.g.
+//! [`super::Erc721Votes`] or [`super::Erc721Soulbound`], store that token
+//! next to the wrapper and use the `_with` versions of the wrapper functions,
+//! which mint and burn through the token's own `_update` by way of
+//! [`IErc721Wrapped`].
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, U256};
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    call::{self, Call},
+    contract, msg,
+    prelude::{storage, TopLevelStorage},
+    storage::StorageAddress,
+    stylus_proc::{public, SolidityError},
+};
+
+use crate::{
+    token::erc721::{
+        self,
+        extensions::{
+            consecutive::{self, Erc721Consecutive},
+            pausable, soulbound, votes, Erc721Pausable, Erc721Soulbound,
+            Erc721Votes,
+        },
+        ERC721IncorrectOwner, Erc721, IErc721, RECEIVER_FN_SELECTOR,
+    },
+    utils::introspection::erc165::IErc165,
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// The received ERC-721 token couldn't be wrapped.
+        ///
+        /// * `token` - Address of the token that was sent to the wrapper.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC721UnsupportedToken(address token);
+    }
+}
+
+mod token {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::stylus_proc::sol_interface;
+
+    sol_interface! {
+        /// Solidity Interface of the underlying ERC-721 token, as used by the
+        /// wrapper.
+        interface IErc721Underlying {
+            function ownerOf(uint256 token_id) external view returns (address);
+            function transferFrom(address from, address to, uint256 token_id) external;
+            function safeTransferFrom(address from, address to, uint256 token_id) external;
+        }
+    }
+}
+
+use token::IErc721Underlying;
+
+/// An [`Erc721Wrapper`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Error type from [`Erc721`] contract [`erc721::Error`].
+    Erc721(erc721::Error),
+    /// The received ERC-721 token couldn't be wrapped.
+    UnsupportedToken(ERC721UnsupportedToken),
+    /// A call to the underlying token reverted.
+    ///
+    /// Since encoding [`stylus_sdk::call::Error`] returns the underlying
+    /// return data, the revert reason of the underlying token is bubbled up.
+    Underlying(call::Error),
+}
+
+/// State of an [`Erc721Wrapper`] token.
+#[storage]
+pub struct Erc721Wrapper {
+    /// [`Erc721`] contract.
+    pub erc721: Erc721,
+    /// Address of the underlying ERC-721 token.
+    pub(crate) underlying: StorageAddress,
+}
+
+impl Deref for Erc721Wrapper {
+    type Target = Erc721;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721
+    }
+}
+
+impl DerefMut for Erc721Wrapper {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc721Wrapper {}
+
+/// Wrapped ERC-721 token minted and burned by an [`Erc721Wrapper`].
+///
+/// Implemented by [`Erc721`] and by the extensions that override its
+/// `_update`, so that the wrapper respects their own bookkeeping.
+pub trait IErc721Wrapped {
+    /// The error type associated to this trait implementation.
+    type Error;
+
+    /// Mints `token_id`, transfers it to `to`, and checks for `to`'s
+    /// acceptance. See [`Erc721::_safe_mint`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `data` - Additional data with no specified format, sent in the call to
+    ///   `to`.
+    ///
+    /// # Errors
+    ///
+    /// * If the token cannot be minted, or `to` does not accept it.
+    fn _safe_mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Self::Error>;
+
+    /// Transfers `token_id` to `to`, or burns it if `to` is `Address::ZERO`.
+    /// See [`Erc721::_update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `auth` - Account used for authorization of the update.
+    ///
+    /// # Errors
+    ///
+    /// * If the token cannot be transferred or burned, e.g. `auth` is not
+    ///   approved.
+    fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Self::Error>;
+}
+
+impl IErc721Wrapped for Erc721 {
+    type Error = erc721::Error;
+
+    fn _safe_mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Self::Error> {
+        Erc721::_safe_mint(self, to, token_id, data)
+    }
+
+    fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Self::Error> {
+        Erc721::_update(self, to, token_id, auth)
+    }
+}
+
+impl IErc721Wrapped for Erc721Consecutive {
+    type Error = consecutive::Error;
+
+    fn _safe_mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Self::Error> {
+        Erc721Consecutive::_safe_mint(self, to, token_id, data)
+    }
+
+    fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Self::Error> {
+        Erc721Consecutive::_update(self, to, token_id, auth)
+    }
+}
+
+impl IErc721Wrapped for Erc721Pausable {
+    type Error = pausable::Error;
+
+    fn _safe_mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Self::Error> {
+        Erc721Pausable::_safe_mint(self, to, token_id, data)
+    }
+
+    fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Self::Error> {
+        Erc721Pausable::_update(self, to, token_id, auth)
+    }
+}
+
+impl IErc721Wrapped for Erc721Soulbound {
+    type Error = soulbound::Error;
+
+    fn _safe_mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Self::Error> {
+        Erc721Soulbound::_safe_mint(self, to, token_id, data)
+    }
+
+    fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Self::Error> {
+        Erc721Soulbound::_update(self, to, token_id, auth)
+    }
+}
+
+impl IErc721Wrapped for Erc721Votes {
+    type Error = votes::Error;
+
+    fn _safe_mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Self::Error> {
+        Erc721Votes::_safe_mint(self, to, token_id, data)
+    }
+
+    fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Self::Error> {
+        Erc721Votes::_update(self, to, token_id, auth)
+    }
+}
+
+#[public]
+impl Erc721Wrapper {
+    /// Returns the address of the underlying ERC-721 token that is being
+    /// wrapped.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn underlying(&self) -> Address {
+        self.underlying.get()
+    }
+
+    /// Allows a user to deposit underlying tokens and mint the corresponding
+    /// wrapped tokens to `account`.
+    ///
+    /// The underlying tokens are moved with `transferFrom`, so the caller
+    /// must have approved the wrapper to move them.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account that receives the wrapped tokens.
+    /// * `token_ids` - Ids of the underlying tokens to wrap.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Underlying`] - If the transfer of an underlying token
+    ///   reverts.
+    /// * [`erc721::Error::InvalidReceiver`] - If `account` is `Address::ZERO`,
+    ///   or it does not accept the wrapped token.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`] - For each wrapped token.
+    pub fn deposit_for(
+        &mut self,
+        account: Address,
+        token_ids: Vec<U256>,
+    ) -> Result<bool, Error> {
+        Self::deposit(self.underlying(), &mut self.erc721, account, token_ids)
+    }
+
+    /// Allows a user to burn wrapped tokens and withdraw the corresponding
+    /// underlying tokens to `account`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account that receives the underlying tokens.
+    /// * `token_ids` - Ids of the wrapped tokens to unwrap.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If a wrapped token does not
+    ///   exist.
+    /// * [`erc721::Error::InsufficientApproval`] - If the caller is not
+    ///   approved to move a wrapped token.
+    /// * [`Error::Underlying`] - If the transfer of an underlying token
+    ///   reverts.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`] - For each unwrapped token.
+    pub fn withdraw_to(
+        &mut self,
+        account: Address,
+        token_ids: Vec<U256>,
+    ) -> Result<bool, Error> {
+        Self::withdraw(self.underlying(), &mut self.erc721, account, token_ids)
+    }
+
+    /// Wraps a token of the underlying collection sent directly to this
+    /// contract through `safeTransferFrom`, minting the wrapped token to
+    /// its previous owner `from`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `_operator` - Account that initiated the transfer.
+    /// * `from` - Previous owner of the underlying token.
+    /// * `token_id` - Token id as a number.
+    /// * `_data` - Additional data with no specified format.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnsupportedToken`] - If the caller is not the underlying
+    ///   token.
+    /// * [`erc721::Error::InvalidReceiver`] - If `from` does not accept the
+    ///   wrapped token.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    #[selector(name = "onERC721Received")]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn on_erc721_received(
+        &mut self,
+        _operator: Address,
+        from: Address,
+        token_id: U256,
+        _data: Bytes,
+    ) -> Result<FixedBytes<4>, Error> {
+        Self::receive(self.underlying(), &mut self.erc721, from, token_id)
+    }
+
+    /// Returns the number of tokens in `owner`'s account.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account of the token's owner.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidOwner`] - If owner address is `Address::ZERO`.
+    pub fn balance_of(&self, owner: Address) -> Result<U256, erc721::Error> {
+        self.erc721.balance_of(owner)
+    }
+
+    /// Returns the owner of the `token_id` token.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If the token does not exist.
+    pub fn owner_of(&self, token_id: U256) -> Result<Address, erc721::Error> {
+        self.erc721.owner_of(token_id)
+    }
+
+    /// Safely transfers `token_id` token from `from` to `to`.
+    ///
+    /// Re-export of [`Erc721::safe_transfer_from`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.safe_transfer_from(from, to, token_id)
+    }
+
+    /// Safely transfers `token_id` token from `from` to `to`, passing `data`
+    /// to the receiver.
+    ///
+    /// Re-export of [`Erc721::safe_transfer_from_with_data`].
+    #[selector(name = "safeTransferFrom")]
+    #[allow(clippy::missing_errors_doc)]
+    pub fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.safe_transfer_from_with_data(from, to, token_id, data)
+    }
+
+    /// Transfers `token_id` token from `from` to `to`.
+    ///
+    /// Re-export of [`Erc721::transfer_from`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.transfer_from(from, to, token_id)
+    }
+
+    /// Gives permission to `to` to transfer `token_id` token to another
+    /// account.
+    ///
+    /// Re-export of [`Erc721::approve`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn approve(
+        &mut self,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.approve(to, token_id)
+    }
+
+    /// Approves or removes `operator` as an operator for the caller.
+    ///
+    /// Re-export of [`Erc721::set_approval_for_all`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), erc721::Error> {
+        self.erc721.set_approval_for_all(operator, approved)
+    }
+
+    /// Returns the account approved for `token_id` token.
+    ///
+    /// Re-export of [`Erc721::get_approved`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn get_approved(
+        &self,
+        token_id: U256,
+    ) -> Result<Address, erc721::Error> {
+        self.erc721.get_approved(token_id)
+    }
+
+    /// Returns whether the `operator` is allowed to manage all the assets of
+    /// `owner`.
+    ///
+    /// Re-export of [`Erc721::is_approved_for_all`].
+    pub fn is_approved_for_all(
+        &self,
+        owner: Address,
+        operator: Address,
+    ) -> bool {
+        self.erc721.is_approved_for_all(owner, operator)
+    }
+}
+
+impl IErc165 for Erc721Wrapper {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        Erc721::supports_interface(interface_id)
+    }
+}
+
+impl Erc721Wrapper {
+    /// Mints a wrapped token to cover any underlying tokens that would have
+    /// been transferred by mistake, i.e. with `transferFrom` instead of
+    /// `safeTransferFrom`. Internal function that can be exposed with access
+    /// control if desired.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account that receives the wrapped token.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Underlying`] - If the call to the underlying token reverts.
+    /// * [`erc721::Error::IncorrectOwner`] - If the underlying token is not
+    ///   owned by this contract.
+    /// * [`erc721::Error::InvalidSender`] - If the wrapped token already
+    ///   exists.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    pub fn _recover(
+        &mut self,
+        account: Address,
+        token_id: U256,
+    ) -> Result<U256, Error> {
+        Self::recover(self.underlying(), &mut self.erc721, account, token_id)
+    }
+
+    /// Version of [`Self::deposit_for`] that mints the wrapped tokens
+    /// through `token` instead of [`Self::erc721`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token` - Wrapped token that mints the wrapped tokens.
+    /// * `account` - Account that receives the wrapped tokens.
+    /// * `token_ids` - Ids of the underlying tokens to wrap.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Underlying`] - If the transfer of an underlying token
+    ///   reverts.
+    /// * If `token` fails to mint a wrapped token.
+    pub fn _deposit_for_with<T, E>(
+        &self,
+        token: &mut T,
+        account: Address,
+        token_ids: Vec<U256>,
+    ) -> Result<bool, E>
+    where
+        T: IErc721Wrapped + TopLevelStorage,
+        E: From<Error> + From<T::Error>,
+    {
+        Self::deposit(self.underlying(), token, account, token_ids)
+    }
+
+    /// Version of [`Self::withdraw_to`] that burns the wrapped tokens
+    /// through `token` instead of [`Self::erc721`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token` - Wrapped token that burns the wrapped tokens.
+    /// * `account` - Account that receives the underlying tokens.
+    /// * `token_ids` - Ids of the wrapped tokens to unwrap.
+    ///
+    /// # Errors
+    ///
+    /// * If `token` fails to burn a wrapped token.
+    /// * [`Error::Underlying`] - If the transfer of an underlying token
+    ///   reverts.
+    pub fn _withdraw_to_with<T, E>(
+        &self,
+        token: &mut T,
+        account: Address,
+        token_ids: Vec<U256>,
+    ) -> Result<bool, E>
+    where
+        T: IErc721Wrapped + TopLevelStorage,
+        E: From<Error> + From<T::Error>,
+    {
+        Self::withdraw(self.underlying(), token, account, token_ids)
+    }
+
+    /// Version of [`Self::on_erc721_received`] that mints the wrapped token
+    /// through `token` instead of [`Self::erc721`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token` - Wrapped token that mints the wrapped token.
+    /// * `from` - Previous owner of the underlying token.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnsupportedToken`] - If the caller is not the underlying
+    ///   token.
+    /// * If `token` fails to mint the wrapped token.
+    pub fn _on_erc721_received_with<T, E>(
+        &self,
+        token: &mut T,
+        from: Address,
+        token_id: U256,
+    ) -> Result<FixedBytes<4>, E>
+    where
+        T: IErc721Wrapped + TopLevelStorage,
+        E: From<Error> + From<T::Error>,
+    {
+        Self::receive(self.underlying(), token, from, token_id)
+    }
+
+    /// Version of [`Self::_recover`] that mints the wrapped token through
+    /// `token` instead of [`Self::erc721`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `token` - Wrapped token that mints the wrapped token.
+    /// * `account` - Account that receives the wrapped token.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Underlying`] - If the call to the underlying token reverts.
+    /// * [`erc721::Error::IncorrectOwner`] - If the underlying token is not
+    ///   owned by this contract.
+    /// * If `token` fails to mint the wrapped token.
+    pub fn _recover_with<T, E>(
+        &self,
+        token: &mut T,
+        account: Address,
+        token_id: U256,
+    ) -> Result<U256, E>
+    where
+        T: IErc721Wrapped + TopLevelStorage,
+        E: From<Error> + From<T::Error>,
+    {
+        Self::recover(self.underlying(), token, account, token_id)
+    }
+}
+
+impl Erc721Wrapper {
+    fn deposit<T, E>(
+        underlying: Address,
+        token: &mut T,
+        account: Address,
+        token_ids: Vec<U256>,
+    ) -> Result<bool, E>
+    where
+        T: IErc721Wrapped + TopLevelStorage,
+        E: From<Error> + From<T::Error>,
+    {
+        let underlying = IErc721Underlying::new(underlying);
+        let sender = msg::sender();
+        for token_id in token_ids {
+            // This is an "unsafe" transfer that doesn't call any hook on the
+            // receiver. With `underlying()` being trusted (by design of this
+            // contract) and no other contracts expected to be called from
+            // there, we are safe.
+            underlying
+                .transfer_from(
+                    Call::new_in(token),
+                    sender,
+                    contract::address(),
+                    token_id,
+                )
+                .map_err(Error::Underlying)?;
+            token._safe_mint(account, token_id, &vec![].into())?;
+        }
+        Ok(true)
+    }
+
+    fn withdraw<T, E>(
+        underlying: Address,
+        token: &mut T,
+        account: Address,
+        token_ids: Vec<U256>,
+    ) -> Result<bool, E>
+    where
+        T: IErc721Wrapped + TopLevelStorage,
+        E: From<Error> + From<T::Error>,
+    {
+        let underlying = IErc721Underlying::new(underlying);
+        let sender = msg::sender();
+        for token_id in token_ids {
+            // Setting an "auth" argument enables the `_is_authorized` check
+            // which verifies that the token exists (`from != 0`). Therefore,
+            // it is not needed to verify that the return value is not 0 here.
+            token._update(Address::ZERO, token_id, sender)?;
+            // Checks were already performed at this point, and there's no
+            // way to retake ownership or approve the wrapped token id after
+            // this point, so it's safe to remove the reentrancy check for the
+            // next line.
+            underlying
+                .safe_transfer_from(
+                    Call::new_in(token),
+                    contract::address(),
+                    account,
+                    token_id,
+                )
+                .map_err(Error::Underlying)?;
+        }
+        Ok(true)
+    }
+
+    fn receive<T, E>(
+        underlying: Address,
+        token: &mut T,
+        from: Address,
+        token_id: U256,
+    ) -> Result<FixedBytes<4>, E>
+    where
+        T: IErc721Wrapped,
+        E: From<Error> + From<T::Error>,
+    {
+        let sender = msg::sender();
+        if sender != underlying {
+            return Err(
+                Error::from(ERC721UnsupportedToken { token: sender }).into()
+            );
+        }
+        token._safe_mint(from, token_id, &vec![].into())?;
+        Ok(RECEIVER_FN_SELECTOR.into())
+    }
+
+    fn recover<T, E>(
+        underlying: Address,
+        token: &mut T,
+        account: Address,
+        token_id: U256,
+    ) -> Result<U256, E>
+    where
+        T: IErc721Wrapped + TopLevelStorage,
+        E: From<Error> + From<T::Error>,
+    {
+        let underlying = IErc721Underlying::new(underlying);
+        let owner = underlying
+            .owner_of(Call::new_in(token), token_id)
+            .map_err(Error::Underlying)?;
+        let this = contract::address();
+        if owner != this {
+            return Err(Error::from(erc721::Error::IncorrectOwner(
+                ERC721IncorrectOwner { sender: this, token_id, owner },
+            ))
+            .into());
+        }
+        token._safe_mint(account, token_id, &vec![].into())?;
+        Ok(token_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use alloy_primitives::{uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::{public, storage, TopLevelStorage};
+
+    use super::{Erc721Wrapper, Error};
+    use crate::token::erc721::{
+        self,
+        extensions::{soulbound, Erc721Soulbound, IErc5192},
+        Erc721, IErc721,
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    #[storage]
+    struct Erc721SoulboundWrapperExample {
+        wrapper: Erc721Wrapper,
+        soulbound: Erc721Soulbound,
+    }
+
+    unsafe impl TopLevelStorage for Erc721SoulboundWrapperExample {}
+
+    #[public]
+    impl Erc721SoulboundWrapperExample {
+        fn deposit_for(
+            &mut self,
+            account: Address,
+            token_ids: Vec<U256>,
+        ) -> Result<bool, Vec<u8>> {
+            self.wrapper._deposit_for_with(
+                &mut self.soulbound,
+                account,
+                token_ids,
+            )
+        }
+
+        fn withdraw_to(
+            &mut self,
+            account: Address,
+            token_ids: Vec<U256>,
+        ) -> Result<bool, Vec<u8>> {
+            self.wrapper._withdraw_to_with(
+                &mut self.soulbound,
+                account,
+                token_ids,
+            )
+        }
+
+        fn transfer_from(
+            &mut self,
+            from: Address,
+            to: Address,
+            token_id: U256,
+        ) -> Result<(), Vec<u8>> {
+            Ok(self.soulbound.transfer_from(from, to, token_id)?)
+        }
+
+        fn locked(&self, token_id: U256) -> Result<bool, Vec<u8>> {
+            Ok(self.soulbound.locked(token_id)?)
+        }
+    }
+
+    fn deploy(
+        wrapper: &Contract<Erc721Wrapper>,
+        underlying: &Contract<Erc721>,
+        alice: Address,
+    ) {
+        let underlying = underlying.address();
+        wrapper.init(alice, |contract| contract.underlying.set(underlying));
+    }
+
+    #[motsu::test]
+    fn deposits_and_withdraws(
+        wrapper: Contract<Erc721Wrapper>,
+        underlying: Contract<Erc721>,
+        alice: Address,
+        bob: Address,
+    ) {
+        deploy(&wrapper, &underlying, alice);
+        underlying.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        underlying
+            .sender(alice)
+            .approve(wrapper.address(), TOKEN_ID)
+            .expect("should approve the wrapper");
+
+        wrapper
+            .sender(alice)
+            .deposit_for(bob, vec![TOKEN_ID])
+            .expect("should deposit");
+
+        assert_eq!(
+            wrapper.address(),
+            underlying.sender(alice).owner_of(TOKEN_ID).expect("should exist")
+        );
+        assert_eq!(
+            bob,
+            wrapper.sender(alice).owner_of(TOKEN_ID).expect("should exist")
+        );
+
+        wrapper
+            .sender(bob)
+            .withdraw_to(alice, vec![TOKEN_ID])
+            .expect("should withdraw");
+
+        assert_eq!(
+            alice,
+            underlying.sender(alice).owner_of(TOKEN_ID).expect("should exist")
+        );
+        let err = wrapper
+            .sender(alice)
+            .owner_of(TOKEN_ID)
+            .expect_err("should burn the wrapped token");
+        assert!(matches!(err, erc721::Error::NonexistentToken(_)));
+    }
+
+    #[motsu::test]
+    fn error_when_withdrawing_without_approval(
+        wrapper: Contract<Erc721Wrapper>,
+        underlying: Contract<Erc721>,
+        alice: Address,
+        bob: Address,
+    ) {
+        deploy(&wrapper, &underlying, alice);
+        underlying.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        underlying
+            .sender(alice)
+            .approve(wrapper.address(), TOKEN_ID)
+            .expect("should approve the wrapper");
+        wrapper
+            .sender(alice)
+            .deposit_for(alice, vec![TOKEN_ID])
+            .expect("should deposit");
+
+        let err = wrapper
+            .sender(bob)
+            .withdraw_to(bob, vec![TOKEN_ID])
+            .expect_err("should not withdraw another account's token");
+        assert!(matches!(
+            err,
+            Error::Erc721(erc721::Error::InsufficientApproval(_))
+        ));
+    }
+
+    #[motsu::test]
+    fn wraps_tokens_sent_directly(
+        wrapper: Contract<Erc721Wrapper>,
+        underlying: Contract<Erc721>,
+        alice: Address,
+    ) {
+        deploy(&wrapper, &underlying, alice);
+        underlying.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+
+        underlying
+            .sender(alice)
+            .safe_transfer_from(alice, wrapper.address(), TOKEN_ID)
+            .expect("should wrap the token");
+
+        assert_eq!(
+            alice,
+            wrapper.sender(alice).owner_of(TOKEN_ID).expect("should exist")
+        );
+    }
+
+    #[motsu::test]
+    fn error_when_receiving_unsupported_token(
+        wrapper: Contract<Erc721Wrapper>,
+        underlying: Contract<Erc721>,
+        alice: Address,
+    ) {
+        deploy(&wrapper, &underlying, alice);
+
+        let err = wrapper
+            .sender(alice)
+            .on_erc721_received(alice, alice, TOKEN_ID, vec![].into())
+            .expect_err("should only accept the underlying token");
+        assert!(matches!(err, Error::UnsupportedToken(_)));
+    }
+
+    #[motsu::test]
+    fn recovers_tokens_sent_by_mistake(
+        wrapper: Contract<Erc721Wrapper>,
+        underlying: Contract<Erc721>,
+        alice: Address,
+        bob: Address,
+    ) {
+        deploy(&wrapper, &underlying, alice);
+        underlying.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+
+        let err = wrapper
+            .sender(alice)
+            ._recover(bob, TOKEN_ID)
+            .expect_err("should not recover a token it doesn't own");
+        assert!(matches!(err, Error::Erc721(erc721::Error::IncorrectOwner(_))));
+
+        underlying
+            .sender(alice)
+            .transfer_from(alice, wrapper.address(), TOKEN_ID)
+            .expect("should transfer");
+        wrapper
+            .sender(alice)
+            ._recover(bob, TOKEN_ID)
+            .expect("should recover the token");
+
+        assert_eq!(
+            bob,
+            wrapper.sender(alice).owner_of(TOKEN_ID).expect("should exist")
+        );
+    }
+
+    #[motsu::test]
+    fn wraps_through_another_erc721_variant(
+        contract: Contract<Erc721SoulboundWrapperExample>,
+        underlying: Contract<Erc721>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let underlying_address = underlying.address();
+        contract.init(alice, |contract| {
+            contract.wrapper.underlying.set(underlying_address);
+            contract
+                .soulbound
+                ._lock_collection()
+                .expect("should lock the collection");
+        });
+        underlying.sender(alice)._mint(alice, TOKEN_ID).expect("should mint");
+        underlying
+            .sender(alice)
+            .approve(contract.address(), TOKEN_ID)
+            .expect("should approve the wrapper");
+
+        contract
+            .sender(alice)
+            .deposit_for(alice, vec![TOKEN_ID])
+            .expect("should deposit");
+        assert!(contract.sender(alice).locked(TOKEN_ID).expect("should exist"));
+
+        let err = contract
+            .sender(alice)
+            .transfer_from(alice, bob, TOKEN_ID)
+            .expect_err("should not transfer a soulbound token");
+        assert_eq!(
+            err,
+            Vec::<u8>::from(soulbound::Error::LockedToken(
+                soulbound::ERC5192LockedToken { token_id: TOKEN_ID }
+            ))
+        );
+
+        contract
+            .sender(alice)
+            .withdraw_to(alice, vec![TOKEN_ID])
+            .expect("should withdraw a soulbound token");
+        assert_eq!(
+            alice,
+            underlying.sender(alice).owner_of(TOKEN_ID).expect("should exist")
+        );
+    }
+}
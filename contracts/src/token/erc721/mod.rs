@@ -18,7 +18,8 @@ use crate::utils::{
 
 pub mod extensions;
 mod receiver;
-pub use receiver::IERC721Receiver;
+pub mod utils;
+pub use receiver::{IERC721Receiver, IErc721Receiver};
 
 /// The expected value returned from [`IERC721Receiver::on_erc_721_received`].
 pub const RECEIVER_FN_SELECTOR: [u8; 4] =
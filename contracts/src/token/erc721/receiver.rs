@@ -4,7 +4,9 @@
 #![cfg_attr(coverage_nightly, coverage(off))]
 use alloc::vec;
 
-use stylus_sdk::stylus_proc::sol_interface;
+use alloy_primitives::{Address, FixedBytes, U256};
+use openzeppelin_stylus_proc::interface_id;
+use stylus_sdk::{abi::Bytes, stylus_proc::sol_interface};
 
 sol_interface! {
     /// [`super::Erc721`] token receiver interface.
@@ -38,3 +40,32 @@ sol_interface! {
         ) external returns (bytes4);
     }
 }
+
+/// Interface for any contract that wants to support safe transfers from
+/// [`super::Erc721`] asset contracts, as the Rust counterpart of
+/// [`IERC721Receiver`].
+#[interface_id]
+pub trait IErc721Receiver {
+    /// This function is called whenever an [`super::Erc721`] `token_id`
+    /// token is transferred to this contract via
+    /// [`super::IErc721::safe_transfer_from`].
+    ///
+    /// It must return [`super::RECEIVER_FN_SELECTOR`] to confirm the token
+    /// transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `operator` - Account of the operator.
+    /// * `from` - Account of the sender.
+    /// * `token_id` - Token id as a number.
+    /// * `data` - Additional data with no specified format.
+    #[selector(name = "onERC721Received")]
+    fn on_erc721_received(
+        &mut self,
+        operator: Address,
+        from: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> FixedBytes<4>;
+}
@@ -0,0 +1,116 @@
+//! Implementation of the [`IErc721Receiver`] trait.
+//!
+//! Accepts all token transfers. Make sure the contract is able to use its
+//! token with [`crate::token::erc721::IErc721::safe_transfer_from`],
+//! [`crate::token::erc721::IErc721::approve`] or
+//! [`crate::token::erc721::IErc721::set_approval_for_all`], otherwise the
+//! received tokens will be locked forever.
+
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use stylus_sdk::{
+    abi::Bytes,
+    prelude::{public, storage},
+};
+
+use crate::{
+    token::erc721::{IErc721Receiver, RECEIVER_FN_SELECTOR},
+    utils::introspection::erc165::{Erc165, IErc165},
+};
+
+/// State of an [`Erc721Holder`] contract.
+#[storage]
+pub struct Erc721Holder {}
+
+#[public]
+impl IErc721Receiver for Erc721Holder {
+    #[selector(name = "onERC721Received")]
+    fn on_erc721_received(
+        &mut self,
+        _operator: Address,
+        _from: Address,
+        _token_id: U256,
+        _data: Bytes,
+    ) -> FixedBytes<4> {
+        RECEIVER_FN_SELECTOR.into()
+    }
+}
+
+impl IErc165 for Erc721Holder {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IErc721Receiver>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{fixed_bytes, uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::TopLevelStorage;
+
+    use super::{Erc721Holder, IErc721Receiver};
+    use crate::{
+        token::erc721::{Erc721, IErc721},
+        utils::introspection::erc165::IErc165,
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    unsafe impl TopLevelStorage for Erc721Holder {}
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <Erc721Holder as IErc721Receiver>::INTERFACE_ID;
+        let expected = 0x150b_7a02;
+        assert_eq!(actual, expected);
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc721Holder::supports_interface(fixed_bytes!("150b7a02")));
+        assert!(Erc721Holder::supports_interface(fixed_bytes!("01ffc9a7")));
+        assert!(!Erc721Holder::supports_interface(fixed_bytes!("80ac58cd")));
+    }
+
+    #[motsu::test]
+    fn receives_safe_mint(
+        erc721: Contract<Erc721>,
+        holder: Contract<Erc721Holder>,
+        alice: Address,
+    ) {
+        erc721
+            .sender(alice)
+            ._safe_mint(holder.address(), TOKEN_ID, &vec![0, 1, 2, 3].into())
+            .expect("should accept the token");
+
+        let owner = erc721
+            .sender(alice)
+            .owner_of(TOKEN_ID)
+            .expect("should return the owner of the token");
+        assert_eq!(owner, holder.address());
+    }
+
+    #[motsu::test]
+    fn receives_safe_transfer(
+        erc721: Contract<Erc721>,
+        holder: Contract<Erc721Holder>,
+        alice: Address,
+    ) {
+        erc721
+            .sender(alice)
+            ._mint(alice, TOKEN_ID)
+            .expect("should mint a token to Alice");
+
+        erc721
+            .sender(alice)
+            .safe_transfer_from(alice, holder.address(), TOKEN_ID)
+            .expect("should accept the token");
+
+        let balance =
+            erc721.sender(alice).balance_of(holder.address()).unwrap();
+        assert_eq!(balance, uint!(1_U256));
+    }
+}
@@ -1,4 +1,6 @@
 //! Token standards.
+pub mod common;
 pub mod erc1155;
 pub mod erc20;
+pub mod erc6909;
 pub mod erc721;
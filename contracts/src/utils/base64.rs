@@ -0,0 +1,70 @@
+//! Base64 encoding, as defined in [RFC 4648], to build data URIs on-chain.
+//!
+//! [RFC 4648]: https://datatracker.ietf.org/doc/html/rfc4648
+use alloc::string::String;
+
+/// Standard Base64 alphabet.
+const TABLE: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Padding character, appended so that the output length is a multiple of 4.
+const PAD: u8 = b'=';
+
+/// Encodes `data` to a padded Base64 string, using the standard alphabet.
+///
+/// # Arguments
+///
+/// * `data` - Bytes to encode.
+#[must_use]
+pub fn encode(data: impl AsRef<[u8]>) -> String {
+    let data = data.as_ref();
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or_default();
+        let b2 = chunk.get(2).copied().unwrap_or_default();
+
+        encoded.push(char::from(TABLE[usize::from(b0 >> 2)]));
+        encoded.push(char::from(
+            TABLE[usize::from(((b0 & 0x03) << 4) | (b1 >> 4))],
+        ));
+        encoded.push(char::from(if chunk.len() > 1 {
+            TABLE[usize::from(((b1 & 0x0f) << 2) | (b2 >> 6))]
+        } else {
+            PAD
+        }));
+        encoded.push(char::from(if chunk.len() > 2 {
+            TABLE[usize::from(b2 & 0x3f)]
+        } else {
+            PAD
+        }));
+    }
+
+    encoded
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::encode;
+
+    #[test]
+    fn encodes_rfc_4648_test_vectors() {
+        assert_eq!(encode(""), "");
+        assert_eq!(encode("f"), "Zg==");
+        assert_eq!(encode("fo"), "Zm8=");
+        assert_eq!(encode("foo"), "Zm9v");
+        assert_eq!(encode("foob"), "Zm9vYg==");
+        assert_eq!(encode("fooba"), "Zm9vYmE=");
+        assert_eq!(encode("foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn encodes_every_byte_value() {
+        let data: Vec<u8> = (0..=u8::MAX).collect();
+        let encoded = encode(&data);
+        assert_eq!(encoded.len(), 344);
+        assert!(encoded.starts_with("AAECAwQFBgcICQoL"));
+        assert!(encoded.ends_with("7/P3+/w=="));
+    }
+}
@@ -0,0 +1,54 @@
+//! JSON utilities to build token metadata on-chain.
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Escapes `value` so that it can be embedded in a JSON string, as defined
+/// in [RFC 8259].
+///
+/// Quotation marks, reverse solidi and control characters are escaped, every
+/// other character is kept as is.
+///
+/// [RFC 8259]: https://datatracker.ietf.org/doc/html/rfc8259#section-7
+///
+/// # Arguments
+///
+/// * `value` - String to escape.
+#[must_use]
+pub fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{08}' => escaped.push_str("\\b"),
+            '\u{0c}' => escaped.push_str("\\f"),
+            c if u32::from(c) < 0x20 => {
+                write!(escaped, "\\u{:04x}", u32::from(c))
+                    .expect("should write to a string");
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::escape;
+
+    #[test]
+    fn keeps_plain_strings() {
+        assert_eq!(escape("Stylus #1 ✓"), "Stylus #1 ✓");
+    }
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(
+            escape("\"quoted\" \\ line\nbreak\ttab\u{01}"),
+            "\\\"quoted\\\" \\\\ line\\nbreak\\ttab\\u0001"
+        );
+    }
+}
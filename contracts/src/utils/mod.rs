@@ -1,12 +1,15 @@
 //! Common Smart Contracts utilities.
+pub mod base64;
 pub mod cryptography;
 pub mod introspection;
+pub mod json;
 pub mod math;
 pub mod metadata;
 pub mod nonces;
 pub mod pausable;
 pub mod reentrant_call_handler;
 pub mod structs;
+pub mod transient;
 
 pub use metadata::Metadata;
 pub use pausable::Pausable;
@@ -18,6 +18,7 @@ use alloc::vec::Vec;
 
 pub use sol::*;
 use stylus_sdk::{
+    call::MethodError,
     evm, msg,
     prelude::storage,
     storage::StorageBool,
@@ -64,6 +65,12 @@ pub enum Error {
     ExpectedPause(ExpectedPause),
 }
 
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
 /// State of a [`Pausable`] Contract.
 #[storage]
 pub struct Pausable {
@@ -84,6 +84,70 @@ impl BitMap {
     }
 
     /// Get mask of value in the bucket.
+    /// Sets every bit from `from` to `to`, both inclusive, writing each
+    /// 256-bit bucket of the range only once.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - index of the first boolean value that should be set `true`.
+    /// * `to` - index of the last boolean value that should be set `true`.
+    pub fn set_range(&mut self, from: U256, to: U256) {
+        let mut bucket = Self::get_bucket(from);
+        let last_bucket = Self::get_bucket(to);
+        loop {
+            let mask = Self::get_range_mask(bucket, from, to);
+            let mut value = self.data.setter(bucket);
+            let prev = value.get();
+            value.set(prev | mask);
+
+            if bucket == last_bucket {
+                break;
+            }
+            bucket += ONE;
+        }
+    }
+
+    /// Returns the lowest index from `from` to `to`, both inclusive, whose bit
+    /// is set, reading each 256-bit bucket of the range only once.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - index of the first boolean value to check.
+    /// * `to` - index of the last boolean value to check.
+    #[must_use]
+    pub fn first_set_in_range(&self, from: U256, to: U256) -> Option<U256> {
+        let mut bucket = Self::get_bucket(from);
+        let last_bucket = Self::get_bucket(to);
+        loop {
+            let mask = Self::get_range_mask(bucket, from, to);
+            let value = self.data.get(bucket) & mask;
+            if value != U256::ZERO {
+                return Some(
+                    (bucket << 8) + U256::from(value.trailing_zeros()),
+                );
+            }
+
+            if bucket == last_bucket {
+                return None;
+            }
+            bucket += ONE;
+        }
+    }
+
+    fn get_range_mask(bucket: U256, from: U256, to: U256) -> U256 {
+        let low = if bucket == Self::get_bucket(from) {
+            (from & HEX_FF).to::<usize>()
+        } else {
+            0
+        };
+        let high = if bucket == Self::get_bucket(to) {
+            (to & HEX_FF).to::<usize>()
+        } else {
+            255
+        };
+        (U256::MAX >> (255 - high)) & (U256::MAX << low)
+    }
+
     fn get_mask(index: U256) -> U256 {
         ONE << (index & HEX_FF)
     }
@@ -144,4 +208,37 @@ mod tests {
             prop_assert!(!bit_map.get(value));
         });
     }
+
+    #[motsu::test]
+    fn set_range_values(bit_map: Contract<BitMap>, alice: Address) {
+        let from = U256::from(250);
+        let to = U256::from(600);
+        bit_map.sender(alice).set_range(from, to);
+
+        let bit_map = bit_map.sender(alice);
+        assert!(!bit_map.get(from - U256::from(1)));
+        assert!(!bit_map.get(to + U256::from(1)));
+        for index in 250..=600 {
+            assert!(bit_map.get(U256::from(index)));
+        }
+    }
+
+    #[motsu::test]
+    fn first_set_in_range_value() {
+        proptest!(|(value: U256, alice: Address)| {
+            let bit_map = Contract::<BitMap>::new();
+            let mut bit_map = bit_map.sender(alice);
+            let from = value.saturating_sub(U256::from(300));
+            let to = value.saturating_add(U256::from(300));
+            prop_assert!(bit_map.first_set_in_range(from, to).is_none());
+
+            bit_map.set(value);
+            prop_assert!(bit_map.first_set_in_range(from, to) == Some(value));
+            if value > U256::ZERO {
+                prop_assert!(bit_map
+                    .first_set_in_range(from, value - U256::from(1))
+                    .is_none());
+            }
+        });
+    }
 }
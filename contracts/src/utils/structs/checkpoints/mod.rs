@@ -218,6 +218,77 @@ impl<S: Size> Trace<S> {
         (guard.key.get(), guard.value.get())
     }
 
+    /// Returns the position of the first (oldest) checkpoint with key greater
+    /// or equal than the search key, or [`Self::length`] if there is none.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the checkpoint's state.
+    /// * `key` - Checkpoint's key to lookup.
+    pub fn lower_position(&self, key: S::Key) -> U256 {
+        self._lower_binary_lookup(key, U256::ZERO, self.length())
+    }
+
+    /// Splits the first (oldest) checkpoint with key greater or equal than
+    /// `key` in two, so that a checkpoint ends exactly at `key`. Both parts
+    /// keep the value of the split checkpoint, so lookups are unchanged.
+    ///
+    /// Returns the position of the checkpoint with key `key`, or [`None`] if
+    /// there is no checkpoint with key greater or equal than `key`.
+    ///
+    /// NOTE: Moves every later checkpoint one position up, so the cost grows
+    /// with the number of later checkpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the checkpoint's state.
+    /// * `key` - Key the new checkpoint should end at.
+    pub fn split(&mut self, key: S::Key) -> Option<U256> {
+        let len = self.length();
+        let pos = self._lower_binary_lookup(key, U256::ZERO, len);
+        if pos == len {
+            return None;
+        }
+
+        let checkpoint = self._index(pos);
+        let (pos_key, value) = (checkpoint.key.get(), checkpoint.value.get());
+        if pos_key == key {
+            return Some(pos);
+        }
+
+        self.checkpoints.grow();
+        let mut i = len;
+        while i > pos {
+            let previous = self._index(i - uint!(1_U256));
+            let (previous_key, previous_value) =
+                (previous.key.get(), previous.value.get());
+            let mut current = self._index_mut(i);
+            current.key.set(previous_key);
+            current.value.set(previous_value);
+            i -= uint!(1_U256);
+        }
+
+        let mut checkpoint = self._index_mut(pos);
+        checkpoint.key.set(key);
+        checkpoint.value.set(value);
+        Some(pos)
+    }
+
+    /// Sets the value of the checkpoint at position `pos`.
+    ///
+    /// # Panics
+    ///
+    /// If `pos` exceeds [`Self::length`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the checkpoint's state.
+    /// * `pos` - Index of the checkpoint.
+    /// * `value` - New value of the checkpoint.
+    pub fn set_value_at(&mut self, pos: U256, value: S::Value) {
+        self._index_mut(pos).value.set(value);
+    }
+
     /// Pushes a (`key`, `value`) pair into an ordered list of checkpoints,
     /// either by inserting a new checkpoint, or by updating the last one.
     /// Returns the previous value and the new value as an ordered pair.
@@ -660,4 +731,48 @@ mod tests {
             )
         ));
     }
+
+    #[motsu::test]
+    fn split(checkpoint: Contract<Trace<S160>>, alice: Address) {
+        checkpoint.sender(alice).push(uint!(9_U96), uint!(1_U160)).unwrap();
+        checkpoint.sender(alice).push(uint!(19_U96), uint!(2_U160)).unwrap();
+
+        assert_eq!(
+            checkpoint.sender(alice).split(uint!(4_U96)),
+            Some(uint!(0_U256))
+        );
+        assert_eq!(
+            checkpoint.sender(alice).split(uint!(9_U96)),
+            Some(uint!(1_U256))
+        );
+        assert_eq!(checkpoint.sender(alice).split(uint!(20_U96)), None);
+
+        assert_eq!(checkpoint.sender(alice).length(), uint!(3_U256));
+        assert_eq!(
+            checkpoint.sender(alice).at(uint!(0_U32)),
+            (uint!(4_U96), uint!(1_U160))
+        );
+        assert_eq!(
+            checkpoint.sender(alice).at(uint!(1_U32)),
+            (uint!(9_U96), uint!(1_U160))
+        );
+        assert_eq!(
+            checkpoint.sender(alice).at(uint!(2_U32)),
+            (uint!(19_U96), uint!(2_U160))
+        );
+        assert_eq!(
+            checkpoint.sender(alice).lower_lookup(uint!(7_U96)),
+            uint!(1_U160)
+        );
+
+        checkpoint.sender(alice).set_value_at(uint!(1_U256), uint!(3_U160));
+        assert_eq!(
+            checkpoint.sender(alice).lower_lookup(uint!(7_U96)),
+            uint!(3_U160)
+        );
+        assert_eq!(
+            checkpoint.sender(alice).lower_position(uint!(10_U96)),
+            uint!(2_U256)
+        );
+    }
 }
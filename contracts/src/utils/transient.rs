@@ -0,0 +1,154 @@
+//! Access to transient storage, as defined in [EIP-1153].
+//!
+//! Transient storage behaves like contract storage, except that it is
+//! discarded at the end of every transaction. Reading and writing it is
+//! priced like warm storage access, and it never leaves state behind.
+//!
+//! The Stylus VM exposes transient storage through the
+//! `transient_load_bytes32` and `transient_store_bytes32` host functions,
+//! which are not wrapped by the Stylus SDK yet.
+//!
+//! [EIP-1153]: https://eips.ethereum.org/EIPS/eip-1153
+
+use alloy_primitives::{Address, B256, U256};
+
+/// Returns the 32-byte value stored at `key` in the transient storage of the
+/// current contract. Unset keys read as zero.
+///
+/// # Arguments
+///
+/// * `key` - Transient storage slot to read.
+#[must_use]
+pub fn load(key: B256) -> B256 {
+    let mut value = B256::ZERO;
+    // SAFETY: `key` and `value` are both 32 bytes long, which is what the
+    // host function reads from and writes to.
+    unsafe { hostio::transient_load_bytes32(key.as_ptr(), value.as_mut_ptr()) };
+    value
+}
+
+/// Stores `value` at `key` in the transient storage of the current contract.
+///
+/// # Arguments
+///
+/// * `key` - Transient storage slot to write.
+/// * `value` - Value to store.
+pub fn store(key: B256, value: B256) {
+    // SAFETY: `key` and `value` are both 32 bytes long, which is what the
+    // host function reads from.
+    unsafe { hostio::transient_store_bytes32(key.as_ptr(), value.as_ptr()) };
+}
+
+/// Returns the slot of `key` in a mapping rooted at `slot`, following the
+/// Solidity storage layout (`keccak256(key . slot)`).
+///
+/// # Arguments
+///
+/// * `slot` - Slot of the mapping.
+/// * `key` - Mapping key.
+#[must_use]
+pub fn derive_mapping(slot: B256, key: Address) -> B256 {
+    let mut preimage = [0u8; 64];
+    preimage[12..32].copy_from_slice(key.as_slice());
+    preimage[32..].copy_from_slice(slot.as_slice());
+    alloy_primitives::keccak256(preimage)
+}
+
+/// Returns the [`U256`] stored at `key` in transient storage.
+///
+/// # Arguments
+///
+/// * `key` - Transient storage slot to read.
+#[must_use]
+pub fn load_u256(key: B256) -> U256 {
+    U256::from_be_bytes(load(key).0)
+}
+
+/// Stores a [`U256`] `value` at `key` in transient storage.
+///
+/// # Arguments
+///
+/// * `key` - Transient storage slot to write.
+/// * `value` - Value to store.
+pub fn store_u256(key: B256, value: U256) {
+    store(key, B256::from(value));
+}
+
+#[cfg(not(all(test, feature = "std")))]
+mod hostio {
+    #[link(wasm_import_module = "vm_hooks")]
+    extern "C" {
+        pub(super) fn transient_load_bytes32(key: *const u8, dest: *mut u8);
+        pub(super) fn transient_store_bytes32(key: *const u8, value: *const u8);
+    }
+}
+
+/// In-memory replacement of the transient storage host functions, which are
+/// not provided by the unit-test host.
+#[cfg(all(test, feature = "std"))]
+pub(crate) mod hostio {
+    use std::{cell::RefCell, collections::HashMap};
+
+    std::thread_local! {
+        static TRANSIENT: RefCell<HashMap<[u8; 32], [u8; 32]>> =
+            RefCell::new(HashMap::new());
+    }
+
+    /// # Safety
+    ///
+    /// `key` must be valid for reads and `dest` for writes of 32 bytes.
+    pub(super) unsafe fn transient_load_bytes32(key: *const u8, dest: *mut u8) {
+        let key = *key.cast::<[u8; 32]>();
+        let value = TRANSIENT
+            .with(|t| t.borrow().get(&key).copied().unwrap_or_default());
+        core::ptr::copy_nonoverlapping(value.as_ptr(), dest, 32);
+    }
+
+    /// # Safety
+    ///
+    /// `key` and `value` must be valid for reads of 32 bytes.
+    pub(super) unsafe fn transient_store_bytes32(
+        key: *const u8,
+        value: *const u8,
+    ) {
+        let key = *key.cast::<[u8; 32]>();
+        let value = *value.cast::<[u8; 32]>();
+        TRANSIENT.with(|t| t.borrow_mut().insert(key, value));
+    }
+
+    /// Discards all transient storage, as happens at the end of a
+    /// transaction.
+    pub(crate) fn end_transaction() {
+        TRANSIENT.with(|t| t.borrow_mut().clear());
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{b256, Address, B256, U256};
+
+    use super::*;
+
+    #[test]
+    fn load_returns_stored_value() {
+        let key = b256!(
+            "0000000000000000000000000000000000000000000000000000000000000001"
+        );
+        assert_eq!(B256::ZERO, load(key));
+
+        store_u256(key, U256::from(42));
+        assert_eq!(U256::from(42), load_u256(key));
+
+        hostio::end_transaction();
+        assert_eq!(U256::ZERO, load_u256(key));
+    }
+
+    #[test]
+    fn derive_mapping_matches_solidity_layout() {
+        // keccak256(abi.encode(address(0), bytes32(0)))
+        let expected = b256!(
+            "ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"
+        );
+        assert_eq!(expected, derive_mapping(B256::ZERO, Address::ZERO));
+    }
+}
@@ -5,28 +5,45 @@ use alloc::vec::Vec;
 
 use alloy_primitives::{Address, U256};
 use openzeppelin_stylus::token::erc20::{
-    extensions::{Erc20FlashMint, IErc3156FlashLender},
+    extensions::{Erc20FlashMint, IErc3156FlashLender, IFlashMintHooks},
     Erc20,
 };
 use stylus_sdk::{
     abi::Bytes,
     prelude::{entrypoint, public, storage},
+    storage::{StorageAddress, StorageU256},
 };
 
+#[storage]
+struct FlashFee {
+    flash_fee_value: StorageU256,
+    flash_fee_receiver_address: StorageAddress,
+}
+
+impl IFlashMintHooks for FlashFee {
+    fn _flash_fee(&self, _token: Address, _value: U256) -> U256 {
+        self.flash_fee_value.get()
+    }
+
+    fn _flash_fee_receiver(&self) -> Address {
+        self.flash_fee_receiver_address.get()
+    }
+}
+
 #[entrypoint]
 #[storage]
 struct Erc20FlashMintExample {
     #[borrow]
     erc20: Erc20,
     #[borrow]
-    flash_mint: Erc20FlashMint,
+    flash_mint: Erc20FlashMint<FlashFee>,
 }
 
 #[public]
 #[inherit(Erc20)]
 impl Erc20FlashMintExample {
     fn max_flash_loan(&self, token: Address) -> U256 {
-        self.flash_mint.max_flash_loan(token, &self.erc20)
+        self.flash_mint.max_flash_loan(token, &self.erc20, None)
     }
 
     fn flash_fee(&self, token: Address, value: U256) -> Result<U256, Vec<u8>> {
@@ -46,6 +63,7 @@ impl Erc20FlashMintExample {
             value,
             data,
             &mut self.erc20,
+            None,
         )?)
     }
 
@@ -51,8 +51,14 @@ impl Erc721MetadataExample {
         self.uri_storage._set_token_uri(token_id, token_uri)
     }
 
+    #[selector(name = "setBaseURI")]
+    pub fn set_base_uri(&mut self, base_uri: String) {
+        self.metadata._set_base_uri(base_uri)
+    }
+
     pub fn supports_interface(interface_id: FixedBytes<4>) -> bool {
         Erc721::supports_interface(interface_id)
             || Metadata::supports_interface(interface_id)
+            || UriStorage::supports_interface(interface_id)
     }
 }
@@ -24,6 +24,7 @@ sol!(
         #[derive(Debug)]
         function tokenURI(uint256 tokenId) external view returns (string memory tokenURI);
         function setTokenURI(uint256 tokenId, string memory tokenURI) external;
+        function setBaseURI(string memory baseURI) external;
         function supportsInterface(bytes4 interfaceId) external pure returns (bool);
 
         error ERC721InvalidOwner(address owner);
@@ -43,5 +44,7 @@ sol!(
         event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
         #[derive(Debug, PartialEq)]
         event MetadataUpdate(uint256 tokenId);
+        #[derive(Debug, PartialEq)]
+        event BatchMetadataUpdate(uint256 fromTokenId, uint256 toTokenId);
    }
 );
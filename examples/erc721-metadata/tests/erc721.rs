@@ -272,6 +272,40 @@ async fn return_token_uri_after_burn_and_remint(
     Ok(())
 }
 
+#[e2e::test]
+async fn set_base_uri_emits_batch_metadata_update(
+    alice: Account,
+) -> eyre::Result<()> {
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(ctr(
+            "https://github.com/OpenZeppelin/rust-contracts-stylus/",
+        ))
+        .deploy()
+        .await?
+        .address()?;
+
+    let contract = Erc721::new(contract_addr, &alice.wallet);
+
+    let token_id = random_token_id();
+    watch!(contract.mint(alice.address(), token_id))?;
+
+    let base_uri = "https://example.com/revealed/";
+    let receipt = receipt!(contract.setBaseURI(base_uri.to_owned()))?;
+
+    assert!(receipt.emits(Erc721::BatchMetadataUpdate {
+        fromTokenId: U256::ZERO,
+        toTokenId: U256::MAX,
+    }));
+
+    let Erc721::tokenURIReturn { tokenURI } =
+        contract.tokenURI(token_id).call().await?;
+
+    assert_eq!(base_uri.to_owned() + &token_id.to_string(), tokenURI);
+
+    Ok(())
+}
+
 // ============================================================================
 // Integration Tests: ERC-165 Support Interface
 // ============================================================================
@@ -297,6 +331,15 @@ async fn supports_interface(alice: Account) -> eyre::Result<()> {
 
     assert!(supports_interface);
 
+    let erc4906_interface_id: u32 = 0x49064906;
+    let supports_interface = contract
+        .supportsInterface(erc4906_interface_id.into())
+        .call()
+        .await?
+        ._0;
+
+    assert!(supports_interface);
+
     // other tests verify that other ERC-721 interfaces are implemented
 
     Ok(())